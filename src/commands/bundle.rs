@@ -75,14 +75,8 @@ fn build_simple_import_graph(
         visited.insert(current.clone());
 
         // Get imports for current file
-        let imports: Vec<PathBuf> = doc
-            .imports
-            .iter()
-            .map(|imp| {
-                
-                base_dir.join(&imp.path)
-            })
-            .collect();
+        let imports: Vec<PathBuf> =
+            doc.imports.iter().map(|imp| base_dir.join(&imp.path)).collect();
 
         graph.insert(current.clone(), imports.clone());
 
@@ -102,9 +96,10 @@ fn detect_cycle(graph: &HashMap<PathBuf, Vec<PathBuf>>) -> Option<Vec<String>> {
 
     for node in graph.keys() {
         if !visited.contains(node)
-            && let Some(cycle) = dfs_cycle(node, graph, &mut visited, &mut rec_stack) {
-                return Some(cycle);
-            }
+            && let Some(cycle) = dfs_cycle(node, graph, &mut visited, &mut rec_stack)
+        {
+            return Some(cycle);
+        }
     }
 
     None
@@ -144,7 +139,7 @@ fn format_as_mon(doc: &MonDocument, _minify: bool) -> Result<String> {
     use crate::formatter::{FormatConfig, Formatter};
 
     // TODO: Implement minified formatting
-    let formatter = Formatter::new(FormatConfig::default());
+    let _formatter = Formatter::new(FormatConfig::default());
 
     // Format the document back to MON
     // Since we have the original parsed doc, we can format it
@@ -172,7 +167,6 @@ fn format_as_mon(doc: &MonDocument, _minify: bool) -> Result<String> {
 
 fn format_as_json(doc: &MonDocument) -> Result<String> {
     // Use mon-core's AnalysisResult for serialization
-    
 
     // We need to re-analyze the document to get proper serialization
     // This is a workaround since to_value is pub(crate)
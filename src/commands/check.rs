@@ -3,6 +3,7 @@ use colored::*;
 use miette::Result;
 use mon_core::parser::Parser;
 use std::fs;
+use std::path::Path;
 
 pub fn run(file: &str, lint: bool, as_json: bool) -> Result<()> {
     println!("Checking {}...", file);
@@ -17,8 +18,14 @@ pub fn run(file: &str, lint: bool, as_json: bool) -> Result<()> {
 
     // If lint flag is enabled, run linter
     if lint {
-        let linter = Linter::new(LintConfig::default());
-        let lint_result = linter.lint(&doc, &content)?;
+        let current_dir = std::env::current_dir()
+            .map_err(|e| miette::miette!("Failed to read current directory: {}", e))?;
+        let (config, _) = LintConfig::discover(&current_dir)?;
+        for warning in &config.warnings {
+            eprintln!("{} {}", "warning:".yellow().bold(), warning);
+        }
+        let linter = Linter::new(config);
+        let lint_result = linter.lint_file(&doc, &content, Path::new(file))?;
 
         if as_json {
             // Output JSON
@@ -83,10 +90,9 @@ pub fn run(file: &str, lint: bool, as_json: bool) -> Result<()> {
                     println!();
                     println!(
                         "{}",
-                        "Run 'mon check --explain <CODE>' for detailed information".to_string()
-                            .dimmed()
+                        "Run 'mon explain <CODE>' for detailed information".to_string().dimmed()
                     );
-                    println!("{}", "Configure rules in .monlint.mon".to_string().dimmed());
+                    println!("{}", "Configure rules in .moncfg.mon".to_string().dimmed());
                 }
 
                 if !errors.is_empty() {
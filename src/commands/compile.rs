@@ -316,10 +316,12 @@ fn create_readme(base_name: &str, input_file: &str, has_docs: bool, null_count:
     );
 
     if has_docs {
-        content.push_str(&"└── docs/\n\
+        content.push_str(
+            "└── docs/\n\
             ├── structure.md     # Data structure documentation\n\
             └── stats.json       # Statistics and metadata\n\
-            ```\n\n".to_string());
+            ```\n\n",
+        );
     } else {
         content.push_str("```\n\n");
     }
@@ -381,7 +383,7 @@ fn create_readme(base_name: &str, input_file: &str, has_docs: bool, null_count:
 
 fn generate_documentation(
     value: &JsonValue,
-    docs_dir: &std::path::PathBuf,
+    docs_dir: &Path,
     base_name: &str,
     input_file: &str,
     null_count: usize,
@@ -0,0 +1,202 @@
+//! `mon explain`: what a lint code or error code means, with an example.
+
+use crate::errors::ERROR_CODES;
+use crate::linter::rules::LintRule;
+use colored::*;
+use miette::Result;
+use serde::Serialize;
+use std::fmt::Write;
+
+#[derive(Debug, Serialize)]
+struct Entry {
+    code: &'static str,
+    /// Rule name, for lint codes
+    rule: Option<&'static str>,
+    title: &'static str,
+    description: &'static str,
+    severity: &'static str,
+    /// `.moncfg.mon` key that configures the rule, for lint codes
+    config_key: Option<&'static str>,
+    example: Example,
+}
+
+#[derive(Debug, Serialize)]
+struct Example {
+    bad: &'static str,
+    good: &'static str,
+}
+
+pub fn run(code: Option<String>, list: bool, format: String) -> Result<()> {
+    let as_json = match format.as_str() {
+        "text" => false,
+        "json" => true,
+        _ => {
+            return Err(miette::miette!(
+                help = "Use one of: text, json",
+                "Unknown output format '{}'",
+                format
+            ));
+        }
+    };
+
+    let entries = catalogue();
+    let out = match code {
+        Some(code) if !list => {
+            let entry = find(&entries, &code)?;
+            if as_json { to_json(entry)? } else { render(entry) }
+        }
+        _ if as_json => to_json(&entries)?,
+        _ => render_table(&entries),
+    };
+    print!("{}", out);
+    Ok(())
+}
+
+/// Every lint code in code order, then the error codes.
+fn catalogue() -> Vec<Entry> {
+    let lints = LintRule::ALL.iter().map(|rule| {
+        let code = rule.code();
+        let (bad, good) = code.example();
+        Entry {
+            code: code.code(),
+            rule: Some(rule.name()),
+            title: code.title(),
+            description: code.description(),
+            severity: code.severity().label(),
+            config_key: Some(code.config_key()),
+            example: Example { bad, good },
+        }
+    });
+    let errors = ERROR_CODES.iter().map(|error| Entry {
+        code: error.code,
+        rule: None,
+        title: error.title,
+        description: error.description,
+        severity: "error",
+        config_key: None,
+        example: Example { bad: error.example.0, good: error.example.1 },
+    });
+    lints.chain(errors).collect()
+}
+
+/// Looks up a code (`LINT2001`, `mon::syntax_error`) or a rule name
+/// (`unused_anchor`, or `unused-anchor`), ignoring case.
+fn find<'a>(entries: &'a [Entry], code: &str) -> Result<&'a Entry> {
+    let code = code.trim();
+    let name = code.to_ascii_lowercase().replace('-', "_");
+    entries
+        .iter()
+        .find(|entry| entry.code.eq_ignore_ascii_case(code) || entry.rule == Some(name.as_str()))
+        .ok_or_else(|| {
+            miette::miette!(
+                help = "Run 'mon explain --list' to see every code",
+                "Unknown code '{}'",
+                code
+            )
+        })
+}
+
+fn render(entry: &Entry) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "{}: {}", entry.code.cyan().bold(), entry.title.bold());
+    let _ = writeln!(out);
+    let _ = writeln!(out, "{}", entry.description);
+    let _ = writeln!(out);
+    if let Some(rule) = entry.rule {
+        let _ = writeln!(out, "  {:<18}{}", "Rule:", rule);
+    }
+    let _ = writeln!(out, "  {:<18}{}", "Default severity:", entry.severity);
+    if let Some(key) = entry.config_key {
+        let _ = writeln!(out, "  {:<18}{}", "Config key:", key);
+    }
+
+    for (label, example) in [("Bad", entry.example.bad), ("Good", entry.example.good)] {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "{}:", label.bold());
+        for line in example.lines() {
+            let _ = writeln!(out, "    {}", line);
+        }
+    }
+    out
+}
+
+/// One line per code: code, default severity, rule name and title.
+fn render_table(entries: &[Entry]) -> String {
+    let width = |column: fn(&Entry) -> &str| {
+        entries.iter().map(|entry| column(entry).len()).max().unwrap_or_default()
+    };
+    let code_width = width(|entry| entry.code);
+    let rule_width = width(|entry| entry.rule.unwrap_or("-"));
+
+    let mut out = String::new();
+    let header =
+        format!("{:<code_width$}  {:<8}  {:<rule_width$}  TITLE", "CODE", "SEVERITY", "RULE");
+    let _ = writeln!(out, "{}", header.bold());
+    for entry in entries {
+        let _ = writeln!(
+            out,
+            "{:<code_width$}  {:<8}  {:<rule_width$}  {}",
+            entry.code,
+            entry.severity,
+            entry.rule.unwrap_or("-"),
+            entry.title
+        );
+    }
+    out
+}
+
+fn to_json<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| miette::miette!("Failed to serialize codes: {}", e))?;
+    Ok(format!("{}\n", json))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::{DiagnosticCode, LintConfig, Linter};
+    use mon_core::parser::Parser;
+
+    /// Codes reported with every rule on.
+    fn codes(source: &str) -> Vec<DiagnosticCode> {
+        let doc = Parser::new(source).unwrap().parse_document().unwrap();
+        let config = LintConfig::default().with_only_rules(vec!["LINT*".to_string()]).unwrap();
+        let result = Linter::new(config).lint(&doc, source).unwrap();
+        result.diagnostics.iter().map(|d| d.code).collect()
+    }
+
+    #[test]
+    fn test_examples_trigger_their_code() {
+        // Import chains and cycles need more than one file, the size examples are abridged,
+        // and the named constant of the magic number example is a literal too
+        let single_file = [
+            LintRule::MaxNestingDepth,
+            LintRule::UnusedAnchor,
+            LintRule::DuplicateKey,
+            LintRule::ExcessiveSpreads,
+            LintRule::UnusedSuppression,
+            LintRule::OverriddenKey,
+            LintRule::MissingTypeValidation,
+            LintRule::InconsistentNaming,
+            LintRule::EmptyStructure,
+            LintRule::UnusedImport,
+        ];
+        for rule in single_file {
+            let code = rule.code();
+            let (bad, good) = code.example();
+            assert!(codes(bad).contains(&code), "{} bad example", code);
+            assert!(!codes(good).contains(&code), "{} good example", code);
+        }
+    }
+
+    #[test]
+    fn test_find_codes_and_rule_names() {
+        let entries = catalogue();
+        assert_eq!(entries.len(), LintRule::ALL.len() + ERROR_CODES.len());
+
+        assert_eq!(find(&entries, "lint2001").unwrap().rule, Some("unused_anchor"));
+        assert_eq!(find(&entries, "unused-anchor").unwrap().code, "LINT2001");
+        assert_eq!(find(&entries, "MON::SYNTAX_ERROR").unwrap().config_key, None);
+        assert!(find(&entries, "LINT9999").is_err());
+    }
+}
@@ -28,13 +28,14 @@ pub fn run(
             }
         }
     } else {
-        // Try to find .monconfig.mon in current directory
-        if std::path::Path::new(".monconfig.mon").exists() {
-            println!("Found .monconfig.mon, using it");
-            FormatConfig::from_mon_file(".monconfig.mon")?
-        } else {
-            FormatConfig::default()
+        // Look for .monconfig.mon in the current directory and its parents
+        let current_dir = std::env::current_dir()
+            .map_err(|e| miette::miette!("Failed to read current directory: {}", e))?;
+        let (config, path) = FormatConfig::discover(&current_dir)?;
+        if let Some(path) = path {
+            println!("Found {}, using it", path.display());
         }
+        config
     };
 
     // Read source file
@@ -112,7 +112,7 @@ fn create_linter_config_interactive() -> Result<String> {
     println!("  ℹ️  Helps keep objects focused and maintainable");
     println!();
 
-    let max_array_elements: u32 = Input::with_theme(&ColorfulTheme::default())
+    let max_array_items: u32 = Input::with_theme(&ColorfulTheme::default())
         .with_prompt("Maximum array elements  (5-200)")
         .default(50)
         .interact_text()
@@ -166,7 +166,7 @@ fn create_linter_config_interactive() -> Result<String> {
     println!("  ℹ️  Recommends using :: Type for complex objects");
     println!();
 
-    let enforce_consistent_naming = Confirm::with_theme(&ColorfulTheme::default())
+    let enforce_naming_convention = Confirm::with_theme(&ColorfulTheme::default())
         .with_prompt("Enforce consistent naming style?")
         .default(false)
         .interact()
@@ -179,7 +179,7 @@ fn create_linter_config_interactive() -> Result<String> {
     println!("{}", style("ADVANCED").yellow().bold());
     println!("{}", style("─".repeat(60)).dim());
 
-    let max_spreads: u32 = Input::with_theme(&ColorfulTheme::default())
+    let max_spreads_per_object: u32 = Input::with_theme(&ColorfulTheme::default())
         .with_prompt("Maximum spread operations (1-10)")
         .default(3)
         .interact_text()
@@ -188,7 +188,7 @@ fn create_linter_config_interactive() -> Result<String> {
     println!("  ℹ️  Limits ...*spread usage in objects");
     println!();
 
-    let max_import_depth: u32 = Input::with_theme(&ColorfulTheme::default())
+    let max_import_chain_depth: u32 = Input::with_theme(&ColorfulTheme::default())
         .with_prompt("Maximum import chain depth (2-10)")
         .default(5)
         .interact_text()
@@ -216,7 +216,7 @@ fn create_linter_config_interactive() -> Result<String> {
     
     /// Maximum array elements (5-200, default: 50)
     /// Controls LINT1003: Large array warning
-    max_array_elements: {},
+    max_array_items: {},
     
     // ═══════════════════════════════════════════════════
     // CODE QUALITY
@@ -236,7 +236,7 @@ fn create_linter_config_interactive() -> Result<String> {
     
     /// Maximum spread operations (1-10, default: 3)
     /// Controls LINT2003: Excessive spreads
-    max_spreads: {},
+    max_spreads_per_object: {},
     
     // ═══════════════════════════════════════════════════
     // BEST PRACTICES
@@ -248,7 +248,7 @@ fn create_linter_config_interactive() -> Result<String> {
     
     /// Enforce consistent naming (default: false)
     /// Controls LINT3002: Inconsistent naming
-    enforce_consistent_naming: {},
+    enforce_naming_convention: {},
     
     /// Maximum import chain depth (2-10, default: 5)
     /// Controls LINT4001: Deep import chains
@@ -257,14 +257,14 @@ fn create_linter_config_interactive() -> Result<String> {
 "#,
         max_nesting,
         max_object_members,
-        max_array_elements,
+        max_array_items,
         warn_unused_anchors,
         warn_magic_numbers,
         warn_empty_structures,
-        max_spreads,
+        max_spreads_per_object,
         suggest_type_validation,
-        enforce_consistent_naming,
-        max_import_depth
+        enforce_naming_convention,
+        max_import_chain_depth
     );
 
     // Show preview
@@ -502,7 +502,7 @@ import {{ FormatConfig }} from "mon:types/formatter"
     Ok(())
 }
 
-fn create_from_template(template: Template, config_type: ConfigType) -> Result<()> {
+fn create_from_template(template: Template, _config_type: ConfigType) -> Result<()> {
     let content = match template {
         Template::Strict => include_str!("../../templates/unified-strict.mon"),
         Template::Lenient => include_str!("../../templates/unified-lenient.mon"),
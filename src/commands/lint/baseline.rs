@@ -0,0 +1,186 @@
+//! Baseline files, for adopting the linter on existing files.
+//!
+//! `--write-baseline` records the current diagnostics, and `--baseline`
+//! hides the ones it recorded. A diagnostic is identified by its file,
+//! relative to the baseline, its code and its line fingerprint, so it stays
+//! known when lines move. Entries that match nothing are stale: the issue was
+//! fixed, and rewriting the baseline drops them.
+
+use super::{FileResult, line_fingerprints};
+use miette::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Version of the baseline file format.
+const VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BaselineFile {
+    version: u32,
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    /// Path of the linted file, relative to the baseline's directory
+    pub file: String,
+    pub code: String,
+    pub fingerprint: String,
+    /// The diagnostic's message when recorded, for readers only
+    pub message: String,
+}
+
+pub struct Baseline {
+    /// Directory that entry paths are relative to
+    root: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Baseline {
+    /// Records the diagnostics of `results` for a baseline written to `path`.
+    pub fn from_results(path: &Path, results: &[FileResult]) -> Self {
+        let root = root(path);
+        let entries = results
+            .iter()
+            .flat_map(|file| {
+                let key = key(&root, &file.file);
+                file.result.diagnostics.iter().zip(line_fingerprints(file)).map(
+                    move |(diagnostic, fingerprint)| Entry {
+                        file: key.clone(),
+                        code: diagnostic.code.code().to_string(),
+                        fingerprint,
+                        message: diagnostic.message.clone(),
+                    },
+                )
+            })
+            .collect();
+        Self { root, entries }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| miette::miette!("Failed to read baseline {}: {}", path.display(), e))?;
+        let file: BaselineFile = serde_json::from_str(&content).map_err(|e| {
+            miette::miette!(
+                help = "Write a new one with --write-baseline",
+                "Invalid baseline {}: {}",
+                path.display(),
+                e
+            )
+        })?;
+        if file.version != VERSION {
+            return Err(miette::miette!(
+                help = "Write a new one with --write-baseline",
+                "Unsupported baseline version {} in {}",
+                file.version,
+                path.display()
+            ));
+        }
+        Ok(Self { root: root(path), entries: file.entries })
+    }
+
+    pub fn write(self, path: &Path) -> Result<()> {
+        let file = BaselineFile { version: VERSION, entries: self.entries };
+        let json = serde_json::to_string_pretty(&file)
+            .map_err(|e| miette::miette!("Failed to serialize baseline: {}", e))?;
+        fs::write(path, format!("{}\n", json))
+            .map_err(|e| miette::miette!("Failed to write baseline {}: {}", path.display(), e))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Removes the diagnostics recorded in the baseline from `results`.
+    ///
+    /// Returns the stale entries: those of a linted file that matched no
+    /// diagnostic, and those of files that no longer exist.
+    pub fn filter(&self, results: &mut [FileResult]) -> Vec<&Entry> {
+        let mut index: HashMap<(&str, &str, &str), usize> = HashMap::new();
+        for (i, entry) in self.entries.iter().enumerate() {
+            index.insert((&entry.file, &entry.code, &entry.fingerprint), i);
+        }
+        let mut matched = vec![false; self.entries.len()];
+        let mut linted = Vec::new();
+
+        for file in results.iter_mut() {
+            let key = key(&self.root, &file.file);
+            let mut fingerprints = line_fingerprints(file).into_iter();
+            file.result.diagnostics.retain(|diagnostic| {
+                let fingerprint = fingerprints.next().unwrap_or_default();
+                match index.get(&(key.as_str(), diagnostic.code.code(), fingerprint.as_str())) {
+                    Some(&i) => {
+                        matched[i] = true;
+                        false
+                    }
+                    None => true,
+                }
+            });
+            linted.push(key);
+        }
+
+        self.entries
+            .iter()
+            .zip(matched)
+            .filter(|(entry, matched)| {
+                !matched && (linted.contains(&entry.file) || !self.root.join(&entry.file).exists())
+            })
+            .map(|(entry, _)| entry)
+            .collect()
+    }
+}
+
+/// The directory entry paths are relative to: the baseline's own.
+fn root(path: &Path) -> PathBuf {
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf())
+}
+
+/// The entry path of a linted file: relative to `root` when it is inside it,
+/// as given otherwise, with `/` separators either way.
+fn key(root: &Path, file: &str) -> String {
+    let path = fs::canonicalize(file).ok();
+    let relative = path.as_deref().and_then(|path| path.strip_prefix(root).ok());
+    let key = match relative {
+        Some(relative) => relative.to_string_lossy().into_owned(),
+        None => file.to_string(),
+    };
+    key.replace('\\', "/").trim_start_matches("./").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::{LintConfig, Linter};
+    use mon_core::parser::Parser;
+
+    fn lint(file: &Path, source: &str) -> FileResult {
+        fs::write(file, source).unwrap();
+        let doc = Parser::new(source).unwrap().parse_document().unwrap();
+        let result = Linter::new(LintConfig::default()).lint(&doc, source).unwrap();
+        FileResult { file: file.display().to_string(), source: source.to_string(), result }
+    }
+
+    #[test]
+    fn test_baseline_hides_known_diagnostics() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(".monlint-baseline.json");
+        let file = dir.path().join("a.mon");
+
+        let results = [lint(&file, "{\n    &a: 1,\n    &b: 2,\n    k: {},\n}")];
+        Baseline::from_results(&path, &results).write(&path).unwrap();
+        let baseline = Baseline::load(&path).unwrap();
+        assert_eq!(baseline.len(), 3);
+        assert!(baseline.entries.iter().all(|entry| entry.file == "a.mon"));
+
+        // Moved lines stay known; a new issue is shown and a fixed one is stale
+        let mut results = [lint(&file, "{\n    x: 0,\n\n    &b: 2,\n    k: {},\n    &c: 3,\n}")];
+        let stale = baseline.filter(&mut results);
+        let messages: Vec<_> = results[0].result.diagnostics.iter().map(|d| &d.message).collect();
+        assert_eq!(messages, vec!["Anchor 'c' is defined but never used"]);
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].message, "Anchor 'a' is defined but never used");
+    }
+}
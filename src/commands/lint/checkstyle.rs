@@ -0,0 +1,34 @@
+//! Checkstyle XML, read by most CI servers and code review tools.
+
+use super::{FileResult, Reporter, escape_xml, start};
+use miette::Result;
+use std::fmt::Write;
+
+pub struct CheckstyleReporter;
+
+impl Reporter for CheckstyleReporter {
+    fn render(&self, results: &[FileResult]) -> Result<String> {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(out, "<checkstyle version=\"4.3\">");
+
+        for file in results {
+            let _ = writeln!(out, "  <file name=\"{}\">", escape_xml(&file.file));
+            for diagnostic in &file.result.diagnostics {
+                let (line, column) = start(diagnostic);
+                let _ = writeln!(
+                    out,
+                    "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"mon.{}\"/>",
+                    line,
+                    column,
+                    diagnostic.severity.label(),
+                    escape_xml(&diagnostic.message),
+                    diagnostic.code
+                );
+            }
+            let _ = writeln!(out, "  </file>");
+        }
+
+        let _ = writeln!(out, "</checkstyle>");
+        Ok(out)
+    }
+}
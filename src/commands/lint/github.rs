@@ -0,0 +1,68 @@
+//! GitHub Actions workflow commands, which show as annotations on the
+//! changed lines of a pull request:
+//!
+//! ```text
+//! ::error file=a.mon,line=3,col=5,endLine=3,endColumn=5,title=LINT2002 Duplicate object key::Duplicate key 'k' in object
+//! ```
+
+use super::{FileResult, Reporter, start};
+use crate::linter::{Diagnostic, DiagnosticSeverity};
+use miette::Result;
+use std::fmt::Write;
+
+pub struct GithubReporter;
+
+impl Reporter for GithubReporter {
+    fn render(&self, results: &[FileResult]) -> Result<String> {
+        let mut out = String::new();
+        for file in results {
+            for diagnostic in &file.result.diagnostics {
+                let command = match diagnostic.severity {
+                    DiagnosticSeverity::Error => "error",
+                    DiagnosticSeverity::Warning => "warning",
+                    DiagnosticSeverity::Info => "notice",
+                };
+                let title = format!("{} {}", diagnostic.code, diagnostic.code.title());
+                let _ = writeln!(
+                    out,
+                    "::{} file={},{},title={}::{}",
+                    command,
+                    escape_property(&file.file),
+                    position(diagnostic),
+                    escape_property(&title),
+                    escape_data(&diagnostic.message)
+                );
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// The `line`, `col`, `endLine` and `endColumn` properties, all one-based
+/// with an inclusive end.
+fn position(diagnostic: &Diagnostic) -> String {
+    let (line, col) = start(diagnostic);
+    let (end_line, end_column) = diagnostic
+        .range
+        .map_or((line, col), |range| (range.end.line + 1, range.end.character.max(1)));
+    format!("line={},col={},endLine={},endColumn={}", line, col, end_line, end_column)
+}
+
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escapes_workflow_command_values() {
+        assert_eq!(escape_data("50% done\nnext"), "50%25 done%0Anext");
+        assert_eq!(escape_property("dir,a:b.mon"), "dir%2Ca%3Ab.mon");
+    }
+}
@@ -0,0 +1,52 @@
+//! GitLab Code Quality report, shown in merge request widgets.
+
+use super::{FileResult, Reporter, fnv1a, line_fingerprints};
+use crate::linter::DiagnosticSeverity;
+use miette::Result;
+use serde_json::json;
+
+pub struct GitlabReporter;
+
+impl Reporter for GitlabReporter {
+    fn render(&self, results: &[FileResult]) -> Result<String> {
+        let mut issues = Vec::new();
+        for file in results {
+            for (diagnostic, fingerprint) in
+                file.result.diagnostics.iter().zip(line_fingerprints(file))
+            {
+                let severity = match diagnostic.severity {
+                    DiagnosticSeverity::Error => "major",
+                    DiagnosticSeverity::Warning => "minor",
+                    DiagnosticSeverity::Info => "info",
+                };
+                // Fingerprints must be unique across the whole report
+                let fingerprint = fnv1a(file.file.bytes().chain([0]).chain(fingerprint.bytes()));
+
+                let mut location = json!({ "path": file.file, "lines": { "begin": 1 } });
+                if let Some(range) = diagnostic.range {
+                    location = json!({
+                        "path": file.file,
+                        "positions": {
+                            "begin": { "line": range.start.line + 1, "column": range.start.character + 1 },
+                            "end": { "line": range.end.line + 1, "column": range.end.character + 1 },
+                        },
+                    });
+                }
+
+                issues.push(json!({
+                    "type": "issue",
+                    "check_name": diagnostic.code.code(),
+                    "description": diagnostic.message,
+                    "categories": ["Style"],
+                    "severity": severity,
+                    "fingerprint": format!("{:016x}", fingerprint),
+                    "location": location,
+                }));
+            }
+        }
+
+        let json = serde_json::to_string_pretty(&issues)
+            .map_err(|e| miette::miette!("Failed to serialize results: {}", e))?;
+        Ok(format!("{}\n", json))
+    }
+}
@@ -0,0 +1,15 @@
+//! JSON output: a `[file, result]` pair per linted file.
+
+use super::{FileResult, Reporter};
+use miette::Result;
+
+pub struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn render(&self, results: &[FileResult]) -> Result<String> {
+        let results: Vec<_> = results.iter().map(|r| (&r.file, &r.result)).collect();
+        let json = serde_json::to_string_pretty(&results)
+            .map_err(|e| miette::miette!("Failed to serialize results: {}", e))?;
+        Ok(format!("{}\n", json))
+    }
+}
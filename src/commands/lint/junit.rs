@@ -0,0 +1,83 @@
+//! JUnit XML with one test case per file.
+//!
+//! A file with errors or warnings fails, listing every diagnostic; info
+//! diagnostics alone do not fail it and go to `<system-out>`.
+
+use super::{FileResult, Reporter, escape_xml, start};
+use crate::linter::Diagnostic;
+use miette::Result;
+use std::fmt::Write;
+
+const SUITE: &str = "mon lint";
+
+pub struct JunitReporter;
+
+impl Reporter for JunitReporter {
+    fn render(&self, results: &[FileResult]) -> Result<String> {
+        let failures = results.iter().filter(|file| fails(file)).count();
+
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            out,
+            "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\">",
+            SUITE,
+            results.len(),
+            failures
+        );
+        let _ = writeln!(
+            out,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\">",
+            SUITE,
+            results.len(),
+            failures
+        );
+
+        for file in results {
+            let name = escape_xml(&file.file);
+            let lines: Vec<_> =
+                file.result.diagnostics.iter().map(|d| escape_xml(&line(&file.file, d))).collect();
+            if lines.is_empty() {
+                let _ = writeln!(out, "    <testcase name=\"{}\" classname=\"{}\"/>", name, SUITE);
+                continue;
+            }
+
+            let _ = writeln!(out, "    <testcase name=\"{}\" classname=\"{}\">", name, SUITE);
+            if fails(file) {
+                let errors = file.result.errors().len();
+                let warnings = file.result.warnings().len();
+                let _ = writeln!(
+                    out,
+                    "      <failure message=\"{} error(s), {} warning(s)\" type=\"lint\">{}</failure>",
+                    errors,
+                    warnings,
+                    lines.join("\n")
+                );
+            } else {
+                let _ = writeln!(out, "      <system-out>{}</system-out>", lines.join("\n"));
+            }
+            let _ = writeln!(out, "    </testcase>");
+        }
+
+        let _ = writeln!(out, "  </testsuite>");
+        let _ = writeln!(out, "</testsuites>");
+        Ok(out)
+    }
+}
+
+fn fails(file: &FileResult) -> bool {
+    !file.result.errors().is_empty() || !file.result.warnings().is_empty()
+}
+
+/// A diagnostic as `file:line:col: severity CODE message`.
+fn line(file: &str, diagnostic: &Diagnostic) -> String {
+    let (line, col) = start(diagnostic);
+    format!(
+        "{}:{}:{}: {} {} {}",
+        file,
+        line,
+        col,
+        diagnostic.severity.label(),
+        diagnostic.code,
+        diagnostic.message
+    )
+}
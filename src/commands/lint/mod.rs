@@ -0,0 +1,295 @@
+mod baseline;
+mod checkstyle;
+mod github;
+mod gitlab;
+mod json;
+mod junit;
+mod sarif;
+mod text;
+
+use crate::linter::fix::FixOutcome;
+use crate::linter::{Diagnostic, DiagnosticSeverity, LintConfig, LintResult, Linter};
+use baseline::Baseline;
+use colored::*;
+use miette::Result;
+use mon_core::parser::Parser;
+use similar::TextDiff;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Names accepted by `--format`.
+const FORMATS: &[&str] = &["text", "json", "sarif", "github", "gitlab", "junit", "checkstyle"];
+
+/// An output format for the results of a lint run.
+///
+/// Each format implements this in its own module and is listed in
+/// `reporter`; the run loop only sees the trait.
+trait Reporter {
+    /// Renders the results of every linted file, in the order linted.
+    fn render(&self, results: &[FileResult]) -> Result<String>;
+}
+
+/// Gets the reporter for a `--format` name.
+fn reporter(format: &str) -> Result<Box<dyn Reporter>> {
+    Ok(match format {
+        "text" => Box::new(text::TextReporter),
+        "json" => Box::new(json::JsonReporter),
+        "sarif" => Box::new(sarif::SarifReporter),
+        "github" => Box::new(github::GithubReporter),
+        "gitlab" => Box::new(gitlab::GitlabReporter),
+        "junit" => Box::new(junit::JunitReporter),
+        "checkstyle" => Box::new(checkstyle::CheckstyleReporter),
+        _ => {
+            return Err(miette::miette!(
+                help = format!("Use one of: {}", FORMATS.join(", ")),
+                "Unknown output format '{}'",
+                format
+            ));
+        }
+    })
+}
+
+/// The lint result of one file, with the text it was computed on.
+struct FileResult {
+    file: String,
+    source: String,
+    result: LintResult,
+}
+
+/// Rule selection and severities given on the command line.
+pub struct RuleOptions {
+    pub rules: Option<Vec<String>>,
+    pub no_rules: Option<Vec<String>>,
+    pub deny: Vec<String>,
+    pub warn: Vec<String>,
+    pub allow: Vec<String>,
+}
+
+impl RuleOptions {
+    /// Applies the options over the configuration file: `--rules`, then
+    /// `--no-rules`, `--allow`, `--warn` and `--deny`, so later ones win.
+    fn apply(self, mut config: LintConfig) -> Result<LintConfig> {
+        if let Some(enabled_rules) = self.rules {
+            config = config.with_only_rules(enabled_rules)?;
+        }
+        if let Some(disabled_rules) = self.no_rules {
+            config = config.without_rules(disabled_rules)?;
+        }
+        config
+            .with_level(&self.allow, None)?
+            .with_level(&self.warn, Some(DiagnosticSeverity::Warning))?
+            .with_level(&self.deny, Some(DiagnosticSeverity::Error))
+    }
+}
+
+/// Baseline files given on the command line.
+pub struct BaselineOptions {
+    /// Hide the diagnostics recorded in this baseline
+    pub baseline: Option<String>,
+    /// Record the diagnostics in this baseline instead of reporting them
+    pub write_baseline: Option<String>,
+}
+
+pub fn run(
+    files: Vec<String>,
+    fix: bool,
+    fix_dry_run: bool,
+    config: Option<String>,
+    format: String,
+    rule_options: RuleOptions,
+    baseline_options: BaselineOptions,
+) -> Result<()> {
+    // Load config, from .moncfg.mon in this or a parent directory by default
+    let lint_config = match config {
+        Some(config_path) => LintConfig::from_mon_file(Path::new(&config_path))?,
+        None => {
+            let current_dir = std::env::current_dir()
+                .map_err(|e| miette::miette!("Failed to read current directory: {}", e))?;
+            LintConfig::discover(&current_dir)?.0
+        }
+    };
+    for warning in &lint_config.warnings {
+        eprintln!("{} {}", "warning:".yellow().bold(), warning);
+    }
+
+    // Apply rule filtering and severities
+    let linter = Linter::new(rule_options.apply(lint_config)?);
+    let reporter = reporter(&format)?;
+    let baseline =
+        baseline_options.baseline.map(|path| Baseline::load(Path::new(&path))).transpose()?;
+    let mut all_results = Vec::new();
+
+    for file in &files {
+        let content = fs::read_to_string(file)
+            .map_err(|e| miette::miette!("Failed to read file {}: {}", file, e))?;
+
+        let mut parser = Parser::new(&content)
+            .map_err(|e| miette::miette!("Parser initialization failed for {}: {:?}", file, e))?;
+
+        let doc = parser
+            .parse_document()
+            .map_err(|e| miette::miette!("Parse error in {}: {:?}", file, e))?;
+
+        let (source, lint_result) = if fix || fix_dry_run {
+            let outcome = linter.fix(&content, Some(Path::new(file)))?;
+            report_fixes(file, &content, &outcome, fix_dry_run, format == "text")?;
+            (outcome.source, outcome.remaining)
+        } else {
+            let lint_result = linter.lint_file(&doc, &content, Path::new(file))?;
+            (content, lint_result)
+        };
+
+        all_results.push(FileResult { file: file.clone(), source, result: lint_result });
+    }
+
+    // A dry run's output is the diff alone
+    if fix_dry_run {
+        return Ok(());
+    }
+
+    if let Some(path) = baseline_options.write_baseline {
+        let path = Path::new(&path);
+        let baseline = Baseline::from_results(path, &all_results);
+        let count = baseline.len();
+        baseline.write(path)?;
+        println!(
+            "{}",
+            format!("Wrote {} diagnostic(s) to baseline {}", count, path.display()).green()
+        );
+        return Ok(());
+    }
+
+    if let Some(baseline) = &baseline {
+        report_stale(&baseline.filter(&mut all_results));
+    }
+
+    // Output results
+    print!("{}", reporter.render(&all_results)?);
+
+    let total_errors: usize = all_results.iter().map(|r| r.result.errors().len()).sum();
+
+    // Exit with error if there are errors
+    if total_errors > 0 {
+        std::process::exit(4); // Exit code 4 for lint errors (per spec)
+    }
+
+    Ok(())
+}
+
+/// Writes the fixed text, or with `dry_run` prints it as a unified diff.
+///
+/// The list of applied fixes goes to stdout for text output and to stderr
+/// otherwise, so it never mixes with JSON or a diff.
+fn report_fixes(
+    file: &str,
+    original: &str,
+    outcome: &FixOutcome,
+    dry_run: bool,
+    text_output: bool,
+) -> Result<()> {
+    if outcome.applied.is_empty() {
+        return Ok(());
+    }
+
+    if dry_run {
+        let diff = TextDiff::from_lines(original, &outcome.source);
+        print!("{}", diff.unified_diff().header(&format!("a/{}", file), &format!("b/{}", file)));
+    } else {
+        fs::write(file, &outcome.source)
+            .map_err(|e| miette::miette!("Failed to write file {}: {}", file, e))?;
+    }
+
+    let verb = if dry_run { "Would fix" } else { "Fixed" };
+    let mut lines = vec![
+        format!("{} {} issue(s) in {}", verb, outcome.applied.len(), file).green().to_string(),
+    ];
+    for applied in &outcome.applied {
+        let line = applied.line.map(|line| format!("line {}: ", line + 1)).unwrap_or_default();
+        lines.push(format!("  {} {}{}", applied.code.to_string().cyan(), line, applied.title));
+    }
+
+    for line in lines {
+        if text_output && !dry_run {
+            println!("{}", line);
+        } else {
+            eprintln!("{}", line);
+        }
+    }
+    Ok(())
+}
+
+/// Lists baseline entries that matched no diagnostic, on stderr so it never
+/// mixes with the report.
+fn report_stale(stale: &[&baseline::Entry]) {
+    if stale.is_empty() {
+        return;
+    }
+    eprintln!(
+        "{}",
+        format!(
+            "{} baseline entry(ies) no longer match a diagnostic; \
+             update the baseline with --write-baseline",
+            stale.len()
+        )
+        .yellow()
+    );
+    for entry in stale {
+        eprintln!("  {}: {} {}", entry.file, entry.code.cyan(), entry.message);
+    }
+}
+
+/// One-based line and column a diagnostic starts at; 1:1 without a range.
+fn start(diagnostic: &Diagnostic) -> (u32, u32) {
+    diagnostic.range.map_or((1, 1), |range| (range.start.line + 1, range.start.character + 1))
+}
+
+/// Fingerprints of a file's diagnostics, in order.
+///
+/// Each is a hash of the rule and the whitespace-normalized text of the line
+/// the diagnostic starts on, numbered among equal hashes in the file
+/// (`<hash>:<n>`), so it survives edits elsewhere in the file.
+fn line_fingerprints(file: &FileResult) -> Vec<String> {
+    let lines: Vec<_> = file.source.lines().collect();
+    let mut occurrences: HashMap<u64, usize> = HashMap::new();
+
+    file.result
+        .diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let line = diagnostic
+                .range
+                .and_then(|range| lines.get(range.start.line as usize))
+                .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+                .unwrap_or_default();
+            let hash = fnv1a(diagnostic.code.code().bytes().chain([0]).chain(line.bytes()));
+
+            let occurrence = occurrences.entry(hash).or_default();
+            *occurrence += 1;
+            format!("{:016x}:{}", hash, occurrence)
+        })
+        .collect()
+}
+
+/// 64-bit FNV-1a hash, stable across runs and platforms.
+fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes.into_iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Escapes text for an XML attribute value or element.
+fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\n' => out.push_str("&#10;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,243 @@
+//! SARIF 2.1.0 output for `mon lint --format sarif`.
+//!
+//! Every rule is listed in `tool.driver.rules` with its default level, so a
+//! result's `level` shows where a severity was overridden. Regions are
+//! one-based with UTF-16 columns, the SARIF default `columnKind`. Each result
+//! has a fingerprint built from its rule and the text of its first line
+//! rather than the line number, so it survives edits elsewhere in the file.
+
+use super::{FileResult, Reporter, line_fingerprints};
+use crate::linter::edit::Fix;
+use crate::linter::rules::LintRule;
+use crate::linter::{Diagnostic, DiagnosticSeverity, Range};
+use miette::Result;
+use serde_json::{Value, json};
+use std::path::Path;
+
+const SCHEMA_URI: &str =
+    "https://docs.oasis-open.org/sarif/sarif/v2.1.0/errata01/os/schemas/sarif-schema-2.1.0.json";
+
+/// Key of the fingerprint in `partialFingerprints`.
+const FINGERPRINT_KEY: &str = "monLineHash/v1";
+
+pub struct SarifReporter;
+
+impl Reporter for SarifReporter {
+    fn render(&self, results: &[FileResult]) -> Result<String> {
+        let json = serde_json::to_string_pretty(&log(results))
+            .map_err(|e| miette::miette!("Failed to serialize results: {}", e))?;
+        Ok(format!("{}\n", json))
+    }
+}
+
+/// Builds the SARIF log for the results of a lint run.
+fn log(results: &[FileResult]) -> Value {
+    let rules: Vec<_> = LintRule::ALL
+        .iter()
+        .map(|rule| {
+            let code = rule.code();
+            json!({
+                "id": code.code(),
+                "name": rule.name(),
+                "shortDescription": { "text": code.title() },
+                "fullDescription": { "text": code.description() },
+                "defaultConfiguration": { "level": level(code.severity()) },
+            })
+        })
+        .collect();
+
+    let sarif_results: Vec<_> = results.iter().flat_map(file_results).collect();
+
+    json!({
+        "$schema": SCHEMA_URI,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "mon",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_HOMEPAGE"),
+                    "rules": rules,
+                }
+            },
+            "columnKind": "utf16CodeUnits",
+            "results": sarif_results,
+        }]
+    })
+}
+
+fn file_results(file: &FileResult) -> Vec<Value> {
+    let uri = artifact_uri(&file.file);
+
+    file.result
+        .diagnostics
+        .iter()
+        .zip(line_fingerprints(file))
+        .map(|(diagnostic, fingerprint)| {
+            let related: Vec<_> = diagnostic
+                .related_information
+                .iter()
+                .enumerate()
+                .map(|(i, info)| {
+                    let uri = match info.location.uri.as_str() {
+                        "" => uri.clone(),
+                        other => other.to_string(),
+                    };
+                    let mut location = location(&uri, Some(info.location.range));
+                    location["id"] = json!(i);
+                    location["message"] = json!({ "text": info.message });
+                    location
+                })
+                .collect();
+
+            let mut result = json!({
+                "ruleId": diagnostic.code.code(),
+                "ruleIndex": rule_index(diagnostic),
+                "level": level(diagnostic.severity),
+                "message": { "text": diagnostic.message },
+                "locations": [location(&uri, diagnostic.range)],
+                "partialFingerprints": {
+                    FINGERPRINT_KEY: fingerprint,
+                },
+            });
+            if !related.is_empty() {
+                result["relatedLocations"] = json!(related);
+            }
+            if !diagnostic.fixes.is_empty() {
+                let fixes: Vec<_> =
+                    diagnostic.fixes.iter().map(|fix| sarif_fix(&uri, fix)).collect();
+                result["fixes"] = json!(fixes);
+            }
+            result
+        })
+        .collect()
+}
+
+fn rule_index(diagnostic: &Diagnostic) -> usize {
+    LintRule::ALL.iter().position(|rule| rule.code() == diagnostic.code).unwrap_or_default()
+}
+
+fn level(severity: DiagnosticSeverity) -> &'static str {
+    match severity {
+        DiagnosticSeverity::Error => "error",
+        DiagnosticSeverity::Warning => "warning",
+        DiagnosticSeverity::Info => "note",
+    }
+}
+
+fn location(uri: &str, range: Option<Range>) -> Value {
+    let mut physical = json!({ "artifactLocation": { "uri": uri } });
+    if let Some(range) = range {
+        physical["region"] = region(range);
+    }
+    json!({ "physicalLocation": physical })
+}
+
+/// A SARIF region: one-based lines and columns, with an exclusive end column.
+fn region(range: Range) -> Value {
+    json!({
+        "startLine": range.start.line + 1,
+        "startColumn": range.start.character + 1,
+        "endLine": range.end.line + 1,
+        "endColumn": range.end.character + 1,
+    })
+}
+
+fn sarif_fix(uri: &str, fix: &Fix) -> Value {
+    let replacements: Vec<_> = fix
+        .edits
+        .iter()
+        .map(|edit| {
+            json!({
+                "deletedRegion": region(edit.range),
+                "insertedContent": { "text": edit.new_text },
+            })
+        })
+        .collect();
+    json!({
+        "description": { "text": fix.title },
+        "artifactChanges": [{
+            "artifactLocation": { "uri": uri },
+            "replacements": replacements,
+        }],
+    })
+}
+
+/// The URI reference of a linted file: a `file://` URI for absolute paths,
+/// a relative reference otherwise.
+fn artifact_uri(file: &str) -> String {
+    let path = file.replace('\\', "/");
+    let encoded: String = path
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect();
+
+    if Path::new(file).is_absolute() {
+        let encoded = encoded.trim_start_matches('/');
+        format!("file:///{}", encoded)
+    } else {
+        encoded.trim_start_matches("./").to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::{LintConfig, Linter};
+    use mon_core::parser::Parser;
+
+    fn lint(file: &str, source: &str) -> FileResult {
+        let doc = Parser::new(source).unwrap().parse_document().unwrap();
+        let result = Linter::new(LintConfig::default()).lint(&doc, source).unwrap();
+        FileResult { file: file.to_string(), source: source.to_string(), result }
+    }
+
+    fn fingerprints(log: &Value) -> Vec<String> {
+        log["runs"][0]["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|result| {
+                result["partialFingerprints"][FINGERPRINT_KEY].as_str().unwrap().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_results_locate_diagnostics() {
+        let log = log(&[lint("conf/a b.mon", "{\n    k: 1,\n    k: 2,\n}")]);
+        let result = &log["runs"][0]["results"][0];
+
+        assert_eq!(result["ruleId"], "LINT2002");
+        assert_eq!(
+            log["runs"][0]["tool"]["driver"]["rules"]
+                [result["ruleIndex"].as_u64().unwrap() as usize]["id"],
+            "LINT2002"
+        );
+        assert_eq!(result["level"], "error");
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "conf/a%20b.mon");
+        assert_eq!(
+            location["region"],
+            json!({ "startLine": 3, "startColumn": 5, "endLine": 3, "endColumn": 6 })
+        );
+        assert_eq!(result["relatedLocations"][0]["physicalLocation"]["region"]["startLine"], 2);
+        assert_eq!(result["fixes"][0]["description"]["text"], "Remove the earlier 'k'");
+    }
+
+    #[test]
+    fn test_fingerprints_survive_moved_lines() {
+        let source = "{\n    &a: 1,\n    &b: 2,\n}";
+        let moved = "{\n    first: 0,\n\n    &a: 1,\n    &b: 2,\n}";
+
+        let before = fingerprints(&log(&[lint("a.mon", source)]));
+        assert_eq!(before.len(), 2);
+        assert_ne!(before[0], before[1]);
+        assert_eq!(before, fingerprints(&log(&[lint("a.mon", moved)])));
+    }
+}
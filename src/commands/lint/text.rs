@@ -0,0 +1,121 @@
+//! Human-readable output, with a code frame per diagnostic.
+
+use super::{FileResult, Reporter};
+use crate::linter::{Diagnostic, DiagnosticSeverity, Range};
+use colored::*;
+use miette::{GraphicalReportHandler, GraphicalTheme, LabeledSpan, NamedSource, Result};
+use std::fmt::{self, Write};
+
+pub struct TextReporter;
+
+impl Reporter for TextReporter {
+    fn render(&self, results: &[FileResult]) -> Result<String> {
+        let theme = if colored::control::SHOULD_COLORIZE.should_colorize() {
+            GraphicalTheme::unicode()
+        } else {
+            GraphicalTheme::unicode_nocolor()
+        };
+        let handler = GraphicalReportHandler::new_themed(theme);
+        let mut out = String::new();
+
+        for FileResult { file, source, result: lint_result } in results {
+            if !lint_result.has_issues() {
+                let _ = writeln!(out, "{}", format!("  ✓ {}", file).green());
+            } else {
+                let _ = writeln!(out, "\n{}", file.bold());
+
+                let source = NamedSource::new(file, source.clone());
+                let diagnostics = lint_result
+                    .errors()
+                    .into_iter()
+                    .chain(lint_result.warnings())
+                    .chain(lint_result.infos());
+                for diagnostic in diagnostics {
+                    handler
+                        .render_report(&mut out, &Frame { diagnostic, source: &source })
+                        .map_err(|e| miette::miette!("Failed to render diagnostic: {}", e))?;
+                    out.push('\n');
+                }
+            }
+        }
+
+        let _ = writeln!(out);
+        let total_errors: usize = results.iter().map(|r| r.result.errors().len()).sum();
+        let total_warnings: usize = results.iter().map(|r| r.result.warnings().len()).sum();
+
+        let _ = writeln!(out, "{}: {} files linted", "Summary".bold(), results.len());
+        if total_errors > 0 {
+            let _ = writeln!(out, "  {} file(s) with errors", total_errors.to_string().red());
+        }
+        if total_warnings > 0 {
+            let _ =
+                writeln!(out, "  {} file(s) with warnings", total_warnings.to_string().yellow());
+        }
+
+        Ok(out)
+    }
+}
+
+/// A lint diagnostic as a miette diagnostic, so it renders with a code frame
+/// like parse errors do.
+///
+/// Related information in the same file becomes extra labels, and the first
+/// fix, if any, becomes the help text.
+#[derive(Debug)]
+struct Frame<'a> {
+    diagnostic: &'a Diagnostic,
+    source: &'a NamedSource<String>,
+}
+
+impl fmt::Display for Frame<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.diagnostic.message)
+    }
+}
+
+impl std::error::Error for Frame<'_> {}
+
+impl miette::Diagnostic for Frame<'_> {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(self.diagnostic.code))
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        Some(match self.diagnostic.severity {
+            DiagnosticSeverity::Error => miette::Severity::Error,
+            DiagnosticSeverity::Warning => miette::Severity::Warning,
+            DiagnosticSeverity::Info => miette::Severity::Advice,
+        })
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let fix = self.diagnostic.fixes.first()?;
+        Some(Box::new(format!("Fix available: {}", fix.title)))
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        Some(self.source)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let text = self.source.inner().as_str();
+        let span = |range: Range| {
+            let start = range.start.to_byte_offset(text);
+            start..range.end.to_byte_offset(text).max(start)
+        };
+
+        let primary = self
+            .diagnostic
+            .range
+            .map(|range| LabeledSpan::new_primary_with_span(None, span(range)));
+        let related = self
+            .diagnostic
+            .related_information
+            .iter()
+            .filter(|info| info.location.uri.is_empty())
+            .map(move |info| {
+                LabeledSpan::new_with_span(Some(info.message.clone()), span(info.location.range))
+            });
+        Some(Box::new(primary.into_iter().chain(related)))
+    }
+}
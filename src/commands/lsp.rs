@@ -0,0 +1,6 @@
+use miette::Result;
+
+pub fn run() -> Result<()> {
+    // stdout carries the protocol, so nothing else may be printed here
+    crate::lsp::run_stdio()
+}
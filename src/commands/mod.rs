@@ -2,6 +2,11 @@ pub mod bundle;
 pub mod check;
 pub mod compile;
 pub mod completions;
+pub mod explain;
 pub mod fmt;
 pub mod init;
 pub mod lint;
+pub mod lsp;
+pub mod refs;
+pub mod rename;
+pub mod symbols;
@@ -0,0 +1,90 @@
+use crate::linter::api::AnalysisService;
+use crate::linter::navigation::{Navigator, SymbolLocation};
+use crate::linter::{LintConfig, Position};
+use colored::*;
+use miette::Result;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// JSON output of `mon refs --as-json`.
+#[derive(Serialize)]
+struct RefsOutput<'a> {
+    symbol: &'a str,
+    kind: String,
+    definition: &'a SymbolLocation,
+    references: &'a [SymbolLocation],
+}
+
+pub fn run(file: &str, position: &str, as_json: bool) -> Result<()> {
+    let position = parse_position(position)?;
+
+    let content = fs::read_to_string(file)
+        .map_err(|e| miette::miette!("Failed to read file {}: {}", file, e))?;
+
+    let service = AnalysisService::new(LintConfig::default());
+    let analysis = service.analyze_document(&content, file)?;
+
+    let mut navigator = Navigator::new(&analysis);
+    let Some(definition) = navigator.definition_at(position) else {
+        return Err(miette::miette!(
+            "No anchor or type at {}:{}:{}",
+            file,
+            position.line + 1,
+            position.character + 1
+        ));
+    };
+    let references = navigator.references_at(position, false);
+
+    if as_json {
+        let output = RefsOutput {
+            symbol: &definition.name,
+            kind: format!("{:?}", definition.kind),
+            definition: &definition.location,
+            references: &references,
+        };
+        let json = serde_json::to_string_pretty(&output)
+            .map_err(|e| miette::miette!("Failed to serialize references: {}", e))?;
+        println!("{}", json);
+        return Ok(());
+    }
+
+    println!("{}: {}", display_location(&definition.location), "definition".green());
+    for reference in &references {
+        println!("{}: {}", display_location(reference), "reference".cyan());
+    }
+
+    Ok(())
+}
+
+/// Parses a one-based `line:col` argument into a zero-based position.
+fn parse_position(position: &str) -> Result<Position> {
+    let parse =
+        |part: Option<&str>| part.and_then(|p| p.trim().parse::<u32>().ok()).filter(|n| *n > 0);
+
+    let mut parts = position.splitn(2, ':');
+    match (parse(parts.next()), parse(parts.next())) {
+        (Some(line), Some(column)) => Ok(Position::new(line - 1, column - 1)),
+        _ => Err(miette::miette!(
+            "Invalid position '{}': expected <line>:<col> (one-based), e.g. 12:7",
+            position
+        )),
+    }
+}
+
+/// Formats a location as `path:line:col` with one-based line and column,
+/// relative to the current directory when possible.
+fn display_location(location: &SymbolLocation) -> String {
+    let cwd = std::env::current_dir().ok();
+    let path = cwd
+        .as_deref()
+        .and_then(|cwd| location.path.strip_prefix(cwd).ok())
+        .unwrap_or(Path::new(&location.path));
+
+    format!(
+        "{}:{}:{}",
+        path.display(),
+        location.range.start.line + 1,
+        location.range.start.character + 1
+    )
+}
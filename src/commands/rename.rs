@@ -0,0 +1,92 @@
+use crate::linter::api::AnalysisService;
+use crate::linter::edit::{self, WorkspaceEdit};
+use crate::linter::navigation::Navigator;
+use crate::linter::workspace::mon_files;
+use crate::linter::{LintConfig, SymbolKind, rename};
+use colored::*;
+use miette::Result;
+use std::fs;
+use std::path::Path;
+
+pub fn run(file: &str, old: &str, new: &str, root: Option<String>, dry_run: bool) -> Result<()> {
+    let content = fs::read_to_string(file)
+        .map_err(|e| miette::miette!("Failed to read file {}: {}", file, e))?;
+
+    let service = AnalysisService::new(LintConfig::default());
+    let analysis = service.analyze_document(&content, file)?;
+    let mut navigator = Navigator::new(&analysis);
+
+    // `&name` always means the anchor; a bare name may be either kind
+    let (name, kinds): (&str, &[SymbolKind]) = match old.strip_prefix('&') {
+        Some(anchor) => (anchor, &[SymbolKind::Anchor]),
+        None => (old, &[SymbolKind::Anchor, SymbolKind::Type]),
+    };
+    let mut found: Vec<_> =
+        kinds.iter().filter_map(|kind| navigator.resolve_name(name, *kind)).collect();
+    let definition = match found.len() {
+        0 => return Err(miette::miette!("No anchor or type named '{}' in {}", name, file)),
+        1 => found.remove(0),
+        _ => {
+            return Err(miette::miette!(
+                "Both an anchor and a type are named '{}' in {}; write '&{}' to rename the anchor",
+                name,
+                file,
+                name
+            ));
+        }
+    };
+
+    let root = root.unwrap_or_else(|| ".".to_string());
+    let root = fs::canonicalize(&root)
+        .map_err(|e| miette::miette!("Failed to read workspace root {}: {}", root, e))?;
+    let workspace = mon_files(&root);
+
+    let new = new.strip_prefix('&').unwrap_or(new);
+    let edit = rename::rename(&mut navigator, &definition, new, &workspace)?;
+
+    if dry_run {
+        print_edits(&edit, &definition.name);
+        return Ok(());
+    }
+
+    for (path, edits) in &edit.changes {
+        let source = if path == navigator.root_path() {
+            content.clone()
+        } else {
+            fs::read_to_string(path)
+                .map_err(|e| miette::miette!("Failed to read file {}: {}", path.display(), e))?
+        };
+        fs::write(path, edit::apply(&source, edits))
+            .map_err(|e| miette::miette!("Failed to write file {}: {}", path.display(), e))?;
+    }
+
+    println!(
+        "{} Renamed '{}' to '{}': {} edit(s) in {} file(s)",
+        "✓".green(),
+        definition.name,
+        new,
+        edit.edit_count(),
+        edit.changes.len()
+    );
+
+    Ok(())
+}
+
+/// Lists the planned edits as `path:line:col: old -> new`.
+fn print_edits(edit: &WorkspaceEdit, old: &str) {
+    let cwd = std::env::current_dir().ok();
+    for (path, edits) in &edit.changes {
+        let path =
+            cwd.as_deref().and_then(|cwd| path.strip_prefix(cwd).ok()).unwrap_or(Path::new(path));
+        for text_edit in edits {
+            println!(
+                "{}:{}:{}: {} -> {}",
+                path.display(),
+                text_edit.range.start.line + 1,
+                text_edit.range.start.character + 1,
+                old.red(),
+                text_edit.new_text.green()
+            );
+        }
+    }
+}
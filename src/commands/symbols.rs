@@ -0,0 +1,94 @@
+use crate::linter::api::AnalysisService;
+use crate::linter::outline::{OutlineNode, document_outline};
+use crate::linter::workspace::{WorkspaceSymbol, mon_files, workspace_symbols};
+use crate::linter::{LintConfig, SymbolKind};
+use colored::*;
+use miette::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn run(path: &str, query: Option<String>, as_json: bool) -> Result<()> {
+    let target = Path::new(path);
+    if !target.exists() {
+        return Err(miette::miette!("No such file or directory: {}", path));
+    }
+
+    // A directory or a query lists matching symbols; a file alone prints its outline
+    if target.is_dir() || query.is_some() {
+        let files = if target.is_dir() {
+            mon_files(target)
+        } else {
+            vec![fs::canonicalize(target).unwrap_or_else(|_| PathBuf::from(path))]
+        };
+        let symbols = workspace_symbols(&files, query.as_deref().unwrap_or(""));
+        return print_symbols(&symbols, as_json);
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| miette::miette!("Failed to read file {}: {}", path, e))?;
+    let service = AnalysisService::new(LintConfig::default());
+    let analysis = service.analyze_document(&content, path)?;
+    let outline = document_outline(&analysis);
+
+    if as_json {
+        let json = serde_json::to_string_pretty(&outline)
+            .map_err(|e| miette::miette!("Failed to serialize symbols: {}", e))?;
+        println!("{}", json);
+        return Ok(());
+    }
+
+    for node in &outline {
+        print_node(node, 0);
+    }
+    Ok(())
+}
+
+/// Prints an outline entry and its children as `line  name (kind) detail`,
+/// indented by depth, with one-based line numbers.
+fn print_node(node: &OutlineNode, depth: usize) {
+    let detail = node.detail.as_deref().map(|d| format!(" {}", d)).unwrap_or_default();
+    println!(
+        "{:>4}  {}{} {}{}",
+        node.selection_range.start.line + 1,
+        "  ".repeat(depth),
+        node.name.bold(),
+        format!("({})", node.kind.label()).dimmed(),
+        detail
+    );
+    for child in &node.children {
+        print_node(child, depth + 1);
+    }
+}
+
+fn print_symbols(symbols: &[WorkspaceSymbol], as_json: bool) -> Result<()> {
+    if as_json {
+        let json = serde_json::to_string_pretty(symbols)
+            .map_err(|e| miette::miette!("Failed to serialize symbols: {}", e))?;
+        println!("{}", json);
+        return Ok(());
+    }
+
+    let cwd = std::env::current_dir().ok();
+    for symbol in symbols {
+        let path = cwd
+            .as_deref()
+            .and_then(|cwd| symbol.location.path.strip_prefix(cwd).ok())
+            .unwrap_or(&symbol.location.path);
+        let kind = match symbol.kind {
+            SymbolKind::Anchor => "anchor",
+            SymbolKind::Type => "type",
+            SymbolKind::Import => "import",
+            SymbolKind::Field => "field",
+            SymbolKind::EnumVariant => "variant",
+        };
+        println!(
+            "{}:{}:{}: {} {}",
+            path.display(),
+            symbol.location.range.start.line + 1,
+            symbol.location.range.start.character + 1,
+            symbol.name.bold(),
+            format!("({})", kind).dimmed()
+        );
+    }
+    Ok(())
+}
@@ -79,7 +79,7 @@ impl MonCliError {
                         format!("expected {} here", expected),
                     ),
                 };
-                
+
                 MonCliError::Parser {
                     message: format!("Unexpected token, expected {}", expected),
                     help,
@@ -101,7 +101,7 @@ impl MonCliError {
                     "RBrace" => "Missing closing brace '}'. Make sure all objects are properly closed.".to_string(),
                     _ => format!("Missing required token: {}. Check the syntax at this location.", expected),
                 };
-                
+
                 MonCliError::Parser {
                     message: format!("Missing expected token: {}", expected),
                     help,
@@ -242,3 +242,43 @@ impl MonCliError {
         }
     }
 }
+
+/// What an error code of [`MonCliError`] means, for `mon explain`.
+pub struct ErrorCode {
+    pub code: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    /// A document that fails with this error, and the same document fixed
+    pub example: (&'static str, &'static str),
+}
+
+/// Every error code [`MonCliError`] reports.
+pub const ERROR_CODES: [ErrorCode; 3] = [
+    ErrorCode {
+        code: "mon::syntax_error",
+        title: "Syntax error",
+        description: "The file is not valid MON: a bracket is not closed, a comma or colon is missing, or a token appears where it cannot. Nothing else is checked until the file parses.",
+        example: (
+            "{\n    name: \"demo\"\n    port: 8080,\n}",
+            "{\n    name: \"demo\",\n    port: 8080,\n}",
+        ),
+    },
+    ErrorCode {
+        code: "mon::resolution_error",
+        title: "Resolution error",
+        description: "An import, alias or spread cannot be resolved: the imported file does not exist, an anchor is not defined, a spread targets the wrong kind of value, or references form a cycle.",
+        example: (
+            "{\n    server: { ...*defaults },\n}",
+            "{\n    &defaults: { port: 80 },\n    server: { ...*defaults },\n}",
+        ),
+    },
+    ErrorCode {
+        code: "mon::validation_error",
+        title: "Validation error",
+        description: "A value annotated with '::' does not match its type: a field has the wrong type, a required field is missing, a field is not in the struct, or an enum variant is not defined.",
+        example: (
+            "{\n    User: #struct { age(Number) },\n    bob :: User = { age: \"thirty\" },\n}",
+            "{\n    User: #struct { age(Number) },\n    bob :: User = { age: 30 },\n}",
+        ),
+    },
+];
@@ -1,4 +1,8 @@
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Name of the configuration file picked up by `FormatConfig::discover`
+pub const CONFIG_FILE_NAME: &str = ".monconfig.mon";
 
 /// Comprehensive formatting configuration
 /// Designed to support multiple coding styles and explicit options
@@ -282,6 +286,16 @@ impl FormatConfig {
         Ok(config)
     }
 
+    /// Load `.monconfig.mon` from `dir` or the closest ancestor that has one,
+    /// falling back to the default configuration. Also returns the path of
+    /// the file that was loaded.
+    pub fn discover(dir: &Path) -> miette::Result<(Self, Option<PathBuf>)> {
+        match dir.ancestors().map(|dir| dir.join(CONFIG_FILE_NAME)).find(|path| path.is_file()) {
+            Some(path) => Ok((Self::from_mon_file(&path.to_string_lossy())?, Some(path))),
+            None => Ok((Self::default(), None)),
+        }
+    }
+
     /// Get indent string based on configuration
     pub fn indent_string(&self) -> String {
         match self.indent_style {
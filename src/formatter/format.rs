@@ -1,6 +1,8 @@
 use super::config::*;
 use miette::Result;
-use mon_core::ast::{ImportSpec, ImportStatement, Member, MonValue, MonValueKind, TypeDefinition};
+use mon_core::ast::{
+    ImportSpec, ImportStatement, Member, MonDocument, MonValue, MonValueKind, TypeDefinition,
+};
 use mon_core::parser::Parser;
 use std::collections::HashMap;
 
@@ -18,6 +20,103 @@ impl Formatter {
     ///
     /// Never fails catastrophically - provides partial formatting or helpful errors
     pub fn format(&self, source: &str) -> Result<String> {
+        let doc = self.parse(source)?;
+
+        // Extract comments from source (preserve positions)
+        let comments = self.extract_comments(source);
+
+        // Create comment map for efficient lookup
+        let comment_map = self.build_comment_map(&comments);
+
+        // Format the document with comment preservation
+        let mut output = String::new();
+        let mut current_line = 0usize;
+
+        // Add top-of-file comments
+        while let Some(comment) = comment_map.get(&current_line) {
+            if !comment.is_trailing {
+                output.push_str(&comment.text);
+                output.push('\n');
+                current_line += 1;
+            } else {
+                break;
+            }
+        }
+
+        // Format imports
+        for (i, import) in doc.imports.iter().enumerate() {
+            output.push_str(&self.format_import(import));
+            output.push('\n');
+
+            // Check for comments after this import
+            let import_line = i + current_line;
+            if let Some(comment) = comment_map.get(&import_line)
+                && comment.is_trailing
+            {
+                output.push_str("  ");
+                output.push_str(&comment.text);
+            }
+        }
+
+        if !doc.imports.is_empty() {
+            output.push('\n');
+        }
+
+        // Format root value
+        let formatted_root = self.format_value(&doc.root, 0, &comment_map, source);
+        output.push_str(&formatted_root);
+
+        // Ensure final newline if configured
+        if self.config.final_newline && !output.ends_with('\n') {
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    /// Formats the innermost object or array enclosing the byte range
+    /// `start..end`, at the indentation of its position in the document.
+    ///
+    /// Returns the byte span of that value (without its anchor) and its
+    /// formatted text, or `None` when the range is not inside one.
+    pub fn format_range(
+        &self,
+        source: &str,
+        start: usize,
+        end: usize,
+    ) -> Result<Option<(std::ops::Range<usize>, String)>> {
+        let doc = self.parse(source)?;
+
+        let mut found = None;
+        let mut candidates = vec![(&doc.root, 0)];
+        while let Some((value, depth)) = candidates.pop() {
+            if value.pos_start > start || value.pos_end < end {
+                continue;
+            }
+            match &value.kind {
+                MonValueKind::Object(members) => {
+                    found = Some((value, depth));
+                    candidates.extend(members.iter().filter_map(|member| match member {
+                        Member::Pair(pair) => Some((&pair.value, depth + 1)),
+                        _ => None,
+                    }));
+                }
+                MonValueKind::Array(items) => {
+                    found = Some((value, depth));
+                    candidates.extend(items.iter().map(|item| (item, depth + 1)));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(found.map(|(value, depth)| {
+            let text = self.format_value_kind(&value.kind, depth, &HashMap::new(), source);
+            (value.pos_start..value.pos_end, text)
+        }))
+    }
+
+    /// Parses `source`, turning parse failures into rich diagnostics
+    fn parse(&self, source: &str) -> Result<MonDocument> {
         use miette::{NamedSource, SourceSpan};
 
         // Parse the source to AST with rich error diagnostics
@@ -36,8 +135,8 @@ impl Formatter {
             }
         };
 
-        let doc = match parser.parse_document() {
-            Ok(d) => d,
+        match parser.parse_document() {
+            Ok(doc) => Ok(doc),
             Err(e) => {
                 // Extract error details for rich diagnostic
                 let error_msg = format!("{:?}", e);
@@ -51,7 +150,7 @@ impl Formatter {
                     SourceSpan::from(0..source.len().min(100))
                 };
 
-                return Err(miette::miette! {
+                Err(miette::miette! {
                     labels = vec![
                         miette::LabeledSpan::at(span, "syntax error here"),
                     ],
@@ -67,59 +166,9 @@ impl Formatter {
                     ),
                     "Failed to parse MON document"
                 }
-                .with_source_code(NamedSource::new("input", source.to_string())));
+                .with_source_code(NamedSource::new("input", source.to_string())))
             }
-        };
-
-        // Extract comments from source (preserve positions)
-        let comments = self.extract_comments(source);
-
-        // Create comment map for efficient lookup
-        let comment_map = self.build_comment_map(&comments);
-
-        // Format the document with comment preservation
-        let mut output = String::new();
-        let mut current_line = 0usize;
-
-        // Add top-of-file comments
-        while let Some(comment) = comment_map.get(&current_line) {
-            if !comment.is_trailing {
-                output.push_str(&comment.text);
-                output.push('\n');
-                current_line += 1;
-            } else {
-                break;
-            }
-        }
-
-        // Format imports
-        for (i, import) in doc.imports.iter().enumerate() {
-            output.push_str(&self.format_import(import));
-            output.push('\n');
-
-            // Check for comments after this import
-            let import_line = i + current_line;
-            if let Some(comment) = comment_map.get(&import_line)
-                && comment.is_trailing {
-                    output.push_str("  ");
-                    output.push_str(&comment.text);
-                }
         }
-
-        if !doc.imports.is_empty() {
-            output.push('\n');
-        }
-
-        // Format root value
-        let formatted_root = self.format_value(&doc.root, 0, &comment_map, source);
-        output.push_str(&formatted_root);
-
-        // Ensure final newline if configured
-        if self.config.final_newline && !output.ends_with('\n') {
-            output.push('\n');
-        }
-
-        Ok(output)
     }
 
     /// Format an import statement
@@ -277,17 +326,10 @@ impl Formatter {
                 result.push_str(&inner_indent);
                 let formatted_item = self.format_value(item, depth + 1, comment_map, source);
 
-                // Check line width
-                if inner_indent.len() + formatted_item.len() > self.config.max_line_width {
-                    result.push_str(&formatted_item);
-                } else {
-                    result.push_str(&formatted_item);
-                }
+                result.push_str(&formatted_item);
 
                 // Add comma
-                if i < items.len() - 1 {
-                    result.push(',');
-                } else if self.should_add_trailing_comma(true) {
+                if i < items.len() - 1 || self.should_add_trailing_comma(true) {
                     result.push(',');
                 }
 
@@ -332,9 +374,7 @@ impl Formatter {
             result.push_str(&inner_indent);
             result.push_str(&self.format_value(item, depth + 1, comment_map, source));
 
-            if i < items.len() - 1 {
-                result.push(',');
-            } else if self.should_add_trailing_comma(true) {
+            if i < items.len() - 1 || self.should_add_trailing_comma(true) {
                 result.push(',');
             }
 
@@ -433,9 +473,7 @@ impl Formatter {
                 }
 
                 // Comma
-                if i < members.len() - 1 {
-                    result.push(',');
-                } else if self.should_add_trailing_comma(true) {
+                if i < members.len() - 1 || self.should_add_trailing_comma(true) {
                     result.push(',');
                 }
 
@@ -511,9 +549,7 @@ impl Formatter {
                         result.push_str(&self.format_value(default, 0, &HashMap::new(), ""));
                     }
 
-                    if i < struct_def.fields.len() - 1 {
-                        result.push(',');
-                    } else if self.should_add_trailing_comma(true) {
+                    if i < struct_def.fields.len() - 1 || self.should_add_trailing_comma(true) {
                         result.push(',');
                     }
 
@@ -533,9 +569,7 @@ impl Formatter {
                     result.push_str(&self.config.indent_string()); // Double indent for enum variants
                     result.push_str(variant);
 
-                    if i < enum_def.variants.len() - 1 {
-                        result.push(',');
-                    } else if self.should_add_trailing_comma(true) {
+                    if i < enum_def.variants.len() - 1 || self.should_add_trailing_comma(true) {
                         result.push(',');
                     }
 
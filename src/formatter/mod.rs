@@ -1,4 +1,3 @@
-pub mod advanced;
 pub mod config;
 pub mod format;
 pub mod style;
@@ -53,16 +53,17 @@ impl WatchMode {
                 Ok(event) => {
                     if let EventKind::Modify(ModifyKind::Data(_)) = event.kind {
                         for path in event.paths {
-                            if !path.extension().is_some_and(|e| e == "mon") {
+                            if path.extension().is_none_or(|e| e != "mon") {
                                 continue;
                             }
 
                             // Debounce
                             let now = std::time::Instant::now();
                             if let Some(last) = last_format.get(&path)
-                                && now.duration_since(*last) < debounce_duration {
-                                    continue;
-                                }
+                                && now.duration_since(*last) < debounce_duration
+                            {
+                                continue;
+                            }
                             last_format.insert(path.clone(), now);
 
                             // Format the file
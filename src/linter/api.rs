@@ -4,12 +4,24 @@
 //! the linter, symbol table, and resolver to provide comprehensive analysis
 //! suitable for both CLI and LSP use.
 
+use crate::linter::render;
+use crate::linter::source_index::SourceIndex;
+use crate::linter::symbol_table::SymbolAt;
 use crate::linter::{
-    LintConfig, LintResult, Linter, Range, ReferenceKind, Symbol, SymbolKind,
-    SymbolReference, SymbolTable,
+    LintConfig, Linter, Position, Range, ReferenceKind, Symbol, SymbolKind, SymbolReference,
+    SymbolTable,
 };
 use miette::Result;
-use mon_core::ast::{Member, MonDocument, MonValue, MonValueKind};
+use mon_core::ast::{
+    ImportSpec, Member, MonDocument, MonValue, MonValueKind, TypeDef, TypeDefinition, TypeSpec,
+};
+use std::collections::HashMap;
+use std::ops::Range as ByteRange;
+use std::path::Path;
+
+/// Type names built into MON; annotations using them are not symbol references.
+pub const BUILTIN_TYPES: &[&str] =
+    &["String", "Number", "Boolean", "Null", "Object", "Array", "Any"];
 
 /// Analysis service for comprehensive MON document analysis.
 ///
@@ -45,7 +57,6 @@ impl AnalysisService {
     /// This performs:
     /// - Full linting (complexity, smells, best practices)
     /// - Symbol table construction
-    /// - Unused symbol detection
     ///
     /// # Arguments
     /// * `source` - The MON source code
@@ -60,101 +71,265 @@ impl AnalysisService {
 
         // Run standard linting
         let linter = Linter::new(self.config.clone());
-        let mut lint_result = linter.lint(&doc, source)?;
+        let lint_result = linter.lint_file(&doc, source, Path::new(file_path))?;
 
         // Build symbol table
-        let mut symbol_table = SymbolTable::new();
-        self.build_symbol_table(&doc, source, &mut symbol_table);
-
-        // Detect unused symbols and add diagnostics
-        self.detect_unused_symbols(&symbol_table, source, &mut lint_result);
+        let symbol_table = SymbolCollector::new(source, file_path).collect(&doc);
 
         Ok(AnalysisResult {
             diagnostics: lint_result.diagnostics,
             symbol_table,
             source: source.to_string(),
+            file_path: file_path.to_string(),
+            document: doc,
+        })
+    }
+
+    /// Parses a document and builds its symbol table without linting it.
+    ///
+    /// Used to index imported files for cross-file navigation; the returned
+    /// result has no diagnostics.
+    pub fn index_document(source: &str, file_path: &str) -> Result<AnalysisResult> {
+        let mut parser = mon_core::parser::Parser::new_with_name(source, file_path.to_string())?;
+        let doc = parser.parse_document()?;
+
+        let symbol_table = SymbolCollector::new(source, file_path).collect(&doc);
+
+        Ok(AnalysisResult {
+            diagnostics: Vec::new(),
+            symbol_table,
+            source: source.to_string(),
+            file_path: file_path.to_string(),
+            document: doc,
         })
     }
+}
+
+/// Walks a parsed document and records its definitions and references.
+struct SymbolCollector<'a> {
+    source: &'a str,
+    index: SourceIndex,
+
+    /// Anchor values after resolution by mon-core (aliases and spreads
+    /// expanded); empty when the document does not resolve
+    resolved_anchors: HashMap<String, MonValue>,
+
+    table: SymbolTable,
+}
+
+impl<'a> SymbolCollector<'a> {
+    fn new(source: &'a str, file_path: &str) -> Self {
+        Self {
+            source,
+            index: SourceIndex::new(source),
+            resolved_anchors: Self::resolve_anchors(source, file_path),
+            table: SymbolTable::new(),
+        }
+    }
+
+    /// Resolves the document with mon-core and collects each anchor's
+    /// resolved value. Resolution failures (missing imports, validation
+    /// errors) leave the map empty; hover then falls back to the source value.
+    ///
+    /// The resolver copies an anchor's value to every alias of it, and those
+    /// copies are not expanded further, so the fully expanded copy is kept.
+    /// Object members come back from the resolver in arbitrary order and are
+    /// put back into source order.
+    fn resolve_anchors(source: &str, file_path: &str) -> HashMap<String, MonValue> {
+        fn walk(value: &MonValue, anchors: &mut HashMap<String, MonValue>) {
+            if let Some(anchor) = &value.anchor {
+                let replace = anchors
+                    .get(anchor)
+                    .is_none_or(|existing| !is_expanded(existing) && is_expanded(value));
+                if replace {
+                    let mut value = value.clone();
+                    sort_members(&mut value);
+                    anchors.insert(anchor.clone(), value);
+                }
+            }
+            match &value.kind {
+                MonValueKind::Object(members) => {
+                    for member in members {
+                        if let Member::Pair(pair) = member {
+                            walk(&pair.value, anchors);
+                        }
+                    }
+                }
+                MonValueKind::Array(items) => items.iter().for_each(|item| walk(item, anchors)),
+                _ => {}
+            }
+        }
+
+        fn is_expanded(value: &MonValue) -> bool {
+            match &value.kind {
+                MonValueKind::Alias(_) | MonValueKind::ArraySpread(_) => false,
+                MonValueKind::Object(members) => members.iter().all(|member| match member {
+                    Member::Pair(pair) => is_expanded(&pair.value),
+                    Member::Spread(_) => false,
+                    _ => true,
+                }),
+                MonValueKind::Array(items) => items.iter().all(is_expanded),
+                _ => true,
+            }
+        }
+
+        fn sort_members(value: &mut MonValue) {
+            match &mut value.kind {
+                MonValueKind::Object(members) => {
+                    members.sort_by_key(|member| match member {
+                        Member::Pair(pair) => pair.value.pos_start,
+                        Member::TypeDefinition(typedef) => typedef.pos_start,
+                        _ => usize::MAX,
+                    });
+                    for member in members {
+                        if let Member::Pair(pair) = member {
+                            sort_members(&mut pair.value);
+                        }
+                    }
+                }
+                MonValueKind::Array(items) => items.iter_mut().for_each(sort_members),
+                _ => {}
+            }
+        }
+
+        let mut anchors = HashMap::new();
+        if let Ok(result) = mon_core::api::analyze(source, file_path) {
+            walk(&result.document.root, &mut anchors);
+        }
+        anchors
+    }
 
     /// Builds the symbol table from the AST.
-    fn build_symbol_table(&self, doc: &MonDocument, source: &str, table: &mut SymbolTable) {
-        // Track imports
+    fn collect(mut self, doc: &MonDocument) -> SymbolTable {
+        // Track imports, and each named specifier as a reference into the imported file
         for import in &doc.imports {
-            table.add_symbol(Symbol {
+            let range = self.range(import.pos_start, import.pos_end);
+            self.table.add_symbol(Symbol {
                 name: import.path.clone(),
                 kind: SymbolKind::Import,
-                range: Range::from_byte_offsets(source, import.pos_start, import.pos_end),
+                range,
+                selection_range: range,
                 detail: Some(format!("import from {}", import.path)),
                 documentation: None,
+                value: None,
             });
+
+            if let ImportSpec::Named(specifiers) = &import.spec {
+                let spans = self.index.import_name_spans(import);
+                for (specifier, span) in specifiers.iter().zip(spans) {
+                    self.table.add_reference(SymbolReference {
+                        symbol_name: specifier.name.clone(),
+                        symbol_kind: if specifier.is_anchor {
+                            SymbolKind::Anchor
+                        } else {
+                            SymbolKind::Type
+                        },
+                        range: self.range(span.start, span.end),
+                        reference_kind: ReferenceKind::Import,
+                    });
+                }
+            }
         }
 
         // Recursively track anchors and types
-        self.build_symbols_from_value(&doc.root, source, table);
+        self.visit_value(&doc.root);
+        self.table
     }
 
     /// Recursively builds symbols from a value.
-    fn build_symbols_from_value(&self, value: &MonValue, source: &str, table: &mut SymbolTable) {
-        // Track anchor definitions
-        if let Some(anchor_name) = &value.anchor {
-            table.add_symbol(Symbol {
-                name: anchor_name.clone(),
-                kind: SymbolKind::Anchor,
-                range: Range::from_byte_offsets(source, value.pos_start, value.pos_end),
-                detail: self.get_value_preview(value),
-                documentation: None,
-            });
-        }
-
-        // Track references (aliases and spreads)
+    fn visit_value(&mut self, value: &MonValue) {
+        // Track references (aliases, spreads and enum values)
         match &value.kind {
-            MonValueKind::Alias(name) => {
-                table.add_reference(SymbolReference {
+            MonValueKind::Alias(name) | MonValueKind::ArraySpread(name) => {
+                let reference_kind = if matches!(value.kind, MonValueKind::Alias(_)) {
+                    ReferenceKind::Alias
+                } else {
+                    ReferenceKind::Spread
+                };
+                let span = self
+                    .index
+                    .name_after(value.pos_start)
+                    .unwrap_or(value.pos_start..value.pos_end);
+                self.table.add_reference(SymbolReference {
                     symbol_name: name.clone(),
                     symbol_kind: SymbolKind::Anchor,
-                    range: Range::from_byte_offsets(source, value.pos_start, value.pos_end),
-                    reference_kind: ReferenceKind::Alias,
+                    range: self.range(span.start, span.end),
+                    reference_kind,
                 });
             }
-            MonValueKind::ArraySpread(name) => {
-                table.add_reference(SymbolReference {
-                    symbol_name: name.clone(),
-                    symbol_kind: SymbolKind::Anchor,
-                    range: Range::from_byte_offsets(source, value.pos_start, value.pos_end),
-                    reference_kind: ReferenceKind::Spread,
-                });
+            MonValueKind::EnumValue { enum_name, .. } => {
+                if let Some(span) = self.index.name_after(value.pos_start) {
+                    let end = (span.start + enum_name.len()).min(span.end);
+                    self.table.add_reference(SymbolReference {
+                        symbol_name: enum_name.clone(),
+                        symbol_kind: SymbolKind::Type,
+                        range: self.range(span.start, end),
+                        reference_kind: ReferenceKind::EnumValue,
+                    });
+                }
             }
             MonValueKind::Object(members) => {
-                for member in members {
+                let spans = self.index.member_spans(value);
+                for (i, member) in members.iter().enumerate() {
+                    let span = spans.get(i);
                     match member {
                         Member::TypeDefinition(typedef) => {
                             // Track type definitions
-                            table.add_symbol(Symbol {
+                            let name_start = typedef.name_span.offset();
+                            let name_end = name_start + typedef.name_span.len();
+                            let start = span.map_or(typedef.pos_start, |s| s.span.start);
+                            self.table.add_symbol(Symbol {
                                 name: typedef.name.clone(),
                                 kind: SymbolKind::Type,
-                                range: Range::from_byte_offsets(
-                                    source,
-                                    value.pos_start,
-                                    value.pos_end,
-                                ),
-                                detail: Some(format!("{:?}", typedef.def_type)),
-                                documentation: None,
+                                range: self.range(typedef.pos_start, typedef.pos_end),
+                                selection_range: self.range(name_start, name_end),
+                                detail: Some(match typedef.def_type {
+                                    TypeDef::Struct(_) => "#struct".to_string(),
+                                    TypeDef::Enum(_) => "#enum".to_string(),
+                                }),
+                                documentation: self.index.doc_comment_before(self.source, start),
+                                value: Some(render::type_definition(typedef)),
                             });
+
+                            self.add_type_members(typedef);
+
+                            // Field types and defaults may refer to other types and anchors
+                            if let TypeDef::Struct(struct_def) = &typedef.def_type {
+                                for field in &struct_def.fields {
+                                    self.visit_type_spec(&field.type_spec);
+                                    if let Some(default) = &field.default_value {
+                                        self.visit_value(default);
+                                    }
+                                }
+                            }
                         }
                         Member::Pair(pair) => {
+                            // Track anchor definitions (`&name: value` or `key: &name value`)
+                            if let Some(anchor_name) = &pair.value.anchor {
+                                let whole = span
+                                    .map(|s| s.span.clone())
+                                    .unwrap_or(pair.value.pos_start..pair.value.pos_end);
+                                let name =
+                                    span.and_then(|s| s.anchor.clone()).unwrap_or(whole.clone());
+                                self.add_anchor(anchor_name, &pair.value, whole, name);
+                            }
+
+                            if let Some(type_spec) = &pair.validation {
+                                self.visit_type_spec(type_spec);
+                            }
+
                             // Recurse into pair values
-                            self.build_symbols_from_value(&pair.value, source, table);
+                            self.visit_value(&pair.value);
                         }
                         Member::Spread(name) => {
                             // Track spread reference
-                            table.add_reference(SymbolReference {
+                            let name_span = span
+                                .and_then(|s| s.name.clone())
+                                .unwrap_or(value.pos_start..value.pos_end);
+                            self.table.add_reference(SymbolReference {
                                 symbol_name: name.clone(),
                                 symbol_kind: SymbolKind::Anchor,
-                                range: Range::from_byte_offsets(
-                                    source,
-                                    value.pos_start,
-                                    value.pos_end,
-                                ),
+                                range: self.range(name_span.start, name_span.end),
                                 reference_kind: ReferenceKind::Spread,
                             });
                         }
@@ -164,15 +339,111 @@ impl AnalysisService {
             }
             MonValueKind::Array(items) => {
                 for item in items {
-                    self.build_symbols_from_value(item, source, table);
+                    // Array items can carry their own anchors (`[&first { ... }]`)
+                    if let Some(anchor_name) = &item.anchor {
+                        let name =
+                            self.index.anchor_before(item).unwrap_or(item.pos_start..item.pos_end);
+                        let whole = name.start.saturating_sub(1)..item.pos_end;
+                        self.add_anchor(anchor_name, item, whole, name);
+                    }
+                    self.visit_value(item);
                 }
             }
             _ => {}
         }
     }
 
+    /// Records an anchor definition spanning `whole`, named at `name`.
+    fn add_anchor(
+        &mut self,
+        anchor_name: &str,
+        value: &MonValue,
+        whole: ByteRange<usize>,
+        name: ByteRange<usize>,
+    ) {
+        let resolved = self.resolved_anchors.get(anchor_name).unwrap_or(value);
+        self.table.add_symbol(Symbol {
+            name: anchor_name.to_string(),
+            kind: SymbolKind::Anchor,
+            range: self.range(whole.start, whole.end),
+            selection_range: self.range(name.start, name.end),
+            detail: Self::get_value_preview(value),
+            documentation: self.index.doc_comment_before(self.source, whole.start),
+            value: Some(render::value(resolved)),
+        });
+    }
+
+    /// Records the fields of a struct or the variants of an enum, named
+    /// `Type.member` so members of different types stay distinct.
+    fn add_type_members(&mut self, typedef: &TypeDefinition) {
+        let spans = self.index.type_member_spans(typedef);
+        let members: Vec<_> = match &typedef.def_type {
+            TypeDef::Struct(def) => def
+                .fields
+                .iter()
+                .map(|field| {
+                    let detail = field.type_spec.to_string();
+                    (&field.name, SymbolKind::Field, detail, render::field(field))
+                })
+                .collect(),
+            TypeDef::Enum(def) => def
+                .variants
+                .iter()
+                .map(|variant| {
+                    let value = format!("${}.{}", typedef.name, variant);
+                    (variant, SymbolKind::EnumVariant, typedef.name.clone(), value)
+                })
+                .collect(),
+        };
+
+        let type_line = Position::from_byte_offset(self.source, typedef.pos_start).line;
+        for ((name, kind, detail, value), span) in members.into_iter().zip(spans) {
+            let range = self.range(span.span.start, span.span.end);
+            let selection = span.name.unwrap_or(span.span.clone());
+
+            // Members on the type's own line would pick up the type's comment
+            let documentation = if range.start.line == type_line {
+                None
+            } else {
+                self.index.doc_comment_before(self.source, span.span.start)
+            };
+
+            self.table.add_symbol(Symbol {
+                name: format!("{}.{}", typedef.name, name),
+                kind,
+                range,
+                selection_range: self.range(selection.start, selection.end),
+                detail: Some(detail),
+                documentation,
+                value: Some(value),
+            });
+        }
+    }
+
+    /// Records references to user-defined types in a `:: Type` annotation.
+    fn visit_type_spec(&mut self, type_spec: &TypeSpec) {
+        match type_spec {
+            TypeSpec::Simple(name, span) => {
+                if !BUILTIN_TYPES.contains(&name.as_str()) {
+                    self.table.add_reference(SymbolReference {
+                        symbol_name: name.clone(),
+                        symbol_kind: SymbolKind::Type,
+                        range: self.range(span.offset(), span.offset() + span.len()),
+                        reference_kind: ReferenceKind::TypeAnnotation,
+                    });
+                }
+            }
+            TypeSpec::Collection(items, _) => {
+                for item in items {
+                    self.visit_type_spec(item);
+                }
+            }
+            TypeSpec::Spread(inner, _) => self.visit_type_spec(inner),
+        }
+    }
+
     /// Gets a preview string for a value (for hover/detail text).
-    fn get_value_preview(&self, value: &MonValue) -> Option<String> {
+    fn get_value_preview(value: &MonValue) -> Option<String> {
         match &value.kind {
             MonValueKind::Object(_) => Some("{ ... }".to_string()),
             MonValueKind::Array(_) => Some("[ ... ]".to_string()),
@@ -184,19 +455,8 @@ impl AnalysisService {
         }
     }
 
-    /// Detects unused symbols and adds diagnostics.
-    fn detect_unused_symbols(&self, table: &SymbolTable, source: &str, result: &mut LintResult) {
-        if self.config.warn_unused_anchors {
-            for symbol in table.find_unused_symbols(SymbolKind::Anchor) {
-                result.add_diagnostic_with_range(
-                    crate::linter::diagnostic::DiagnosticCode::UnusedAnchor,
-                    format!("Anchor '{}' is defined but never used", symbol.name),
-                    Some(symbol.range),
-                    vec![],
-                    vec![crate::linter::DiagnosticTag::Unnecessary],
-                );
-            }
-        }
+    fn range(&self, start: usize, end: usize) -> Range {
+        Range::from_byte_offsets(self.source, start, end)
     }
 }
 
@@ -213,30 +473,18 @@ pub struct AnalysisResult {
 
     /// Original source text
     pub source: String,
-}
 
-impl AnalysisResult {
-    /// Checks if there are any error-level diagnostics.
-    pub fn has_errors(&self) -> bool {
-        self.diagnostics
-            .iter()
-            .any(|d| matches!(d.severity, crate::linter::DiagnosticSeverity::Error))
-    }
+    /// Path of the analyzed file, as passed to `analyze_document`
+    pub file_path: String,
 
-    /// Gets all error diagnostics.
-    pub fn errors(&self) -> Vec<&crate::linter::Diagnostic> {
-        self.diagnostics
-            .iter()
-            .filter(|d| matches!(d.severity, crate::linter::DiagnosticSeverity::Error))
-            .collect()
-    }
+    /// The parsed (unresolved) document
+    pub document: MonDocument,
+}
 
-    /// Gets all warning diagnostics.
-    pub fn warnings(&self) -> Vec<&crate::linter::Diagnostic> {
-        self.diagnostics
-            .iter()
-            .filter(|d| matches!(d.severity, crate::linter::DiagnosticSeverity::Warning))
-            .collect()
+impl AnalysisResult {
+    /// Finds the anchor or type definition or reference at `position`.
+    pub fn find_symbol_at(&self, position: Position) -> Option<SymbolAt<'_>> {
+        self.symbol_table.find_symbol_at(position)
     }
 }
 
@@ -0,0 +1,569 @@
+//! Context-aware completion for MON documents.
+//!
+//! Completion works on the text as the user is typing it, which usually does
+//! not parse. The context at the cursor is found from the token stream alone,
+//! and the candidates come from the last successful analysis of the document
+//! (its `SymbolTable` and type definitions) plus the files it imports:
+//!
+//! | Context                         | Candidates                         |
+//! | ------------------------------- | ---------------------------------- |
+//! | `*` / `...*`                    | Anchor names (and import namespaces) |
+//! | `*ns.`                          | Anchors exported by `ns`           |
+//! | `$`                             | Enum names                         |
+//! | `$Enum.`                        | Variants of `Enum`                 |
+//! | `::`, `:: [`, `field(`          | Type names (built-in and defined)  |
+//! | key position in `:: Struct = {` | Required fields not yet written    |
+//! | `import { ... } from "..."`     | Anchors and types the file exports |
+
+use crate::linter::api::BUILTIN_TYPES;
+use crate::linter::navigation::{Navigator, find_type_definition};
+use crate::linter::source_index::SourceIndex;
+use crate::linter::{Position, Range, Symbol, SymbolKind};
+use mon_core::ast::{ImportSpec, TypeDef};
+use mon_core::lexer::{Token, TokenType};
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// What a completion candidate is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CompletionKind {
+    /// An anchor name
+    Anchor,
+
+    /// A type name (struct, enum or built-in)
+    Type,
+
+    /// An enum variant
+    EnumVariant,
+
+    /// A struct field
+    Field,
+
+    /// A namespace from `import * as ns`
+    Namespace,
+}
+
+/// A single completion candidate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CompletionItem {
+    /// The name shown in the completion list
+    pub label: String,
+
+    /// What kind of name this is
+    pub kind: CompletionKind,
+
+    /// Short description, e.g. a value preview or a field's type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+
+    /// Doc comment of the definition
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documentation: Option<String>,
+
+    /// Text to insert in place of `replace_range`
+    pub insert_text: String,
+
+    /// The partially typed name at the cursor, replaced on accept
+    pub replace_range: Range,
+}
+
+/// What kind of name is expected at the cursor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Context {
+    Anchor { namespace: Option<String> },
+    EnumName,
+    EnumVariant { enum_name: String },
+    Type { namespace: Option<String> },
+    ImportName { path: String, listed: Vec<String>, needs_sigil: bool },
+    Field { type_name: String, present: Vec<String> },
+}
+
+/// Computes completions at `position` in `text`.
+///
+/// `text` is the current (possibly unparsable) document text; `navigator`
+/// is rooted at the last successful analysis of the same document.
+pub fn complete(
+    navigator: &mut Navigator<'_>,
+    text: &str,
+    position: Position,
+) -> Vec<CompletionItem> {
+    let offset = position.to_byte_offset(text);
+    let index = SourceIndex::new(text);
+    let tokens = index.tokens();
+    let split = tokens.partition_point(|t| t.pos_start < offset);
+    let (mut before, after) = tokens.split_at(split);
+
+    // The partially typed name under the cursor, if any
+    let (word_start, word_end) = match before.last() {
+        Some(token)
+            if matches!(token.ttype, TokenType::Identifier(_)) && token.pos_end >= offset =>
+        {
+            before = &before[..before.len() - 1];
+            (token.pos_start, token.pos_end)
+        }
+        _ => (offset, offset),
+    };
+    let prefix = &text[word_start..offset];
+    let replace_range = Range::from_byte_offsets(text, word_start, word_end);
+
+    let Some(context) = detect_context(before, after) else {
+        return Vec::new();
+    };
+
+    let mut items = candidates(navigator, &context);
+    items.retain(|(label, ..)| label.starts_with(prefix));
+    items.sort_by(|a, b| a.0.cmp(&b.0));
+    items.dedup_by(|a, b| a.0 == b.0);
+
+    items
+        .into_iter()
+        .map(|(label, kind, detail, documentation)| {
+            let insert_text = match &context {
+                Context::ImportName { needs_sigil: true, .. } if kind == CompletionKind::Anchor => {
+                    format!("&{}", label)
+                }
+                _ => label.clone(),
+            };
+            CompletionItem { label, kind, detail, documentation, insert_text, replace_range }
+        })
+        .collect()
+}
+
+type Candidate = (String, CompletionKind, Option<String>, Option<String>);
+
+/// Finds the completion context from the tokens before and after the cursor
+/// (the partial name at the cursor already removed).
+fn detect_context(before: &[Token], after: &[Token]) -> Option<Context> {
+    if let Some(context) = import_context(before, after) {
+        return Some(context);
+    }
+
+    // Qualifier written before the name: `ns.` in `*ns.na`, `Status.` in `$Status.Ac`
+    let mut rest = before;
+    let mut segments = Vec::new();
+    while let [head @ .., name, dot] = rest {
+        match (&name.ttype, &dot.ttype) {
+            (TokenType::Identifier(name), TokenType::Dot) => {
+                segments.push(name.clone());
+                rest = head;
+            }
+            _ => break,
+        }
+    }
+    segments.reverse();
+    let qualifier = (!segments.is_empty()).then(|| segments.join("."));
+
+    match &rest.last()?.ttype {
+        TokenType::Asterisk => Some(Context::Anchor { namespace: qualifier }),
+        TokenType::Dollar => Some(match qualifier {
+            Some(enum_name) => Context::EnumVariant { enum_name },
+            None => Context::EnumName,
+        }),
+        _ if is_type_position(rest) => Some(Context::Type { namespace: qualifier }),
+        TokenType::LBrace | TokenType::Comma if qualifier.is_none() => field_context(rest, after),
+        _ => None,
+    }
+}
+
+/// `import { &a, B, <cursor> } from "./file.mon"`
+fn import_context(before: &[Token], after: &[Token]) -> Option<Context> {
+    let import = before.iter().rposition(|t| t.ttype == TokenType::Import)?;
+    let spec = &before[import + 1..];
+    if spec.first()?.ttype != TokenType::LBrace
+        || spec.iter().any(|t| matches!(t.ttype, TokenType::RBrace | TokenType::From))
+    {
+        return None;
+    }
+
+    let listed = spec
+        .iter()
+        .filter_map(|t| match &t.ttype {
+            TokenType::Identifier(name) => Some(name.clone()),
+            _ => None,
+        })
+        .collect();
+    let needs_sigil = before.last()?.ttype != TokenType::Ampersand;
+
+    let from = after.iter().position(|t| t.ttype == TokenType::From)?;
+    let path = match &after.get(from + 1)?.ttype {
+        TokenType::String(path) => path.clone(),
+        _ => return None,
+    };
+
+    Some(Context::ImportName { path, listed, needs_sigil })
+}
+
+/// Whether a type name is expected: after `::` (including inside a
+/// collection type like `:: [String, `) or as a struct field type `name(`.
+fn is_type_position(before: &[Token]) -> bool {
+    for (i, token) in before.iter().enumerate().rev() {
+        match token.ttype {
+            TokenType::DoubleColon => return true,
+            TokenType::LParen => return enclosing_struct_body(&before[..i]),
+            TokenType::LBracket | TokenType::Comma | TokenType::Spread | TokenType::Dot => {}
+            TokenType::Identifier(_) if i + 1 < before.len() => {}
+            _ => return false,
+        }
+    }
+    false
+}
+
+/// Whether the innermost unclosed `{` before the end of `tokens` opens a
+/// `#struct` body.
+fn enclosing_struct_body(tokens: &[Token]) -> bool {
+    match enclosing_brace(tokens) {
+        Some(open) if open >= 2 => {
+            tokens[open - 2].ttype == TokenType::Hash
+                && tokens[open - 1].ttype == TokenType::Identifier("struct".to_string())
+        }
+        _ => false,
+    }
+}
+
+/// Index of the innermost unclosed `{` in `tokens`.
+fn enclosing_brace(tokens: &[Token]) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().rev() {
+        match token.ttype {
+            TokenType::RBrace | TokenType::RBracket | TokenType::RParen => depth += 1,
+            TokenType::LBrace if depth == 0 => return Some(i),
+            TokenType::LBrace | TokenType::LBracket | TokenType::LParen => {
+                depth = depth.saturating_sub(1)
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Key position inside `key :: Struct = { ... }`.
+fn field_context(before: &[Token], after: &[Token]) -> Option<Context> {
+    let open = enclosing_brace(before)?;
+    if open == 0 || before[open - 1].ttype != TokenType::Equals {
+        return None;
+    }
+
+    // The type name may be dotted (`schemas.User`)
+    let head = &before[..open - 1];
+    let start = head.iter().rposition(|t| t.ttype == TokenType::DoubleColon)? + 1;
+    if start == head.len() {
+        return None;
+    }
+    let mut type_name = String::new();
+    for token in &head[start..] {
+        match &token.ttype {
+            TokenType::Identifier(name) => type_name.push_str(name),
+            TokenType::Dot => type_name.push('.'),
+            _ => return None,
+        }
+    }
+
+    // Keys already written anywhere in this object
+    let mut present = Vec::new();
+    let mut depth = 0usize;
+    let body = before[open + 1..].iter().chain(after.iter()).collect::<Vec<_>>();
+    for (i, token) in body.iter().enumerate() {
+        match &token.ttype {
+            TokenType::LBrace | TokenType::LBracket | TokenType::LParen => depth += 1,
+            TokenType::RBrace if depth == 0 => break,
+            TokenType::RBrace | TokenType::RBracket | TokenType::RParen => depth -= 1,
+            TokenType::Identifier(key) | TokenType::String(key) if depth == 0 => {
+                let is_key = body.get(i + 1).is_some_and(|next| {
+                    matches!(next.ttype, TokenType::Colon | TokenType::DoubleColon)
+                });
+                if is_key {
+                    present.push(key.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(Context::Field { type_name, present })
+}
+
+/// Gathers the candidates for a context, unfiltered.
+fn candidates(navigator: &mut Navigator<'_>, context: &Context) -> Vec<Candidate> {
+    let root = navigator.root();
+    match context {
+        Context::Anchor { namespace: None } => {
+            let mut items: Vec<_> = root
+                .symbol_table
+                .symbols_by_kind(SymbolKind::Anchor)
+                .into_iter()
+                .map(|symbol| from_symbol(symbol, CompletionKind::Anchor))
+                .collect();
+            items.extend(imported(navigator, SymbolKind::Anchor));
+            items.extend(namespaces(navigator));
+            items
+        }
+        Context::Anchor { namespace: Some(namespace) } => {
+            exported_by_namespace(navigator, namespace, SymbolKind::Anchor)
+        }
+        Context::Type { namespace: None } => {
+            let mut items: Vec<_> = BUILTIN_TYPES
+                .iter()
+                .map(|name| {
+                    (name.to_string(), CompletionKind::Type, Some("built-in".to_string()), None)
+                })
+                .collect();
+            items.extend(
+                root.symbol_table
+                    .symbols_by_kind(SymbolKind::Type)
+                    .into_iter()
+                    .map(|symbol| from_symbol(symbol, CompletionKind::Type)),
+            );
+            items.extend(imported(navigator, SymbolKind::Type));
+            items.extend(namespaces(navigator));
+            items
+        }
+        Context::Type { namespace: Some(namespace) } => {
+            exported_by_namespace(navigator, namespace, SymbolKind::Type)
+        }
+        Context::EnumName => {
+            let mut items: Vec<_> = root
+                .symbol_table
+                .symbols_by_kind(SymbolKind::Type)
+                .into_iter()
+                .filter(|symbol| {
+                    find_type_definition(&root.document.root, &symbol.name)
+                        .is_some_and(|typedef| matches!(typedef.def_type, TypeDef::Enum(_)))
+                })
+                .map(|symbol| from_symbol(symbol, CompletionKind::Type))
+                .collect();
+            let imported_enums: Vec<_> = imported(navigator, SymbolKind::Type)
+                .into_iter()
+                .filter(|(name, ..)| {
+                    navigator
+                        .type_definition(name)
+                        .is_some_and(|typedef| matches!(typedef.def_type, TypeDef::Enum(_)))
+                })
+                .collect();
+            items.extend(imported_enums);
+            items
+        }
+        Context::EnumVariant { enum_name } => match navigator.type_definition(enum_name) {
+            Some(typedef) => match typedef.def_type {
+                TypeDef::Enum(def) => def
+                    .variants
+                    .into_iter()
+                    .map(|variant| {
+                        (variant, CompletionKind::EnumVariant, Some(enum_name.clone()), None)
+                    })
+                    .collect(),
+                TypeDef::Struct(_) => Vec::new(),
+            },
+            None => Vec::new(),
+        },
+        Context::Field { type_name, present } => match navigator.type_definition(type_name) {
+            Some(typedef) => match typedef.def_type {
+                TypeDef::Struct(def) => def
+                    .fields
+                    .into_iter()
+                    .filter(|field| field.default_value.is_none() && !present.contains(&field.name))
+                    .map(|field| {
+                        let detail = Some(field.type_spec.to_string());
+                        (field.name, CompletionKind::Field, detail, None)
+                    })
+                    .collect(),
+                TypeDef::Enum(_) => Vec::new(),
+            },
+            None => Vec::new(),
+        },
+        Context::ImportName { path, listed, .. } => navigator
+            .exports(path)
+            .iter()
+            .filter(|symbol| !listed.contains(&symbol.name))
+            .map(|symbol| {
+                let kind = match symbol.kind {
+                    SymbolKind::Anchor => CompletionKind::Anchor,
+                    _ => CompletionKind::Type,
+                };
+                from_symbol(symbol, kind)
+            })
+            .collect(),
+    }
+}
+
+fn from_symbol(symbol: &Symbol, kind: CompletionKind) -> Candidate {
+    (symbol.name.clone(), kind, symbol.detail.clone(), symbol.documentation.clone())
+}
+
+/// Names brought in by `import { ... }` with the given kind.
+fn imported(navigator: &mut Navigator<'_>, kind: SymbolKind) -> Vec<Candidate> {
+    let names: Vec<String> = navigator
+        .root()
+        .document
+        .imports
+        .iter()
+        .flat_map(|import| match &import.spec {
+            ImportSpec::Named(specifiers) => specifiers
+                .iter()
+                .filter(|s| s.is_anchor == (kind == SymbolKind::Anchor))
+                .map(|s| s.name.clone())
+                .collect(),
+            ImportSpec::Namespace(_) => Vec::new(),
+        })
+        .collect();
+
+    let completion_kind =
+        if kind == SymbolKind::Anchor { CompletionKind::Anchor } else { CompletionKind::Type };
+    names
+        .into_iter()
+        .map(|name| {
+            let definition = navigator.resolve_name(&name, kind);
+            let symbol = definition.and_then(|d| navigator.symbol(&d).cloned());
+            match symbol {
+                Some(symbol) => (name, completion_kind, symbol.detail, symbol.documentation),
+                None => (name, completion_kind, None, None),
+            }
+        })
+        .collect()
+}
+
+/// Namespaces brought in by `import * as ns`.
+fn namespaces(navigator: &Navigator<'_>) -> Vec<Candidate> {
+    navigator
+        .root()
+        .document
+        .imports
+        .iter()
+        .filter_map(|import| match &import.spec {
+            ImportSpec::Namespace(namespace) => Some((
+                namespace.clone(),
+                CompletionKind::Namespace,
+                Some(format!("import from {}", import.path)),
+                None,
+            )),
+            ImportSpec::Named(_) => None,
+        })
+        .collect()
+}
+
+/// Top-level anchors or types of the file imported as `namespace`.
+fn exported_by_namespace(
+    navigator: &mut Navigator<'_>,
+    namespace: &str,
+    kind: SymbolKind,
+) -> Vec<Candidate> {
+    let path = navigator.root().document.imports.iter().find_map(|import| match &import.spec {
+        ImportSpec::Namespace(ns) if ns == namespace => Some(import.path.clone()),
+        _ => None,
+    });
+    let Some(path) = path else {
+        return Vec::new();
+    };
+
+    let completion_kind =
+        if kind == SymbolKind::Anchor { CompletionKind::Anchor } else { CompletionKind::Type };
+    let mut seen = HashSet::new();
+    navigator
+        .exports(&path)
+        .iter()
+        .filter(|symbol| symbol.kind == kind && seen.insert(symbol.name.clone()))
+        .map(|symbol| from_symbol(symbol, completion_kind))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintConfig;
+    use crate::linter::api::AnalysisService;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Completes at the `|` marker in `text`, using `analyzed` as the last
+    /// successful parse of the document.
+    fn complete_at(analyzed: &str, text: &str, path: &str) -> Vec<CompletionItem> {
+        let result =
+            AnalysisService::new(LintConfig::default()).analyze_document(analyzed, path).unwrap();
+        let offset = text.find('|').unwrap();
+        let text = text.replace('|', "");
+        let position = Position::from_byte_offset(&text, offset);
+        complete(&mut Navigator::new(&result), &text, position)
+    }
+
+    fn labels(items: &[CompletionItem]) -> Vec<&str> {
+        items.iter().map(|item| item.label.as_str()).collect()
+    }
+
+    const TYPES: &str = r#"{
+    // Lifecycle of a task.
+    Status: #enum { Active, Done },
+    Task: #struct { title(String), owner(String), status(Status) = $Status.Active },
+    &base: { retries: 3 },
+    &bare: 1,
+}"#;
+
+    #[test]
+    fn test_anchor_completion() {
+        let text = "{\n    Status: #enum { Active, Done },\n    &base: { retries: 3 },\n    &bare: 1,\n    copy: *ba|\n}";
+        let items = complete_at(TYPES, text, "/tmp/completion.mon");
+        assert_eq!(labels(&items), vec!["bare", "base"]);
+        assert_eq!(items[1].detail.as_deref(), Some("{ ... }"));
+        assert_eq!(items[1].replace_range.start, Position::new(4, 11));
+
+        let text = "{ &base: {}, x: { ...*| } }";
+        assert_eq!(labels(&complete_at(TYPES, text, "/tmp/completion.mon")), vec!["bare", "base"]);
+    }
+
+    #[test]
+    fn test_enum_and_type_completion() {
+        let items = complete_at(TYPES, "{ s: $Status.| }", "/tmp/completion.mon");
+        assert_eq!(labels(&items), vec!["Active", "Done"]);
+        assert_eq!(items[0].kind, CompletionKind::EnumVariant);
+
+        let items = complete_at(TYPES, "{ s: $| }", "/tmp/completion.mon");
+        assert_eq!(labels(&items), vec!["Status"]);
+        assert_eq!(items[0].documentation.as_deref(), Some("Lifecycle of a task."));
+
+        let items = complete_at(TYPES, "{ t :: T| }", "/tmp/completion.mon");
+        assert_eq!(labels(&items), vec!["Task"]);
+
+        let items = complete_at(TYPES, "{ t :: [S| }", "/tmp/completion.mon");
+        assert_eq!(labels(&items), vec!["Status", "String"]);
+
+        let items = complete_at(TYPES, "{ P: #struct { x(Num| } }", "/tmp/completion.mon");
+        assert_eq!(labels(&items), vec!["Number"]);
+    }
+
+    #[test]
+    fn test_missing_field_completion() {
+        let text = "{\n    t :: Task = {\n        title: \"x\",\n        |\n    },\n}";
+        let items = complete_at(TYPES, text, "/tmp/completion.mon");
+        assert_eq!(labels(&items), vec!["owner"]);
+        assert_eq!(items[0].detail.as_deref(), Some("String"));
+
+        // Plain objects offer nothing
+        assert!(complete_at(TYPES, "{ t: { | } }", "/tmp/completion.mon").is_empty());
+    }
+
+    #[test]
+    fn test_import_completion() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("types.mon"), TYPES).unwrap();
+        let main = dir.path().join("main.mon").display().to_string();
+
+        let items = complete_at("{}", "import { Task, | } from \"./types.mon\"\n{}", &main);
+        assert_eq!(labels(&items), vec!["Status", "bare", "base"]);
+        let base = items.iter().find(|item| item.label == "base").unwrap();
+        assert_eq!(base.insert_text, "&base");
+
+        let items = complete_at("{}", "import { &b| } from \"./types.mon\"\n{}", &main);
+        assert_eq!(
+            items.iter().map(|i| i.insert_text.as_str()).collect::<Vec<_>>(),
+            vec!["bare", "base"]
+        );
+
+        let analyzed = "import * as types from \"./types.mon\"\n{}";
+        let items =
+            complete_at(analyzed, "import * as types from \"./types.mon\"\n{ a: *types.| }", &main);
+        assert_eq!(labels(&items), vec!["bare", "base"]);
+
+        let items =
+            complete_at(analyzed, "import * as types from \"./types.mon\"\n{ a: *| }", &main);
+        assert_eq!(labels(&items), vec!["types"]);
+        assert_eq!(items[0].kind, CompletionKind::Namespace);
+    }
+}
@@ -1,7 +1,11 @@
 // Complexity analysis for MON structures
 
-use crate::linter::{LintConfig, LintResult, diagnostic::DiagnosticCode};
+use crate::linter::source_index::{SourceIndex, value_head};
+use crate::linter::{
+    LintConfig, LintResult, Range, RelatedInformation, diagnostic::DiagnosticCode,
+};
 use mon_core::ast::{Member, MonValue, MonValueKind};
+use std::ops::Range as ByteRange;
 
 pub struct ComplexityAnalyzer {
     config: LintConfig,
@@ -12,108 +16,139 @@ impl ComplexityAnalyzer {
         Self { config }
     }
 
-    pub fn analyze(&self, root: &MonValue, result: &mut LintResult) {
+    pub fn analyze(&self, root: &MonValue, source: &str, result: &mut LintResult) {
+        let index = SourceIndex::new(source);
+        let range = |span: ByteRange<usize>| Range::from_byte_offsets(source, span.start, span.end);
+
         // Analyze nesting depth
-        let max_depth = self.calculate_max_depth(root, 0);
-        if max_depth > self.config.max_nesting_depth {
-            if max_depth > self.config.max_nesting_depth + 2 {
-                result.add_diagnostic(
-                    DiagnosticCode::MaxNestingDepth,
-                    format!(
-                        "Maximum nesting depth of {} exceeds limit of {} by more than 2 levels",
-                        max_depth, self.config.max_nesting_depth
-                    ),
-                    None,
-                );
+        let path = deepest_path(root);
+        let max_depth = path.len() - 1;
+        let limit = self.config.max_nesting_depth;
+        if self.config.is_enabled(DiagnosticCode::MaxNestingDepth) && max_depth > limit {
+            let message = if max_depth > limit + 2 {
+                format!(
+                    "Maximum nesting depth of {} exceeds limit of {} by more than 2 levels",
+                    max_depth, limit
+                )
             } else {
-                result.add_diagnostic(
-                    DiagnosticCode::MaxNestingDepth,
-                    format!(
-                        "Maximum nesting depth of {} exceeds recommended limit of {}",
-                        max_depth, self.config.max_nesting_depth
-                    ),
-                    None,
-                );
-            }
+                format!(
+                    "Maximum nesting depth of {} exceeds recommended limit of {}",
+                    max_depth, limit
+                )
+            };
+
+            // Point at the first value past the limit, on the way to the deepest
+            let head = |depth: usize| range(head_in(&index, path[depth - 1], path[depth]));
+            let related = (max_depth > limit + 1)
+                .then(|| {
+                    RelatedInformation::here(
+                        head(max_depth),
+                        format!("Deepest value, at depth {}", max_depth),
+                    )
+                })
+                .into_iter()
+                .collect();
+            result.add_diagnostic_with_range(
+                DiagnosticCode::MaxNestingDepth,
+                message,
+                Some(head(limit + 1)),
+                related,
+                vec![],
+            );
         }
 
         // Analyze object/array sizes
-        self.analyze_sizes(root, result, 0);
-    }
-
-    fn calculate_max_depth(&self, value: &MonValue, current_depth: usize) -> usize {
-        match &value.kind {
-            MonValueKind::Object(members) => {
-                let mut max = current_depth;
-                for member in members {
-                    match member {
-                        Member::Pair(pair) => {
-                            let depth = self.calculate_max_depth(&pair.value, current_depth + 1);
-                            max = max.max(depth);
-                        }
-                        Member::TypeDefinition(_) => {}
-                        Member::Spread(_) => {}
-                        Member::Import(_) => {}
-                    }
-                }
-                max
-            }
-            MonValueKind::Array(items) => {
-                let mut max = current_depth;
-                for item in items {
-                    let depth = self.calculate_max_depth(item, current_depth + 1);
-                    max = max.max(depth);
-                }
-                max
-            }
-            _ => current_depth,
+        if self.config.is_enabled(DiagnosticCode::MaxObjectMembers)
+            || self.config.is_enabled(DiagnosticCode::MaxArrayItems)
+        {
+            self.analyze_sizes(root, value_head(root, None), source, &index, result, 0);
         }
     }
 
-    fn analyze_sizes(&self, value: &MonValue, result: &mut LintResult, depth: usize) {
+    /// `head` is the span diagnostics about `value` point at.
+    fn analyze_sizes(
+        &self,
+        value: &MonValue,
+        head: ByteRange<usize>,
+        source: &str,
+        index: &SourceIndex,
+        result: &mut LintResult,
+        depth: usize,
+    ) {
+        let range = |span: ByteRange<usize>| Range::from_byte_offsets(source, span.start, span.end);
+
         match &value.kind {
             MonValueKind::Object(members) => {
+                let spans = index.member_spans(value);
+
                 // Count non-type-definition members
-                let regular_members: Vec<_> =
-                    members.iter().filter(|m| !matches!(m, Member::TypeDefinition(_))).collect();
+                let regular_members: Vec<_> = members
+                    .iter()
+                    .zip(&spans)
+                    .filter(|(m, _)| !matches!(m, Member::TypeDefinition(_)))
+                    .collect();
 
-                if regular_members.len() > self.config.max_object_members {
-                    result.add_diagnostic(
+                let limit = self.config.max_object_members;
+                if self.config.is_enabled(DiagnosticCode::MaxObjectMembers)
+                    && regular_members.len() > limit
+                {
+                    let (_, first_over) = regular_members[limit];
+                    result.add_diagnostic_with_range(
                         DiagnosticCode::MaxObjectMembers,
                         format!(
                             "Object at depth {} has {} members, exceeds recommended limit of {}",
                             depth,
                             regular_members.len(),
-                            self.config.max_object_members
+                            limit
                         ),
-                        None,
+                        Some(range(head)),
+                        vec![RelatedInformation::here(
+                            range(first_over.span.clone()),
+                            format!("Member {} of {}", limit + 1, regular_members.len()),
+                        )],
+                        vec![],
                     );
                 }
 
                 // Recurse into nested structures
-                for member in members {
+                for (member, span) in members.iter().zip(&spans) {
                     if let Member::Pair(pair) = member {
-                        self.analyze_sizes(&pair.value, result, depth + 1);
+                        let head = value_head(&pair.value, span.name.clone());
+                        self.analyze_sizes(&pair.value, head, source, index, result, depth + 1);
                     }
                 }
             }
             MonValueKind::Array(items) => {
-                if items.len() > self.config.max_array_items {
-                    result.add_diagnostic(
+                let limit = self.config.max_array_items;
+                if self.config.is_enabled(DiagnosticCode::MaxArrayItems) && items.len() > limit {
+                    let first_over = &items[limit];
+                    result.add_diagnostic_with_range(
                         DiagnosticCode::MaxArrayItems,
                         format!(
                             "Array at depth {} has {} items, exceeds recommended limit of {}",
                             depth,
                             items.len(),
-                            self.config.max_array_items
+                            limit
                         ),
-                        None,
+                        Some(range(head)),
+                        vec![RelatedInformation::here(
+                            range(first_over.pos_start..first_over.pos_end),
+                            format!("Item {} of {}", limit + 1, items.len()),
+                        )],
+                        vec![],
                     );
                 }
 
                 // Recurse into nested structures
                 for item in items {
-                    self.analyze_sizes(item, result, depth + 1);
+                    self.analyze_sizes(
+                        item,
+                        value_head(item, None),
+                        source,
+                        index,
+                        result,
+                        depth + 1,
+                    );
                 }
             }
             _ => {}
@@ -121,6 +156,43 @@ impl ComplexityAnalyzer {
     }
 }
 
+/// The values from `value` down to its most deeply nested descendant,
+/// outermost first; a value's depth is its index in the path.
+fn deepest_path(value: &MonValue) -> Vec<&MonValue> {
+    let children: Vec<&MonValue> = match &value.kind {
+        MonValueKind::Object(members) => members
+            .iter()
+            .filter_map(|member| match member {
+                Member::Pair(pair) => Some(&pair.value),
+                _ => None,
+            })
+            .collect(),
+        MonValueKind::Array(items) => items.iter().collect(),
+        _ => Vec::new(),
+    };
+
+    // The first of equally deep paths wins
+    let mut path = children
+        .into_iter()
+        .map(deepest_path)
+        .fold(Vec::new(), |deepest, path| if path.len() > deepest.len() { path } else { deepest });
+    path.insert(0, value);
+    path
+}
+
+/// The span a diagnostic about `value`, written directly in `parent`, points at.
+fn head_in(index: &SourceIndex, parent: &MonValue, value: &MonValue) -> ByteRange<usize> {
+    let key = match &parent.kind {
+        MonValueKind::Object(members) => members
+            .iter()
+            .zip(index.member_spans(parent))
+            .find(|(member, _)| matches!(member, Member::Pair(pair) if std::ptr::eq(&pair.value, value)))
+            .and_then(|(_, span)| span.name),
+        _ => None,
+    };
+    value_head(value, key)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,9 +219,19 @@ mod tests {
 
         let analyzer = ComplexityAnalyzer::new(config);
         let mut result = LintResult::new();
-        analyzer.analyze(&doc.root, &mut result);
+        analyzer.analyze(&doc.root, source, &mut result);
 
         assert!(!result.warnings().is_empty() || !result.errors().is_empty());
+
+        // The first key past the limit, with the deepest one related
+        let diagnostic = &result.diagnostics[0];
+        let range = diagnostic.range.unwrap();
+        assert_eq!(
+            &source[range.start.to_byte_offset(source)..range.end.to_byte_offset(source)],
+            "d"
+        );
+        let deepest = diagnostic.related_information[0].location.range;
+        assert_eq!((deepest.start.line, deepest.start.character), (5, 28));
     }
 
     #[test]
@@ -158,7 +240,7 @@ mod tests {
         for i in 0..25 {
             source.push_str(&format!("    key{}: {},\n", i, i));
         }
-        source.push_str("}");
+        source.push('}');
 
         let mut parser = Parser::new(&source).unwrap();
         let doc = parser.parse_document().unwrap();
@@ -167,8 +249,12 @@ mod tests {
 
         let analyzer = ComplexityAnalyzer::new(config);
         let mut result = LintResult::new();
-        analyzer.analyze(&doc.root, &mut result);
+        analyzer.analyze(&doc.root, &source, &mut result);
 
         assert!(!result.warnings().is_empty());
+
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.range.unwrap().start.line, 0);
+        assert_eq!(diagnostic.related_information[0].location.range.start.line, 21);
     }
 }
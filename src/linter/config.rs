@@ -0,0 +1,666 @@
+//! Lint configuration and the `.moncfg.mon` file it is loaded from.
+//!
+//! The file is the one `mon init` writes: an object whose `linter` section
+//! holds the settings below. A file with neither a `linter` nor a
+//! `formatter` section is read as a linter section itself.
+
+use crate::linter::naming::NamingStyle;
+use crate::linter::rules::{LintRule, select_rules};
+use crate::linter::source_index::SourceIndex;
+use crate::linter::{DiagnosticCode, DiagnosticSeverity};
+use miette::{LabeledSpan, NamedSource, Result};
+use mon_core::ast::{Member, MonDocument, MonValue, MonValueKind};
+use mon_core::parser::Parser;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range as ByteRange;
+use std::path::{Path, PathBuf};
+
+/// Name of the configuration file picked up by `LintConfig::discover`
+pub const CONFIG_FILE_NAME: &str = ".moncfg.mon";
+
+/// Every key of the linter section
+const KEYS: &[&str] = &[
+    "max_nesting_depth",
+    "max_object_members",
+    "max_array_items",
+    "max_spreads_per_object",
+    "allow_explicit_overrides",
+    "max_import_chain_depth",
+    "warn_unused_anchors",
+    "warn_magic_numbers",
+    "warn_unused_imports",
+    "warn_empty_structures",
+    "suggest_type_validation",
+    "enforce_naming_convention",
+    "fix_empty_structures",
+    "disabled_rules",
+    "rules",
+];
+
+/// Keys written by earlier versions of `mon init`, and the keys that replaced
+/// them
+const DEPRECATED_KEYS: &[(&str, &str)] = &[
+    ("max_array_elements", "max_array_items"),
+    ("max_spreads", "max_spreads_per_object"),
+    ("max_import_depth", "max_import_chain_depth"),
+    ("enforce_consistent_naming", "enforce_naming_convention"),
+];
+
+#[derive(Debug, Clone)]
+pub struct LintConfig {
+    pub max_nesting_depth: usize,
+    pub max_object_members: usize,
+    pub max_array_items: usize,
+    /// Spreads allowed in one object before LINT2003
+    pub max_spreads_per_object: usize,
+    /// Let keys written after a spread override its keys without LINT2006
+    pub allow_explicit_overrides: bool,
+    pub max_import_chain_depth: usize,
+    pub warn_unused_anchors: bool,
+    pub warn_magic_numbers: bool,
+    /// Report imported names that are never used
+    pub warn_unused_imports: bool,
+    /// Report empty objects and arrays
+    pub warn_empty_structures: bool,
+    /// Suggest `:: Struct` for objects shaped like a struct in scope
+    pub suggest_type_validation: bool,
+    /// Naming styles checked by LINT3002
+    pub enforce_naming_convention: NamingConvention,
+    /// Let `mon lint --fix` remove empty objects and arrays
+    pub fix_empty_structures: bool,
+    /// Rules that are not run, by the code they report
+    pub disabled_rules: HashSet<DiagnosticCode>,
+    /// Severities that replace the default of a code
+    pub severities: HashMap<DiagnosticCode, DiagnosticSeverity>,
+    /// Deprecated keys the configuration was loaded with, as messages
+    pub warnings: Vec<String>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            max_nesting_depth: 4,
+            max_object_members: 20,
+            max_array_items: 100,
+            max_spreads_per_object: 3,
+            allow_explicit_overrides: true,
+            max_import_chain_depth: 2,
+            warn_unused_anchors: true,
+            warn_magic_numbers: false,
+            warn_unused_imports: false,
+            warn_empty_structures: true,
+            suggest_type_validation: false,
+            enforce_naming_convention: NamingConvention::default(),
+            fix_empty_structures: false,
+            disabled_rules: HashSet::new(),
+            severities: HashMap::new(),
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// What LINT3002 checks, from `enforce_naming_convention`: `true` or
+/// `false`, or an object of styles per kind of name and exemptions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamingConvention {
+    pub enabled: bool,
+    /// Style of object keys; without one, the keys of each object only have
+    /// to agree on snake_case or camelCase
+    pub keys: Option<NamingStyle>,
+    pub anchors: Option<NamingStyle>,
+    /// Style of struct and enum names
+    pub types: Option<NamingStyle>,
+    pub fields: Option<NamingStyle>,
+    pub variants: Option<NamingStyle>,
+    /// Key paths whose contents are not checked, such as `headers` or
+    /// `**.labels`
+    pub exempt: Vec<String>,
+    /// Leave quoted keys such as `"user-agent"` alone
+    pub exempt_quoted_keys: bool,
+}
+
+impl Default for NamingConvention {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            keys: None,
+            anchors: None,
+            types: None,
+            fields: None,
+            variants: None,
+            exempt: Vec::new(),
+            exempt_quoted_keys: true,
+        }
+    }
+}
+
+impl LintConfig {
+    /// Create a config that only runs the specified rules.
+    ///
+    /// Rules are selected as in `rules::select_rules`. Selecting a rule that
+    /// is off by default, such as magic numbers, turns it on.
+    pub fn with_only_rules(mut self, rules: Vec<String>) -> Result<Self> {
+        let selected = select_rules(&rules)?;
+        self.disabled_rules = LintRule::ALL
+            .iter()
+            .map(LintRule::code)
+            .filter(|code| !selected.contains(code))
+            .collect();
+        for code in selected {
+            self.turn_on(code);
+        }
+        Ok(self)
+    }
+
+    /// Create a config that disables the specified rules.
+    pub fn without_rules(mut self, rules: Vec<String>) -> Result<Self> {
+        self.disabled_rules.extend(select_rules(&rules)?);
+        Ok(self)
+    }
+
+    /// Create a config that reports the specified rules at `level`, or
+    /// turns them off if it is `None`.
+    pub fn with_level(
+        mut self,
+        rules: &[String],
+        level: Option<DiagnosticSeverity>,
+    ) -> Result<Self> {
+        for code in select_rules(rules)? {
+            self.set_level(code, level);
+        }
+        Ok(self)
+    }
+
+    /// Whether the rule reporting `code` runs.
+    pub fn is_enabled(&self, code: DiagnosticCode) -> bool {
+        !self.disabled_rules.contains(&code)
+    }
+
+    /// The severity `code` is reported with.
+    pub fn severity(&self, code: DiagnosticCode) -> DiagnosticSeverity {
+        self.severities.get(&code).copied().unwrap_or_else(|| code.severity())
+    }
+
+    /// Reports `code` at `level`, or turns it off if it is `None`. A rule
+    /// given a severity runs even if it is off by default.
+    fn set_level(&mut self, code: DiagnosticCode, level: Option<DiagnosticSeverity>) {
+        match level {
+            Some(severity) => {
+                self.disabled_rules.remove(&code);
+                self.severities.insert(code, severity);
+                self.turn_on(code);
+            }
+            None => {
+                self.disabled_rules.insert(code);
+            }
+        }
+    }
+
+    /// Turns on the opt-in setting of the rule reporting `code`, if it has one.
+    fn turn_on(&mut self, code: DiagnosticCode) {
+        match code {
+            DiagnosticCode::UnusedAnchor => self.warn_unused_anchors = true,
+            DiagnosticCode::MagicNumber => self.warn_magic_numbers = true,
+            DiagnosticCode::MissingTypeValidation => self.suggest_type_validation = true,
+            DiagnosticCode::InconsistentNaming => self.enforce_naming_convention.enabled = true,
+            DiagnosticCode::EmptyObject => self.warn_empty_structures = true,
+            DiagnosticCode::UnusedImport => self.warn_unused_imports = true,
+            _ => {}
+        }
+    }
+
+    /// Load config from a MonDocument parsed from `source`.
+    ///
+    /// Keys that are not set keep their default. Unknown keys and values of
+    /// the wrong type are errors labelled with their span in `source`.
+    pub fn from_document(doc: &MonDocument, source: &str) -> Result<Self> {
+        let mut config = Self::default();
+        let Some(section) = linter_section(&doc.root) else {
+            return Ok(config);
+        };
+        let MonValueKind::Object(members) = &section.kind else {
+            return Err(invalid("linter", section, "an object"));
+        };
+
+        let index = SourceIndex::new(source);
+        for (member, span) in members.iter().zip(index.member_spans(section)) {
+            let Member::Pair(pair) = member else {
+                continue;
+            };
+            let (mut key, value) = (pair.key.as_str(), &pair.value);
+            if let Some((_, renamed)) = DEPRECATED_KEYS.iter().find(|(old, _)| *old == key) {
+                config.warnings.push(format!(
+                    "Lint config key '{}' is deprecated, use '{}' instead",
+                    key, renamed
+                ));
+                key = renamed;
+            }
+
+            match key {
+                "max_nesting_depth" => config.max_nesting_depth = count(key, value)?,
+                "max_object_members" => config.max_object_members = count(key, value)?,
+                "max_array_items" => config.max_array_items = count(key, value)?,
+                "max_spreads_per_object" => config.max_spreads_per_object = count(key, value)?,
+                "allow_explicit_overrides" => config.allow_explicit_overrides = flag(key, value)?,
+                "max_import_chain_depth" => config.max_import_chain_depth = count(key, value)?,
+                "warn_unused_anchors" => config.warn_unused_anchors = flag(key, value)?,
+                "warn_magic_numbers" => config.warn_magic_numbers = flag(key, value)?,
+                "warn_unused_imports" => config.warn_unused_imports = flag(key, value)?,
+                "warn_empty_structures" => config.warn_empty_structures = flag(key, value)?,
+                "suggest_type_validation" => config.suggest_type_validation = flag(key, value)?,
+                "enforce_naming_convention" => {
+                    config.enforce_naming_convention = naming_convention(value, &index)?
+                }
+                "fix_empty_structures" => config.fix_empty_structures = flag(key, value)?,
+                "disabled_rules" => config.disabled_rules.extend(rule_set(key, value)?),
+                "rules" => {
+                    for (code, level) in rule_levels(value, &index)? {
+                        config.set_level(code, level);
+                    }
+                }
+                _ => return Err(unknown_key(key, span.name.unwrap_or(span.span))),
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Load configuration from a MON file
+    pub fn from_mon_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| miette::miette!("Failed to read config file: {}", e))?;
+
+        let mut parser = Parser::new(&content)
+            .map_err(|e| miette::miette!("Failed to parse config: {:?}", e))?;
+        let doc =
+            parser.parse_document().map_err(|e| miette::miette!("Config parse error: {:?}", e))?;
+
+        let mut config = Self::from_document(&doc, &content).map_err(|error| {
+            error.with_source_code(NamedSource::new(path.display().to_string(), content.clone()))
+        })?;
+        for warning in &mut config.warnings {
+            *warning = format!("{}: {}", path.display(), warning);
+        }
+        Ok(config)
+    }
+
+    /// Load `.moncfg.mon` from `dir` or the closest ancestor that has one,
+    /// falling back to the default configuration. Also returns the path of
+    /// the file that was loaded.
+    pub fn discover(dir: &Path) -> Result<(Self, Option<PathBuf>)> {
+        match dir.ancestors().map(|dir| dir.join(CONFIG_FILE_NAME)).find(|path| path.is_file()) {
+            Some(path) => Ok((Self::from_mon_file(&path)?, Some(path))),
+            None => Ok((Self::default(), None)),
+        }
+    }
+}
+
+/// The value holding the linter settings, if the document has any.
+fn linter_section(root: &MonValue) -> Option<&MonValue> {
+    let MonValueKind::Object(members) = &root.kind else {
+        return Some(root);
+    };
+    let section = |name: &str| {
+        members.iter().find_map(|member| match member {
+            Member::Pair(pair) if pair.key == name => Some(&pair.value),
+            _ => None,
+        })
+    };
+
+    match (section("linter"), section("formatter")) {
+        (Some(linter), _) => Some(linter),
+        (None, Some(_)) => None,
+        (None, None) => Some(root),
+    }
+}
+
+/// Every key of an `enforce_naming_convention` object
+const NAMING_KEYS: &[&str] =
+    &["keys", "anchors", "types", "fields", "variants", "exempt", "exempt_quoted_keys"];
+
+/// Reads `enforce_naming_convention`: a flag, or an object of styles.
+fn naming_convention(value: &MonValue, index: &SourceIndex) -> Result<NamingConvention> {
+    let mut convention = NamingConvention::default();
+    let members = match &value.kind {
+        MonValueKind::Boolean(enabled) => {
+            convention.enabled = *enabled;
+            return Ok(convention);
+        }
+        MonValueKind::Object(members) => members,
+        _ => return Err(invalid("enforce_naming_convention", value, "true, false or an object")),
+    };
+
+    for (member, span) in members.iter().zip(index.member_spans(value)) {
+        let Member::Pair(pair) = member else {
+            continue;
+        };
+        let (key, value) = (pair.key.as_str(), &pair.value);
+        match key {
+            "keys" => convention.keys = Some(naming_style(key, value)?),
+            "anchors" | "types" | "fields" | "variants" => {
+                let style = naming_style(key, value)?;
+                // `-` cannot appear in a name outside quotes
+                if style == NamingStyle::Kebab {
+                    return Err(invalid(key, value, "a style other than kebab-case"));
+                }
+                match key {
+                    "anchors" => convention.anchors = Some(style),
+                    "types" => convention.types = Some(style),
+                    "fields" => convention.fields = Some(style),
+                    _ => convention.variants = Some(style),
+                }
+            }
+            "exempt" => {
+                let MonValueKind::Array(items) = &value.kind else {
+                    return Err(invalid(key, value, "a list of key paths"));
+                };
+                for item in items {
+                    let MonValueKind::String(path) = &item.kind else {
+                        return Err(invalid(key, item, "a key path such as \"headers\""));
+                    };
+                    convention.exempt.push(path.clone());
+                }
+            }
+            "exempt_quoted_keys" => convention.exempt_quoted_keys = flag(key, value)?,
+            _ => {
+                return Err(miette::miette!(
+                    labels = vec![LabeledSpan::at(span.name.unwrap_or(span.span), "unknown key")],
+                    help = format!("Known keys are: {}", NAMING_KEYS.join(", ")),
+                    "Unknown naming convention key '{}'",
+                    key
+                ));
+            }
+        }
+    }
+    Ok(convention)
+}
+
+fn naming_style(key: &str, value: &MonValue) -> Result<NamingStyle> {
+    match &value.kind {
+        MonValueKind::String(name) => NamingStyle::parse(name),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        invalid(key, value, "\"snake_case\", \"camelCase\", \"kebab-case\" or \"PascalCase\"")
+    })
+}
+
+fn count(key: &str, value: &MonValue) -> Result<usize> {
+    match value.kind {
+        MonValueKind::Number(n) if n >= 0.0 && n.fract() == 0.0 => Ok(n as usize),
+        _ => Err(invalid(key, value, "a whole number")),
+    }
+}
+
+fn flag(key: &str, value: &MonValue) -> Result<bool> {
+    match value.kind {
+        MonValueKind::Boolean(b) => Ok(b),
+        _ => Err(invalid(key, value, "true or false")),
+    }
+}
+
+/// Reads a list of rules, selected as for `mon lint --rules`.
+fn rule_set(key: &str, value: &MonValue) -> Result<HashSet<DiagnosticCode>> {
+    let MonValueKind::Array(items) = &value.kind else {
+        return Err(invalid(key, value, "a list of rules"));
+    };
+
+    let mut codes = HashSet::new();
+    for item in items {
+        let MonValueKind::String(id) = &item.kind else {
+            return Err(invalid(key, item, "a rule code or name"));
+        };
+        let selected = select_rules(std::slice::from_ref(id)).map_err(|error| {
+            miette::miette!(
+                labels = vec![LabeledSpan::at(span(item), "unknown rule")],
+                help = error.help().map(|help| help.to_string()).unwrap_or_default(),
+                "{}",
+                error
+            )
+        })?;
+        codes.extend(selected);
+    }
+    Ok(codes)
+}
+
+/// Reads the `rules` map: rule ids, selected as for `mon lint --rules`, to
+/// `"error"`, `"warning"`, `"info"` or `"off"`.
+fn rule_levels(
+    value: &MonValue,
+    index: &SourceIndex,
+) -> Result<Vec<(DiagnosticCode, Option<DiagnosticSeverity>)>> {
+    let MonValueKind::Object(members) = &value.kind else {
+        return Err(invalid("rules", value, "an object of rule levels"));
+    };
+
+    let mut levels = Vec::new();
+    for (member, member_span) in members.iter().zip(index.member_spans(value)) {
+        let Member::Pair(pair) = member else {
+            continue;
+        };
+        let key_span = member_span.name.unwrap_or(member_span.span);
+        let codes = select_rules(std::slice::from_ref(&pair.key)).map_err(|error| {
+            miette::miette!(
+                labels = vec![LabeledSpan::at(key_span, "unknown rule")],
+                help = error.help().map(|help| help.to_string()).unwrap_or_default(),
+                "{}",
+                error
+            )
+        })?;
+
+        let level = match &pair.value.kind {
+            MonValueKind::String(level) => match level.as_str() {
+                "error" => Some(Some(DiagnosticSeverity::Error)),
+                "warning" => Some(Some(DiagnosticSeverity::Warning)),
+                "info" => Some(Some(DiagnosticSeverity::Info)),
+                "off" => Some(None),
+                _ => None,
+            },
+            _ => None,
+        };
+        let Some(level) = level else {
+            return Err(invalid(
+                &pair.key,
+                &pair.value,
+                "\"error\", \"warning\", \"info\" or \"off\"",
+            ));
+        };
+        levels.extend(codes.into_iter().map(|code| (code, level)));
+    }
+    Ok(levels)
+}
+
+fn span(value: &MonValue) -> ByteRange<usize> {
+    value.pos_start..value.pos_end
+}
+
+fn invalid(key: &str, value: &MonValue, expected: &str) -> miette::Report {
+    miette::miette!(
+        labels = vec![LabeledSpan::at(span(value), format!("expected {}", expected))],
+        "Invalid value for lint config key '{}'",
+        key
+    )
+}
+
+fn unknown_key(key: &str, span: ByteRange<usize>) -> miette::Report {
+    let help = match suggest_key(key) {
+        Some(known) => format!("Did you mean '{}'?", known),
+        None => format!("Known keys are: {}", KEYS.join(", ")),
+    };
+    miette::miette!(
+        labels = vec![LabeledSpan::at(span, "unknown key")],
+        help = help,
+        "Unknown lint config key '{}'",
+        key
+    )
+}
+
+/// The known key closest to `key`: one that shares at least two words with
+/// it or is a few edits away, preferring more shared words.
+fn suggest_key(key: &str) -> Option<&'static str> {
+    let words: HashSet<&str> = key.split('_').collect();
+    KEYS.iter()
+        .map(|known| {
+            let shared = known.split('_').filter(|word| words.contains(word)).count();
+            (*known, shared, edit_distance(key, known))
+        })
+        .filter(|(_, shared, distance)| *shared >= 2 || *distance <= 3)
+        .max_by_key(|(_, shared, distance)| (*shared, Reverse(*distance)))
+        .map(|(known, ..)| known)
+}
+
+/// Levenshtein distance between two strings, by character.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(source: &str) -> Result<LintConfig> {
+        let doc = Parser::new(source).unwrap().parse_document().unwrap();
+        LintConfig::from_document(&doc, source)
+    }
+
+    #[test]
+    fn test_reads_linter_section() {
+        let config = load(
+            r#"{
+    linter: {
+        max_nesting_depth: 6,
+        max_spreads_per_object: 1,
+        allow_explicit_overrides: false,
+        warn_empty_structures: false,
+        fix_empty_structures: true,
+        disabled_rules: ["LINT2002", "magic_number"],
+    },
+    formatter: { indent_size: 2 },
+}"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.max_nesting_depth, 6);
+        assert_eq!(config.max_spreads_per_object, 1);
+        assert!(!config.allow_explicit_overrides);
+        assert!(!config.warn_empty_structures && config.fix_empty_structures);
+        assert!(!config.is_enabled(DiagnosticCode::DuplicateKey));
+        assert!(!config.is_enabled(DiagnosticCode::MagicNumber));
+        assert_eq!(config.max_object_members, LintConfig::default().max_object_members);
+
+        // Without sections, the whole document is the linter section
+        assert_eq!(load("{ max_array_items: 7 }").unwrap().max_array_items, 7);
+        assert_eq!(load("{ formatter: {} }").unwrap().max_array_items, 100);
+
+        for template in [
+            include_str!("../../templates/unified-default.mon"),
+            include_str!("../../templates/unified-strict.mon"),
+            include_str!("../../templates/unified-lenient.mon"),
+        ] {
+            assert!(load(template).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_reads_deprecated_keys() {
+        let config = load(
+            "{\n    max_array_elements: 7,\n    max_spreads: 1,\n    enforce_consistent_naming: false,\n}",
+        )
+        .unwrap();
+
+        // Old names set the new keys, with a warning each
+        assert_eq!(config.max_array_items, 7);
+        assert_eq!(config.max_spreads_per_object, 1);
+        assert!(!config.enforce_naming_convention.enabled);
+        assert_eq!(config.warnings.len(), 3);
+        assert_eq!(
+            config.warnings[0],
+            "Lint config key 'max_array_elements' is deprecated, use 'max_array_items' instead"
+        );
+        assert!(load("{ max_array_items: 7 }").unwrap().warnings.is_empty());
+    }
+
+    #[test]
+    fn test_reads_rule_levels() {
+        let config = load(
+            r#"{
+    disabled_rules: ["LINT2001"],
+    rules: { LINT2004: "error", max_nesting_depth: "info", "LINT2*": "warning", LINT3003: "off" },
+}"#,
+        )
+        .unwrap();
+
+        // Later entries win; a level turns a rule on
+        assert!(config.warn_magic_numbers && config.is_enabled(DiagnosticCode::MagicNumber));
+        assert_eq!(config.severity(DiagnosticCode::MagicNumber), DiagnosticSeverity::Warning);
+        assert_eq!(config.severity(DiagnosticCode::DuplicateKey), DiagnosticSeverity::Warning);
+        assert!(config.is_enabled(DiagnosticCode::UnusedAnchor));
+        assert_eq!(config.severity(DiagnosticCode::MaxNestingDepth), DiagnosticSeverity::Info);
+        assert!(!config.is_enabled(DiagnosticCode::EmptyObject));
+
+        let error = load(r#"{ rules: { LINT2004: "fatal" } }"#).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid value for lint config key 'LINT2004'");
+        let error = load(r#"{ rules: { LINT9999: "error" } }"#).unwrap_err();
+        assert_eq!(error.to_string(), "Unknown lint rule 'LINT9999'");
+    }
+
+    #[test]
+    fn test_reads_naming_convention() {
+        let config = load(
+            r#"{ enforce_naming_convention: { keys: "kebab-case", types: "PascalCase", exempt: ["**.headers"] } }"#,
+        )
+        .unwrap();
+        let convention = config.enforce_naming_convention;
+        assert!(convention.enabled && convention.exempt_quoted_keys);
+        assert_eq!(convention.keys, Some(NamingStyle::Kebab));
+        assert_eq!(convention.types, Some(NamingStyle::Pascal));
+        assert_eq!(convention.anchors, None);
+        assert_eq!(convention.exempt, vec!["**.headers"]);
+
+        assert!(
+            !load("{ enforce_naming_convention: false }")
+                .unwrap()
+                .enforce_naming_convention
+                .enabled
+        );
+        // Only quoted keys can be written in kebab-case
+        let error =
+            load(r#"{ enforce_naming_convention: { anchors: "kebab-case" } }"#).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid value for lint config key 'anchors'");
+        let error = load(r#"{ enforce_naming_convention: { keys: "lower" } }"#).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid value for lint config key 'keys'");
+        let error = load("{ enforce_naming_convention: { key: \"snake_case\" } }").unwrap_err();
+        assert_eq!(error.to_string(), "Unknown naming convention key 'key'");
+    }
+
+    #[test]
+    fn test_reports_bad_entries_with_span() {
+        let source = "{ linter: { max_array_size: 5 } }";
+        let error = load(source).unwrap_err();
+        let label = error.labels().unwrap().next().unwrap();
+
+        assert_eq!(error.to_string(), "Unknown lint config key 'max_array_size'");
+        assert_eq!(error.help().unwrap().to_string(), "Did you mean 'max_array_items'?");
+        assert_eq!(&source[label.offset()..label.offset() + label.len()], "max_array_size");
+
+        let error = load("{ warn_magic_numbers: 1 }").unwrap_err();
+        assert_eq!(error.to_string(), "Invalid value for lint config key 'warn_magic_numbers'");
+        assert!(load("{ max_nesting_depth: 2.5 }").is_err());
+        assert!(load(r#"{ disabled_rules: ["LINT9001"] }"#).is_err());
+        assert_eq!(suggest_key("max_spreads"), Some("max_spreads_per_object"));
+        assert_eq!(suggest_key("colour"), None);
+    }
+}
@@ -3,7 +3,7 @@
 use serde::{Serialize, Serializer};
 use std::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DiagnosticCode {
     // Complexity warnings (LINT1xxx)
     MaxNestingDepth,  // LINT1001
@@ -11,26 +11,22 @@ pub enum DiagnosticCode {
     MaxArrayItems,    // LINT1003
 
     // Code smell warnings (LINT2xxx)
-    UnusedAnchor,     // LINT2001
-    DuplicateKey,     // LINT2002
-    ExcessiveSpreads, // LINT2003
-    MagicNumber,      // LINT2004
+    UnusedAnchor,      // LINT2001
+    DuplicateKey,      // LINT2002
+    ExcessiveSpreads,  // LINT2003
+    MagicNumber,       // LINT2004
+    UnusedSuppression, // LINT2005
+    OverriddenKey,     // LINT2006
 
-    // Best practices (LINT3xxx) - Future implementation
-    #[allow(dead_code)]
+    // Best practices (LINT3xxx)
     MissingTypeValidation, // LINT3001
-    #[allow(dead_code)]
-    InconsistentNaming, // LINT3002
-    #[allow(dead_code)]
-    EmptyObject, // LINT3003
-
-    // Import issues (LINT4xxx) - Future implementation
-    #[allow(dead_code)]
-    DeepImportChain, // LINT4001
-    #[allow(dead_code)]
+    InconsistentNaming,    // LINT3002
+    EmptyObject,           // LINT3003
+
+    // Import issues (LINT4xxx)
+    DeepImportChain,    // LINT4001
     CircularDependency, // LINT4002
-    #[allow(dead_code)]
-    UnusedImport, // LINT4003
+    UnusedImport,       // LINT4003
 }
 
 // Custom serialization to output error codes instead of variant names
@@ -53,6 +49,8 @@ impl DiagnosticCode {
             DiagnosticCode::DuplicateKey => "LINT2002",
             DiagnosticCode::ExcessiveSpreads => "LINT2003",
             DiagnosticCode::MagicNumber => "LINT2004",
+            DiagnosticCode::UnusedSuppression => "LINT2005",
+            DiagnosticCode::OverriddenKey => "LINT2006",
             DiagnosticCode::MissingTypeValidation => "LINT3001",
             DiagnosticCode::InconsistentNaming => "LINT3002",
             DiagnosticCode::EmptyObject => "LINT3003",
@@ -71,6 +69,8 @@ impl DiagnosticCode {
             DiagnosticCode::DuplicateKey => "Duplicate object key",
             DiagnosticCode::ExcessiveSpreads => "Too many spread operators",
             DiagnosticCode::MagicNumber => "Magic number literal",
+            DiagnosticCode::UnusedSuppression => "Unused suppression comment",
+            DiagnosticCode::OverriddenKey => "Key overridden by a spread",
             DiagnosticCode::MissingTypeValidation => "Missing type validation",
             DiagnosticCode::InconsistentNaming => "Inconsistent naming convention",
             DiagnosticCode::EmptyObject => "Empty object or array",
@@ -103,8 +103,14 @@ impl DiagnosticCode {
             DiagnosticCode::MagicNumber => {
                 "Literal numbers without context are hard to understand. Extract them as named constants with descriptive names."
             }
+            DiagnosticCode::UnusedSuppression => {
+                "A mon-lint-disable comment no longer suppresses any diagnostic, or names an unknown rule. Remove it so it does not hide future issues."
+            }
+            DiagnosticCode::OverriddenKey => {
+                "A key is set both by a spread and by another member of the same object. Only the later one is kept, so the earlier value is silently lost."
+            }
             DiagnosticCode::MissingTypeValidation => {
-                "Data lacks type validation. Add type constraints (:: TypeName) to ensure data integrity."
+                "An object or array of objects has the shape of a struct in scope but no type validation. Add :: TypeName to ensure data integrity."
             }
             DiagnosticCode::InconsistentNaming => {
                 "Keys use inconsistent naming conventions (camelCase vs snake_case). Choose one style for consistency."
@@ -124,6 +130,71 @@ impl DiagnosticCode {
         }
     }
 
+    /// A short document that triggers the diagnostic, and the same document
+    /// fixed, as `(bad, good)`.
+    pub fn example(&self) -> (&'static str, &'static str) {
+        match self {
+            DiagnosticCode::MaxNestingDepth => (
+                "{\n    app: { server: { http: { tls: { cert: { path: \"cert.pem\" } } } } },\n}",
+                "{\n    http: { port: 8080 },\n    tls: { cert_path: \"cert.pem\" },\n}",
+            ),
+            DiagnosticCode::MaxObjectMembers => (
+                "{\n    db_host: \"x\",\n    db_port: 5432,\n    // ... 20 more keys\n    log_level: \"info\",\n}",
+                "{\n    db: { host: \"x\", port: 5432 },\n    log: { level: \"info\" },\n}",
+            ),
+            DiagnosticCode::MaxArrayItems => (
+                "{\n    ids: [1, 2, 3, 4, 5], // ... up to 150 items\n}",
+                "{\n    // Keep long lists in data files\n    id_file: \"ids.csv\",\n}",
+            ),
+            DiagnosticCode::UnusedAnchor => (
+                "{\n    &defaults: { port: 80 },\n    server: { port: 80 },\n}",
+                "{\n    &defaults: { port: 80 },\n    server: { ...*defaults },\n}",
+            ),
+            DiagnosticCode::DuplicateKey => {
+                ("{\n    timeout: 30,\n    timeout: 60,\n}", "{\n    timeout: 60,\n}")
+            }
+            DiagnosticCode::ExcessiveSpreads => (
+                "{\n    service: { ...*base, ...*logging, ...*metrics, ...*tracing },\n}",
+                "{\n    &observability: { ...*logging, ...*metrics, ...*tracing },\n    service: { ...*base, ...*observability },\n}",
+            ),
+            DiagnosticCode::MagicNumber => (
+                "{\n    cache: { ttl: 86400 },\n}",
+                "{\n    &seconds_per_day: 86400,\n    cache: { ttl: *seconds_per_day },\n}",
+            ),
+            DiagnosticCode::UnusedSuppression => (
+                "{\n    // mon-lint-disable-next-line LINT2002\n    port: 8080,\n}",
+                "{\n    port: 8080,\n}",
+            ),
+            DiagnosticCode::OverriddenKey => (
+                "{\n    &defaults: { timeout: 30 },\n    job: {\n        timeout: 60,\n        ...*defaults,\n    },\n}",
+                "{\n    &defaults: { timeout: 30 },\n    job: {\n        ...*defaults,\n        timeout: 60,\n    },\n}",
+            ),
+            DiagnosticCode::MissingTypeValidation => (
+                "{\n    User: #struct { name(String) },\n    admin: { name: \"root\" },\n}",
+                "{\n    User: #struct { name(String) },\n    admin :: User = { name: \"root\" },\n}",
+            ),
+            DiagnosticCode::InconsistentNaming => (
+                "{\n    maxRetries: 3,\n    retry_delay: 5,\n}",
+                "{\n    max_retries: 3,\n    retry_delay: 5,\n}",
+            ),
+            DiagnosticCode::EmptyObject => {
+                ("{\n    plugins: {},\n    tags: [\"web\"],\n}", "{\n    tags: [\"web\"],\n}")
+            }
+            DiagnosticCode::DeepImportChain => (
+                "// app.mon imports &server from base.mon, which imports it from\n// shared.mon, which imports it from defaults.mon\nimport { &server } from \"./base.mon\"",
+                "// app.mon imports what it uses from where it is defined\nimport { &server } from \"./defaults.mon\"",
+            ),
+            DiagnosticCode::CircularDependency => (
+                "// a.mon\nimport { &b } from \"./b.mon\"\n// b.mon\nimport { &a } from \"./a.mon\"",
+                "// a.mon and b.mon both import what they share\nimport { &shared } from \"./shared.mon\"",
+            ),
+            DiagnosticCode::UnusedImport => (
+                "import { &defaults, &limits } from \"./shared.mon\"\n{\n    server: *defaults,\n}",
+                "import { &defaults } from \"./shared.mon\"\n{\n    server: *defaults,\n}",
+            ),
+        }
+    }
+
     pub fn severity(&self) -> DiagnosticSeverity {
         match self {
             DiagnosticCode::DuplicateKey => DiagnosticSeverity::Error,
@@ -136,6 +207,8 @@ impl DiagnosticCode {
             DiagnosticCode::ExcessiveSpreads => DiagnosticSeverity::Warning,
             DiagnosticCode::DeepImportChain => DiagnosticSeverity::Warning,
             DiagnosticCode::UnusedImport => DiagnosticSeverity::Warning,
+            DiagnosticCode::UnusedSuppression => DiagnosticSeverity::Warning,
+            DiagnosticCode::OverriddenKey => DiagnosticSeverity::Warning,
 
             DiagnosticCode::MagicNumber => DiagnosticSeverity::Info,
             DiagnosticCode::MissingTypeValidation => DiagnosticSeverity::Info,
@@ -153,6 +226,8 @@ impl DiagnosticCode {
             DiagnosticCode::DuplicateKey => "N/A (always enabled)",
             DiagnosticCode::ExcessiveSpreads => "max_spreads_per_object",
             DiagnosticCode::MagicNumber => "warn_magic_numbers",
+            DiagnosticCode::UnusedSuppression => "rules",
+            DiagnosticCode::OverriddenKey => "allow_explicit_overrides",
             DiagnosticCode::MissingTypeValidation => "suggest_type_validation",
             DiagnosticCode::InconsistentNaming => "enforce_naming_convention",
             DiagnosticCode::EmptyObject => "warn_empty_structures",
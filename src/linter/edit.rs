@@ -0,0 +1,184 @@
+//! Text edits produced by refactorings and fixes.
+//!
+//! Edits use the same zero-based, UTF-16 `Range`s as diagnostics, so they can
+//! be handed to an editor as-is or applied to the source text here.
+
+use crate::linter::Range;
+use serde::Serialize;
+use similar::{DiffTag, TextDiff};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Replacement of a range of text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TextEdit {
+    /// The range to replace (empty to insert)
+    pub range: Range,
+
+    /// The text to put in its place (empty to delete)
+    pub new_text: String,
+}
+
+impl TextEdit {
+    /// Creates an edit replacing `range` with `new_text`.
+    pub fn new(range: Range, new_text: impl Into<String>) -> Self {
+        Self { range, new_text: new_text.into() }
+    }
+}
+
+/// A named set of edits that resolves a diagnostic in its own file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Fix {
+    /// What the fix does, e.g. "Remove unused anchor '&base'"
+    pub title: String,
+
+    /// Edits to the diagnostic's file
+    pub edits: Vec<TextEdit>,
+
+    /// Whether the fix changes a name other files can import, which their
+    /// edits would miss
+    #[serde(skip)]
+    pub affects_importers: bool,
+}
+
+impl Fix {
+    /// Creates a fix applying `edits`.
+    pub fn new(title: impl Into<String>, edits: Vec<TextEdit>) -> Self {
+        Self { title: title.into(), edits, affects_importers: false }
+    }
+
+    /// Marks the fix as changing a name other files can import.
+    pub fn affecting_importers(mut self) -> Self {
+        self.affects_importers = true;
+        self
+    }
+}
+
+/// Edits to several files, keyed by path.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WorkspaceEdit {
+    /// Edits per file, each list sorted by position
+    pub changes: BTreeMap<PathBuf, Vec<TextEdit>>,
+}
+
+impl WorkspaceEdit {
+    /// Adds an edit to a file, keeping that file's edits sorted and free of
+    /// duplicates.
+    pub fn insert(&mut self, path: PathBuf, edit: TextEdit) {
+        let edits = self.changes.entry(path).or_default();
+        if !edits.contains(&edit) {
+            edits.push(edit);
+            edits.sort_by_key(|edit| (edit.range.start.line, edit.range.start.character));
+        }
+    }
+
+    /// Total number of edits across all files.
+    pub fn edit_count(&self) -> usize {
+        self.changes.values().map(Vec::len).sum()
+    }
+}
+
+/// Applies non-overlapping edits to `source`.
+pub fn apply(source: &str, edits: &[TextEdit]) -> String {
+    let mut spans: Vec<_> = edits
+        .iter()
+        .map(|edit| {
+            let start = edit.range.start.to_byte_offset(source);
+            let end = edit.range.end.to_byte_offset(source).max(start);
+            (start, end, edit.new_text.as_str())
+        })
+        .collect();
+    spans.sort_by_key(|(start, end, _)| (*start, *end));
+
+    let mut out = String::with_capacity(source.len());
+    let mut cursor = 0;
+    for (start, end, new_text) in spans {
+        // Skip an edit that overlaps the previous one
+        if start < cursor {
+            continue;
+        }
+        out.push_str(&source[cursor..start]);
+        out.push_str(new_text);
+        cursor = end;
+    }
+    out.push_str(&source[cursor..]);
+    out
+}
+
+/// Computes line-level edits that turn `old` into `new`.
+///
+/// Unchanged lines are left out, so applying the edits touches only the
+/// lines that differ instead of replacing the whole text.
+pub fn diff(old: &str, new: &str) -> Vec<TextEdit> {
+    let text_diff = TextDiff::from_lines(old, new);
+    let (old_lines, new_lines) = (text_diff.old_slices(), text_diff.new_slices());
+
+    let mut offsets = vec![0];
+    for line in old_lines {
+        offsets.push(offsets[offsets.len() - 1] + line.len());
+    }
+
+    // Adjacent deletions and insertions become one replacement
+    let mut spans: Vec<(usize, usize, String)> = Vec::new();
+    for op in text_diff.ops() {
+        if op.tag() == DiffTag::Equal {
+            continue;
+        }
+        let (start, end) = (offsets[op.old_range().start], offsets[op.old_range().end]);
+        let text = new_lines[op.new_range()].concat();
+        match spans.last_mut() {
+            Some(last) if last.1 == start => {
+                last.1 = end;
+                last.2.push_str(&text);
+            }
+            _ => spans.push((start, end, text)),
+        }
+    }
+
+    spans
+        .into_iter()
+        .map(|(start, end, text)| TextEdit::new(Range::from_byte_offsets(old, start, end), text))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::Position;
+
+    fn edit(line: u32, start: u32, end: u32, text: &str) -> TextEdit {
+        TextEdit::new(Range::new(Position::new(line, start), Position::new(line, end)), text)
+    }
+
+    #[test]
+    fn test_apply_edits() {
+        let source = "{\n    &base: 1,\n    a: *base,\n}";
+        let edits = [edit(2, 8, 12, "defaults"), edit(1, 5, 9, "defaults"), edit(3, 1, 1, "\n")];
+        assert_eq!(apply(source, &edits), "{\n    &defaults: 1,\n    a: *defaults,\n}\n");
+    }
+
+    #[test]
+    fn test_diff_only_touches_changed_lines() {
+        let old = "{\n  a: 1,\n    b: 2,\n  c: 3\n}";
+        let new = "{\n    a: 1,\n    b: 2,\n    c: 3,\n}\n";
+
+        let edits = diff(old, new);
+        assert_eq!(edits.len(), 2);
+        assert_eq!(
+            edits[0],
+            TextEdit::new(Range::new(Position::new(1, 0), Position::new(2, 0)), "    a: 1,\n")
+        );
+        assert_eq!(edits[1].range.start, Position::new(3, 0));
+        assert_eq!(apply(old, &edits), new);
+    }
+
+    #[test]
+    fn test_workspace_edit_sorts_and_dedups() {
+        let mut workspace = WorkspaceEdit::default();
+        workspace.insert(PathBuf::from("a.mon"), edit(2, 0, 1, "x"));
+        workspace.insert(PathBuf::from("a.mon"), edit(1, 0, 1, "x"));
+        workspace.insert(PathBuf::from("a.mon"), edit(1, 0, 1, "x"));
+        assert_eq!(workspace.edit_count(), 2);
+        assert_eq!(workspace.changes[&PathBuf::from("a.mon")][0].range.start.line, 1);
+    }
+}
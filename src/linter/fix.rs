@@ -0,0 +1,185 @@
+//! Applying automatic fixes to a document.
+//!
+//! Every pass lints the text, takes the first fix of each diagnostic and
+//! applies them together. Fixes can reveal more issues (dropping the only
+//! member of an object leaves it empty) and a fix that overlaps one already
+//! taken waits, so passes repeat until nothing is left to fix.
+
+use crate::linter::edit::{self, TextEdit};
+use crate::linter::{Diagnostic, DiagnosticCode, LintResult, Linter};
+use miette::Result;
+use mon_core::parser::Parser;
+use std::path::Path;
+
+/// Upper bound on lint-and-fix passes over one document.
+const MAX_PASSES: usize = 10;
+
+/// A fix that was applied, for reporting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedFix {
+    /// Code of the diagnostic it resolved
+    pub code: DiagnosticCode,
+
+    /// The fix's title
+    pub title: String,
+
+    /// Zero-based line of the diagnostic, in the text the fix was applied to
+    pub line: Option<u32>,
+}
+
+/// The result of fixing a document.
+#[derive(Debug, Clone)]
+pub struct FixOutcome {
+    /// The fixed text
+    pub source: String,
+
+    /// Fixes applied over all passes, by line
+    pub applied: Vec<AppliedFix>,
+
+    /// Lint result of the fixed text
+    pub remaining: LintResult,
+}
+
+impl Linter {
+    /// Applies every automatic fix to `source` and lints the result.
+    ///
+    /// Removing empty structures is only automatic when
+    /// `LintConfig::fix_empty_structures` is set and adding a type
+    /// validation only when one struct matches. Fixes marked as affecting
+    /// importers, such as removing an unused anchor or renaming a type, are
+    /// never applied.
+    /// Fails if the text does not parse, before or after fixing. With the document's `path`, rules that
+    /// read imported files run too.
+    pub fn fix(&self, source: &str, path: Option<&Path>) -> Result<FixOutcome> {
+        let mut source = source.to_string();
+        let mut applied = Vec::new();
+
+        for _ in 0..MAX_PASSES {
+            let result = self.lint_text(&source, path)?;
+
+            let mut taken: Vec<&TextEdit> = Vec::new();
+            for diagnostic in &result.diagnostics {
+                let Some(fix) = diagnostic.fixes.first().filter(|_| self.is_automatic(diagnostic))
+                else {
+                    continue;
+                };
+                if fix.edits.iter().any(|edit| taken.iter().any(|t| overlaps(edit, t))) {
+                    continue;
+                }
+                taken.extend(&fix.edits);
+                applied.push(AppliedFix {
+                    code: diagnostic.code,
+                    title: fix.title.clone(),
+                    line: diagnostic.range.map(|range| range.start.line),
+                });
+            }
+
+            if taken.is_empty() {
+                applied.sort_by_key(|fix| fix.line);
+                return Ok(FixOutcome { source, applied, remaining: result });
+            }
+            let edits: Vec<_> = taken.into_iter().cloned().collect();
+            source = edit::apply(&source, &edits);
+        }
+
+        applied.sort_by_key(|fix| fix.line);
+        let remaining = self.lint_text(&source, path)?;
+        Ok(FixOutcome { source, applied, remaining })
+    }
+
+    /// Whether a diagnostic's fix can be applied without asking.
+    fn is_automatic(&self, diagnostic: &Diagnostic) -> bool {
+        match diagnostic.code {
+            DiagnosticCode::EmptyObject => self.config.fix_empty_structures,
+            // Several matching structs leave the choice to the user
+            DiagnosticCode::MissingTypeValidation => diagnostic.fixes.len() == 1,
+            // Edits to this file alone would break the files importing it
+            _ => !diagnostic.fixes.first().is_some_and(|fix| fix.affects_importers),
+        }
+    }
+
+    fn lint_text(&self, source: &str, path: Option<&Path>) -> Result<LintResult> {
+        let mut parser = Parser::new(source)?;
+        let doc = parser.parse_document()?;
+        match path {
+            Some(path) => self.lint_file(&doc, source, path),
+            None => self.lint(&doc, source),
+        }
+    }
+}
+
+/// Whether two edits touch the same text. Insertions at the same point
+/// count as overlapping, since their order would be ambiguous.
+fn overlaps(a: &TextEdit, b: &TextEdit) -> bool {
+    let start = |edit: &TextEdit| (edit.range.start.line, edit.range.start.character);
+    let end = |edit: &TextEdit| (edit.range.end.line, edit.range.end.character);
+    (start(a) < end(b) && start(b) < end(a)) || start(a) == start(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintConfig;
+    use crate::linter::naming::NamingStyle;
+
+    #[test]
+    fn test_fix_repeats_until_clean() {
+        let source = "{\n    k: 1,\n    k: 2,\n    app: { tags: [] },\n}";
+
+        let linter = Linter::new(LintConfig { fix_empty_structures: true, ..Default::default() });
+        let outcome = linter.fix(source, None).unwrap();
+
+        // Dropping `tags` empties `app`, which goes on the second pass
+        assert_eq!(outcome.source, "{\n    k: 2,\n}");
+        let lines: Vec<_> = outcome.applied.iter().map(|fix| fix.line).collect();
+        assert_eq!(lines, vec![Some(2), Some(2), Some(3)]);
+        assert!(!outcome.remaining.has_issues());
+    }
+
+    #[test]
+    fn test_empty_structures_need_opt_in() {
+        let source = "{\n    k: 1,\n    k: 2,\n    tags: [],\n}";
+
+        let outcome = Linter::new(LintConfig::default()).fix(source, None).unwrap();
+
+        assert_eq!(outcome.source, "{\n    k: 2,\n    tags: [],\n}");
+        assert_eq!(outcome.remaining.diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn test_renames_only_keys() {
+        let source = "{\n    base: &baseSettings { logLevel: 1 },\n    app: *baseSettings,\n}";
+        let mut config = LintConfig::default();
+        config.enforce_naming_convention.keys = Some(NamingStyle::Snake);
+        config.enforce_naming_convention.anchors = Some(NamingStyle::Snake);
+
+        let outcome = Linter::new(config).fix(source, None).unwrap();
+
+        // Files importing '&baseSettings' would break, so it is left to `mon rename`
+        assert_eq!(
+            outcome.source,
+            "{\n    base: &baseSettings { log_level: 1 },\n    app: *baseSettings,\n}"
+        );
+        assert_eq!(outcome.applied.len(), 1);
+        assert_eq!(outcome.remaining.diagnostics.len(), 1);
+        assert_eq!(outcome.remaining.diagnostics[0].fixes.len(), 1);
+    }
+
+    #[test]
+    fn test_keeps_anchors_other_files_import() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let shared = dir.path().join("shared.mon");
+        let source = "{\n    &defaults: { port: 8080 },\n}";
+        std::fs::write(&shared, source).unwrap();
+        let app = "import { &defaults } from \"./shared.mon\"\n{\n    server: *defaults,\n}";
+        std::fs::write(dir.path().join("app.mon"), app).unwrap();
+
+        let outcome = Linter::new(LintConfig::default()).fix(source, Some(&shared)).unwrap();
+
+        // shared.mon never uses '&defaults', but app.mon does
+        assert_eq!(outcome.source, source);
+        assert!(outcome.applied.is_empty());
+        assert_eq!(outcome.remaining.diagnostics[0].code, DiagnosticCode::UnusedAnchor);
+        assert_eq!(outcome.remaining.diagnostics[0].fixes.len(), 1);
+    }
+}
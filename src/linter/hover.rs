@@ -0,0 +1,111 @@
+//! Hover information for anchors, types and enum values.
+//!
+//! Hovering a definition or any usage of it (`*alias`, `...*spread`,
+//! `:: Type`, `$Enum.Variant`, a name in `import { ... }`) shows the
+//! definition as MON source followed by its doc comment. Anchors show their
+//! value after mon-core resolution, so spreads and aliases inside them are
+//! already expanded.
+
+use crate::linter::navigation::Navigator;
+use crate::linter::symbol_table::SymbolAt;
+use crate::linter::{Position, Range, SymbolKind};
+
+/// Content to show when hovering a symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HoverInfo {
+    /// Markdown text: a fenced `mon` code block, then the documentation
+    pub contents: String,
+
+    /// Range of the hovered name
+    pub range: Range,
+}
+
+/// Builds hover information for the symbol at `position` in the navigator's
+/// root document, following imports to the defining file.
+pub fn hover_at(navigator: &mut Navigator<'_>, position: Position) -> Option<HoverInfo> {
+    let at = navigator.root().find_symbol_at(position)?;
+    let range = at.range();
+
+    if let SymbolAt::Definition(symbol) = at
+        && symbol.kind == SymbolKind::Import
+    {
+        return Some(HoverInfo { contents: symbol.detail.clone()?, range });
+    }
+
+    let definition = navigator.definition_at(position)?;
+    let symbol = navigator.symbol(&definition)?;
+
+    let code = match (symbol.kind, &symbol.value) {
+        (SymbolKind::Anchor, Some(value)) => format!("&{} {}", symbol.name, value),
+        (_, Some(value)) => value.clone(),
+        (_, None) => symbol.name.clone(),
+    };
+
+    let mut contents = format!("```mon\n{}\n```", code);
+    if let Some(documentation) = &symbol.documentation {
+        contents.push_str("\n\n");
+        contents.push_str(documentation);
+    }
+
+    Some(HoverInfo { contents, range })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintConfig;
+    use crate::linter::api::AnalysisService;
+
+    fn hover(source: &str, needle: &str) -> Option<HoverInfo> {
+        let result = AnalysisService::new(LintConfig::default())
+            .analyze_document(source, "/tmp/hover.mon")
+            .unwrap();
+        let position = Position::from_byte_offset(source, source.find(needle).unwrap());
+        hover_at(&mut Navigator::new(&result), position)
+    }
+
+    #[test]
+    fn test_hover_alias_shows_resolved_value_and_docs() {
+        let source = r#"{
+    // Shared defaults.
+    &base: { debug: false, retries: 3 },
+    &service: { ...*base, name: "api" },
+    copy: *service,
+}"#;
+        let info = hover(source, "service,").unwrap();
+        assert_eq!(
+            info.contents,
+            "```mon\n&service {\n    debug: false,\n    retries: 3,\n    name: \"api\",\n}\n```"
+        );
+
+        let info = hover(source, "base, name").unwrap();
+        assert!(info.contents.ends_with("```\n\nShared defaults."));
+        assert_eq!(info.range.start, Position::new(3, 20));
+    }
+
+    #[test]
+    fn test_hover_struct_and_enum() {
+        let source = r#"{
+    // Lifecycle of a task.
+    Status: #enum { Active, Done },
+    Task: #struct { title(String), status(Status) = $Status.Active },
+    task :: Task = { title: "x" },
+}"#;
+        let info = hover(source, "Task = ").unwrap();
+        assert_eq!(
+            info.contents,
+            "```mon\nTask: #struct {\n    title(String),\n    status(Status) = $Status.Active,\n}\n```"
+        );
+
+        let info = hover(source, "Status.Active").unwrap();
+        assert_eq!(
+            info.contents,
+            "```mon\nStatus: #enum { Active, Done }\n```\n\nLifecycle of a task."
+        );
+    }
+
+    #[test]
+    fn test_no_hover_on_plain_key() {
+        assert!(hover("{ plain: 1 }", "plain").is_none());
+    }
+}
@@ -0,0 +1,251 @@
+//! The files a document imports, read from disk, for the import rules.
+//!
+//! Names are followed through the files that re-export them the same way
+//! `Navigator` resolves them, and import statements are followed to find
+//! cycles back to the document. Each file is parsed at most once; files that
+//! cannot be read or parsed are left out of the graph.
+
+use crate::linter::Range;
+use crate::linter::SymbolKind;
+use crate::linter::navigation::{
+    find_anchor_value, find_type_definition, resolve_import_path, type_definitions,
+};
+use crate::linter::position::Location;
+use crate::linter::source_index::{SourceIndex, value_head};
+use lsp_types::Url;
+use mon_core::ast::{
+    ImportSpec, ImportStatement, Member, MonDocument, MonValueKind, TypeDefinition,
+};
+use mon_core::parser::Parser;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::ops::Range as ByteRange;
+use std::path::{Path, PathBuf};
+
+/// Maximum number of imports followed from the document.
+const MAX_HOPS: usize = 16;
+
+struct File {
+    source: String,
+    doc: MonDocument,
+    index: SourceIndex,
+}
+
+/// An import followed from one file to another.
+#[derive(Debug, Clone)]
+pub struct Hop {
+    /// File the import is written in
+    pub file: PathBuf,
+    /// Span of the imported name, or of the whole statement for cycles
+    pub span: ByteRange<usize>,
+    /// File it imports
+    pub target: PathBuf,
+}
+
+/// The imports a name goes through before reaching the document, from the
+/// document's own import to the file that defines the name.
+#[derive(Debug)]
+pub struct Chain {
+    pub hops: Vec<Hop>,
+    /// The name in the file that defines it, without namespaces
+    pub name: String,
+    /// Span of the name where it is defined, in the target of the last hop
+    pub definition: ByteRange<usize>,
+}
+
+/// An anchored object, as found by `ImportGraph::anchored_object`.
+#[derive(Debug)]
+pub struct AnchoredObject {
+    /// File the object is written in
+    pub file: PathBuf,
+    pub members: Vec<Member>,
+    /// Span of each member's name in `file`, lined up with `members`
+    pub names: Vec<Option<ByteRange<usize>>>,
+}
+
+pub struct ImportGraph {
+    root: PathBuf,
+    files: HashMap<PathBuf, Option<File>>,
+}
+
+impl ImportGraph {
+    /// Creates the graph of the document at `path`, parsed as `doc`.
+    pub fn new(path: &Path, doc: &MonDocument, source: &str) -> Self {
+        let root = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let file =
+            File { source: source.to_string(), doc: doc.clone(), index: SourceIndex::new(source) };
+        Self { files: HashMap::from([(root.clone(), Some(file))]), root }
+    }
+
+    /// Canonical path of the document.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Where the name imported by `hop` is defined, following re-exports.
+    pub fn chain(&mut self, hop: Hop, name: &str, kind: SymbolKind) -> Option<Chain> {
+        let mut hops = vec![hop];
+        let mut name = name.to_string();
+
+        while hops.len() <= MAX_HOPS {
+            let path = hops.last()?.target.clone();
+            let file = self.file(&path)?;
+
+            if let Some(definition) = definition(file, &name, kind) {
+                return Some(Chain { hops, name, definition });
+            }
+
+            let (import, span, rest) = imported(file, &name, kind)?;
+            let target = resolve_import_path(&import.path, &path);
+            hops.push(Hop { file: path, span, target });
+            name = rest;
+        }
+
+        None
+    }
+
+    /// The object anchored as `name` in the file at `path`, or in the file
+    /// it imports it from. `None` if the anchor is not an object.
+    pub fn anchored_object(&mut self, path: &Path, name: &str) -> Option<AnchoredObject> {
+        let mut path = path.to_path_buf();
+        let mut name = name.to_string();
+
+        for _ in 0..=MAX_HOPS {
+            let file = self.file(&path)?;
+            if let Some(value) = find_anchor_value(&file.doc.root, &name) {
+                let MonValueKind::Object(members) = &value.kind else {
+                    return None;
+                };
+                let names = file.index.member_spans(value).into_iter().map(|span| span.name);
+                return Some(AnchoredObject {
+                    members: members.clone(),
+                    names: names.collect(),
+                    file: path,
+                });
+            }
+
+            let (import, _, rest) = imported(file, &name, SymbolKind::Anchor)?;
+            path = resolve_import_path(&import.path, &path);
+            name = rest;
+        }
+
+        None
+    }
+
+    /// The definition of the type a chain ends at.
+    pub fn type_definition(&mut self, chain: &Chain) -> Option<TypeDefinition> {
+        let file = self.file(&chain.hops.last()?.target)?;
+        find_type_definition(&file.doc.root, &chain.name).cloned()
+    }
+
+    /// The types defined in the file at `path`, for `ns.Type` names.
+    pub fn type_definitions(&mut self, path: &Path) -> Vec<TypeDefinition> {
+        let Some(file) = self.file(path) else {
+            return Vec::new();
+        };
+        type_definitions(&file.doc.root).into_iter().cloned().collect()
+    }
+
+    /// The shortest path of imports from the target of `hop`, one of the
+    /// document's imports, back to the document.
+    pub fn cycle(&mut self, hop: Hop) -> Option<Vec<Hop>> {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from([vec![hop]]);
+
+        while let Some(hops) = queue.pop_front() {
+            let path = hops.last()?.target.clone();
+            if path == self.root {
+                return Some(hops);
+            }
+            if hops.len() > MAX_HOPS || !visited.insert(path.clone()) {
+                continue;
+            }
+            let Some(file) = self.file(&path) else {
+                continue;
+            };
+
+            for import in &file.doc.imports {
+                let target = resolve_import_path(&import.path, &path);
+                let mut next = hops.clone();
+                next.push(Hop {
+                    file: path.clone(),
+                    span: import.pos_start..import.pos_end,
+                    target,
+                });
+                queue.push_back(next);
+            }
+        }
+
+        None
+    }
+
+    /// The location of `span` in the file at `path`, with an empty URI for
+    /// the document itself.
+    pub fn location(&mut self, path: &Path, span: ByteRange<usize>) -> Option<Location> {
+        let uri = if path == self.root {
+            String::new()
+        } else {
+            Url::from_file_path(path).ok()?.to_string()
+        };
+        let file = self.file(path)?;
+        Some(Location::new(uri, Range::from_byte_offsets(&file.source, span.start, span.end)))
+    }
+
+    /// A path for messages: relative to the document's directory when it is
+    /// inside it.
+    pub fn display(&self, path: &Path) -> String {
+        let dir = self.root.parent().unwrap_or(Path::new(""));
+        path.strip_prefix(dir).unwrap_or(path).display().to_string()
+    }
+
+    /// Gets a file, parsing it from disk on first use.
+    fn file(&mut self, path: &Path) -> Option<&File> {
+        self.files
+            .entry(path.to_path_buf())
+            .or_insert_with(|| {
+                let source = fs::read_to_string(path).ok()?;
+                let doc = Parser::new(&source).ok()?.parse_document().ok()?;
+                Some(File { index: SourceIndex::new(&source), source, doc })
+            })
+            .as_ref()
+    }
+}
+
+/// The import of `file` that brings in `name`, `import { &name, Name }` or
+/// `import * as ns` for `ns.name`, with the span of the imported name and the
+/// name in the imported file.
+fn imported<'f>(
+    file: &'f File,
+    name: &str,
+    kind: SymbolKind,
+) -> Option<(&'f ImportStatement, ByteRange<usize>, String)> {
+    file.doc.imports.iter().find_map(|import| {
+        let spans = file.index.import_name_spans(import);
+        match &import.spec {
+            ImportSpec::Named(specifiers) => specifiers
+                .iter()
+                .position(|s| s.name == name && s.is_anchor == (kind == SymbolKind::Anchor))
+                .and_then(|i| spans.get(i).cloned())
+                .map(|span| (import, span, name.to_string())),
+            ImportSpec::Namespace(namespace) => {
+                let rest = name.strip_prefix(namespace.as_str())?.strip_prefix('.')?;
+                Some((import, spans.first()?.clone(), rest.to_string()))
+            }
+        }
+    })
+}
+
+/// Span of the name of the anchor or type `name` defined in `file`.
+fn definition(file: &File, name: &str, kind: SymbolKind) -> Option<ByteRange<usize>> {
+    match kind {
+        SymbolKind::Anchor => {
+            let value = find_anchor_value(&file.doc.root, name)?;
+            Some(file.index.anchor_before(value).unwrap_or_else(|| value_head(value, None)))
+        }
+        _ => {
+            let typedef = find_type_definition(&file.doc.root, name)?;
+            let start = typedef.name_span.offset();
+            Some(start..start + typedef.name_span.len())
+        }
+    }
+}
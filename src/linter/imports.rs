@@ -1,115 +1,379 @@
 // Import analysis for MON files
 
-use crate::linter::{LintConfig, LintResult, diagnostic::DiagnosticCode};
-use mon_core::ast::{Member, MonValue, MonValueKind};
+use crate::linter::edit::{Fix, TextEdit};
+use crate::linter::import_graph::{Hop, ImportGraph};
+use crate::linter::navigation::resolve_import_path;
+use crate::linter::source_index::{SourceIndex, removal_span};
+use crate::linter::{
+    DiagnosticTag, LintConfig, LintResult, Range, RelatedInformation, SymbolKind,
+    diagnostic::DiagnosticCode,
+};
+use mon_core::ast::{
+    ImportSpec, ImportStatement, Member, MonDocument, MonValue, MonValueKind, TypeDef, TypeSpec,
+};
 use std::collections::HashSet;
+use std::ops::Range as ByteRange;
+use std::path::Path;
 
 pub struct ImportAnalyzer {
     config: LintConfig,
 }
 
+/// Names a document refers to.
+#[derive(Default)]
+struct References {
+    /// Aliased and spread anchors, `ns.name` included
+    anchors: HashSet<String>,
+    /// Types in annotations, struct fields and enum values
+    types: HashSet<String>,
+}
+
+impl References {
+    fn uses_namespace(&self, namespace: &str) -> bool {
+        let prefix = format!("{}.", namespace);
+        self.anchors.iter().chain(&self.types).any(|name| name.starts_with(&prefix))
+    }
+}
+
 impl ImportAnalyzer {
     pub fn new(config: LintConfig) -> Self {
         Self { config }
     }
 
-    pub fn analyze(&self, root: &MonValue, result: &mut LintResult) {
-        // Collect all used references (anchors, aliases, types)
-        let used_refs = self.collect_used_references(root);
+    /// Analyzes the imports of `doc`. The import chain and cycle rules read
+    /// the imported files, so they only run when the document's `path` is
+    /// known.
+    pub fn analyze(
+        &self,
+        doc: &MonDocument,
+        source: &str,
+        path: Option<&Path>,
+        result: &mut LintResult,
+    ) {
+        let enabled = |code| self.config.is_enabled(code);
+        let mut references = References::default();
+        self.collect_references(&doc.root, &mut references);
 
-        // Note: To properly detect unused imports, we'd need access to MonDocument.imports
-        // For now, we can detect deep import chains by counting namespace usage
-        self.detect_namespace_complexity(root, result);
-    }
+        if self.config.warn_unused_imports && enabled(DiagnosticCode::UnusedImport) {
+            self.detect_unused_imports(&doc.imports, &references, source, result);
+        }
 
-    fn collect_used_references(&self, value: &MonValue) -> HashSet<String> {
-        let mut used = HashSet::new();
-        self.collect_used_recursive(value, &mut used);
-        used
+        let Some(path) = path.filter(|_| !doc.imports.is_empty()) else {
+            return;
+        };
+        let mut graph = ImportGraph::new(path, doc, source);
+        if enabled(DiagnosticCode::DeepImportChain) {
+            self.detect_import_chains(&doc.imports, &references, source, &mut graph, result);
+        }
+        if enabled(DiagnosticCode::CircularDependency) {
+            self.detect_import_cycles(&doc.imports, source, &mut graph, result);
+        }
     }
 
-    fn collect_used_recursive(&self, value: &MonValue, used: &mut HashSet<String>) {
+    fn collect_references(&self, value: &MonValue, references: &mut References) {
         match &value.kind {
-            MonValueKind::Alias(name) => {
-                used.insert(name.clone());
+            MonValueKind::Alias(name) | MonValueKind::ArraySpread(name) => {
+                references.anchors.insert(name.clone());
             }
-            MonValueKind::ArraySpread(name) => {
-                used.insert(name.clone());
+            MonValueKind::EnumValue { enum_name, .. } => {
+                references.types.insert(enum_name.clone());
             }
             MonValueKind::Object(members) => {
                 for member in members {
                     match member {
                         Member::Pair(pair) => {
-                            self.collect_used_recursive(&pair.value, used);
+                            if let Some(spec) = &pair.validation {
+                                collect_types(spec, &mut references.types);
+                            }
+                            self.collect_references(&pair.value, references);
                         }
                         Member::Spread(name) => {
-                            used.insert(name.clone());
+                            references.anchors.insert(name.clone());
                         }
-                        _ => {}
+                        Member::TypeDefinition(typedef) => {
+                            if let TypeDef::Struct(def) = &typedef.def_type {
+                                for field in &def.fields {
+                                    collect_types(&field.type_spec, &mut references.types);
+                                    if let Some(default) = &field.default_value {
+                                        self.collect_references(default, references);
+                                    }
+                                }
+                            }
+                        }
+                        Member::Import(_) => {}
                     }
                 }
             }
             MonValueKind::Array(items) => {
                 for item in items {
-                    self.collect_used_recursive(item, used);
+                    self.collect_references(item, references);
                 }
             }
             _ => {}
         }
     }
 
-    fn detect_namespace_complexity(&self, value: &MonValue, result: &mut LintResult) {
-        // Detect if there are too many namespaced references
-        // This could indicate complex import chains
-        let namespace_references = self.count_namespace_refs(value);
-
-        if namespace_references > self.config.max_import_chain_depth * 3 {
-            result.add_diagnostic(
-                DiagnosticCode::DeepImportChain,
-                format!(
-                    "File has {} namespaced references, consider simplifying imports",
-                    namespace_references
-                ),
-                None,
-            );
-        }
-    }
+    /// Reports named imports that are never referenced, and namespaces that
+    /// no reference goes through.
+    fn detect_unused_imports(
+        &self,
+        imports: &[ImportStatement],
+        references: &References,
+        source: &str,
+        result: &mut LintResult,
+    ) {
+        let index = SourceIndex::new(source);
+        let range = |span: ByteRange<usize>| Range::from_byte_offsets(source, span.start, span.end);
 
-    fn count_namespace_refs(&self, value: &MonValue) -> usize {
-        let mut count = 0;
-        self.count_namespace_refs_recursive(value, &mut count);
-        count
-    }
+        for import in imports {
+            let names = index.import_name_spans(import);
+            let statement = removal_span(source, &(import.pos_start..import.pos_end));
+            // Files importing this one may get the names through it
+            let remove_statement = || {
+                Fix::new("Remove unused import", vec![TextEdit::new(range(statement.clone()), "")])
+                    .affecting_importers()
+            };
 
-    fn count_namespace_refs_recursive(&self, value: &MonValue, count: &mut usize) {
-        match &value.kind {
-            MonValueKind::Alias(name) | MonValueKind::ArraySpread(name) => {
-                // Namespaced references contain a dot
-                if name.contains('.') {
-                    *count += 1;
+            match &import.spec {
+                ImportSpec::Namespace(namespace) => {
+                    if references.uses_namespace(namespace) {
+                        continue;
+                    }
+                    let Some(name) = names.first() else {
+                        continue;
+                    };
+                    let diagnostic = result.add_diagnostic_with_range(
+                        DiagnosticCode::UnusedImport,
+                        format!("Namespace '{}' is never used", namespace),
+                        Some(range(name.clone())),
+                        vec![],
+                        vec![DiagnosticTag::Unnecessary],
+                    );
+                    diagnostic.fixes.push(remove_statement());
                 }
-            }
-            MonValueKind::Object(members) => {
-                for member in members {
-                    match member {
-                        Member::Pair(pair) => {
-                            self.count_namespace_refs_recursive(&pair.value, count);
-                        }
-                        Member::Spread(name) => {
-                            if name.contains('.') {
-                                *count += 1;
-                            }
-                        }
-                        _ => {}
+                ImportSpec::Named(specifiers) => {
+                    if names.len() != specifiers.len() {
+                        continue;
+                    }
+                    // Item spans, with the `&` of anchors
+                    let items: Vec<_> = names
+                        .iter()
+                        .map(|name| {
+                            let start = if source[..name.start].ends_with('&') {
+                                name.start - 1
+                            } else {
+                                name.start
+                            };
+                            start..name.end
+                        })
+                        .collect();
+                    let unused: Vec<_> = specifiers
+                        .iter()
+                        .map(|specifier| {
+                            let used = if specifier.is_anchor {
+                                &references.anchors
+                            } else {
+                                &references.types
+                            };
+                            !used.contains(&specifier.name)
+                        })
+                        .collect();
+                    let all_unused = unused.iter().all(|&unused| unused);
+
+                    for (i, specifier) in specifiers.iter().enumerate().filter(|&(i, _)| unused[i])
+                    {
+                        let (kind, written) = if specifier.is_anchor {
+                            ("anchor", format!("&{}", specifier.name))
+                        } else {
+                            ("type", specifier.name.clone())
+                        };
+                        let diagnostic = result.add_diagnostic_with_range(
+                            DiagnosticCode::UnusedImport,
+                            format!("Imported {} '{}' is never used", kind, written),
+                            Some(range(items[i].clone())),
+                            vec![],
+                            vec![DiagnosticTag::Unnecessary],
+                        );
+
+                        // The item and the comma after it, or before it for the last one
+                        let fix = if all_unused {
+                            remove_statement()
+                        } else {
+                            let removal = match items.get(i + 1) {
+                                Some(next) => items[i].start..next.start,
+                                None => items[i - 1].end..items[i].end,
+                            };
+                            Fix::new(
+                                format!("Remove '{}' from import", written),
+                                vec![TextEdit::new(range(removal), "")],
+                            )
+                            .affecting_importers()
+                        };
+                        diagnostic.fixes.push(fix);
                     }
                 }
             }
-            MonValueKind::Array(items) => {
-                for item in items {
-                    self.count_namespace_refs_recursive(item, count);
+        }
+    }
+
+    /// Reports imported names that reach the document through more imports
+    /// than `max_import_chain_depth`.
+    fn detect_import_chains(
+        &self,
+        imports: &[ImportStatement],
+        references: &References,
+        source: &str,
+        graph: &mut ImportGraph,
+        result: &mut LintResult,
+    ) {
+        let root = graph.root().to_path_buf();
+        let index = SourceIndex::new(source);
+
+        for import in imports {
+            let target = resolve_import_path(&import.path, &root);
+            let spans = index.import_name_spans(import);
+
+            // Each named item, or each name used through the namespace
+            let names: Vec<(String, SymbolKind, String, ByteRange<usize>)> = match &import.spec {
+                ImportSpec::Named(specifiers) => specifiers
+                    .iter()
+                    .zip(&spans)
+                    .map(|(specifier, span)| {
+                        let (kind, written) = if specifier.is_anchor {
+                            (SymbolKind::Anchor, format!("&{}", specifier.name))
+                        } else {
+                            (SymbolKind::Type, specifier.name.clone())
+                        };
+                        (specifier.name.clone(), kind, written, span.clone())
+                    })
+                    .collect(),
+                ImportSpec::Namespace(namespace) => {
+                    let Some(span) = spans.first() else {
+                        continue;
+                    };
+                    let prefix = format!("{}.", namespace);
+                    let mut used: Vec<_> = references
+                        .anchors
+                        .iter()
+                        .map(|name| (name, SymbolKind::Anchor))
+                        .chain(references.types.iter().map(|name| (name, SymbolKind::Type)))
+                        .filter_map(|(name, kind)| Some((name.strip_prefix(&prefix)?, kind, name)))
+                        .map(|(rest, kind, name)| {
+                            (rest.to_string(), kind, name.clone(), span.clone())
+                        })
+                        .collect();
+                    used.sort_by(|a, b| a.2.cmp(&b.2));
+                    used
+                }
+            };
+
+            for (name, kind, written, span) in names {
+                let hop = Hop { file: root.clone(), span: span.clone(), target: target.clone() };
+                let Some(chain) = graph.chain(hop, &name, kind) else {
+                    continue;
+                };
+                if chain.hops.len() <= self.config.max_import_chain_depth {
+                    continue;
                 }
+
+                let files: Vec<_> = std::iter::once(graph.display(&root))
+                    .chain(chain.hops.iter().map(|hop| graph.display(&hop.target)))
+                    .collect();
+                let mut related: Vec<_> = chain.hops[1..]
+                    .iter()
+                    .filter_map(|hop| {
+                        let message =
+                            format!("Imported by '{}' from here", graph.display(&hop.file));
+                        Some(RelatedInformation {
+                            location: graph.location(&hop.file, hop.span.clone())?,
+                            message,
+                        })
+                    })
+                    .collect();
+                let defining = &chain.hops[chain.hops.len() - 1].target;
+                if let Some(location) = graph.location(defining, chain.definition.clone()) {
+                    related.push(RelatedInformation {
+                        location,
+                        message: format!("'{}' is defined here", written),
+                    });
+                }
+
+                result.add_diagnostic_with_range(
+                    DiagnosticCode::DeepImportChain,
+                    format!(
+                        "'{}' is imported through {} imports (max {}): {}",
+                        written,
+                        chain.hops.len(),
+                        self.config.max_import_chain_depth,
+                        files.join(" -> ")
+                    ),
+                    Some(Range::from_byte_offsets(source, span.start, span.end)),
+                    related,
+                    vec![],
+                );
+            }
+        }
+    }
+
+    /// Reports the imports through which the document ends up importing
+    /// itself.
+    fn detect_import_cycles(
+        &self,
+        imports: &[ImportStatement],
+        source: &str,
+        graph: &mut ImportGraph,
+        result: &mut LintResult,
+    ) {
+        let root = graph.root().to_path_buf();
+
+        for import in imports {
+            let span = import.pos_start..import.pos_end;
+            let target = resolve_import_path(&import.path, &root);
+            let hop = Hop { file: root.clone(), span: span.clone(), target };
+            let Some(cycle) = graph.cycle(hop) else {
+                continue;
+            };
+
+            let files: Vec<_> = std::iter::once(graph.display(&root))
+                .chain(cycle.iter().map(|hop| graph.display(&hop.target)))
+                .collect();
+            let related = cycle[1..]
+                .iter()
+                .filter_map(|hop| {
+                    let message = format!(
+                        "'{}' imports '{}' here",
+                        graph.display(&hop.file),
+                        graph.display(&hop.target)
+                    );
+                    Some(RelatedInformation {
+                        location: graph.location(&hop.file, hop.span.clone())?,
+                        message,
+                    })
+                })
+                .collect();
+
+            result.add_diagnostic_with_range(
+                DiagnosticCode::CircularDependency,
+                format!("Import cycle: {}", files.join(" -> ")),
+                Some(Range::from_byte_offsets(source, span.start, span.end)),
+                related,
+                vec![],
+            );
+        }
+    }
+}
+
+/// Adds the type names in `spec` to `types`.
+fn collect_types(spec: &TypeSpec, types: &mut HashSet<String>) {
+    match spec {
+        TypeSpec::Simple(name, _) => {
+            types.insert(name.clone());
+        }
+        TypeSpec::Collection(items, _) => {
+            for item in items {
+                collect_types(item, types);
             }
-            _ => {}
         }
+        TypeSpec::Spread(inner, _) => collect_types(inner, types),
     }
 }
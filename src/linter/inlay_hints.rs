@@ -0,0 +1,301 @@
+//! Inlay hints for struct-typed values and object spreads.
+//!
+//! Inside a value annotated with `:: Struct` (or `:: [Struct]`), each key
+//! that matches a field gets the field's declared type, following nested
+//! struct fields. Each `...*spread` in an object lists the keys it adds to
+//! the object and the anchor every key comes from, so keys contributed by
+//! nested spreads are traced to their own anchor.
+
+use crate::linter::navigation::Navigator;
+use crate::linter::source_index::SourceIndex;
+use crate::linter::{Position, Range};
+use mon_core::ast::{Member, MonValue, MonValueKind, StructDef, TypeDef, TypeSpec};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Maximum depth of nested spreads followed for one spread hint.
+const MAX_SPREAD_DEPTH: usize = 16;
+
+/// What an inlay hint shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintKind {
+    /// The declared type of a struct field, after the key
+    Type,
+
+    /// The keys an object spread contributes, after the spread
+    Spread,
+}
+
+/// Text shown inline at a position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlayHint {
+    pub position: Position,
+    pub label: String,
+    pub kind: HintKind,
+}
+
+/// Builds the inlay hints of the navigator's root document. With `range`,
+/// only hints inside it are returned.
+pub fn inlay_hints(navigator: &mut Navigator<'_>, range: Option<Range>) -> Vec<InlayHint> {
+    let root = navigator.root();
+    let root_path = navigator.root_path().to_path_buf();
+    let mut builder = HintBuilder {
+        source: &root.source,
+        index: SourceIndex::new(&root.source),
+        root_path,
+        navigator,
+        hints: Vec::new(),
+    };
+    builder.value(&root.document.root, None);
+
+    let inside = |position: Position| {
+        range.is_none_or(|range| {
+            let key = |p: Position| (p.line, p.character);
+            key(range.start) <= key(position) && key(position) <= key(range.end)
+        })
+    };
+    let mut hints: Vec<_> =
+        builder.hints.into_iter().filter(|hint| inside(hint.position)).collect();
+    hints.sort_by_key(|hint| (hint.position.line, hint.position.character));
+    hints
+}
+
+/// A struct a value is expected to match, with the file that defines it so
+/// its field types resolve from there.
+struct Expected {
+    path: PathBuf,
+    def: StructDef,
+}
+
+struct HintBuilder<'a, 'n> {
+    source: &'a str,
+    index: SourceIndex,
+    root_path: PathBuf,
+    navigator: &'a mut Navigator<'n>,
+    hints: Vec<InlayHint>,
+}
+
+impl HintBuilder<'_, '_> {
+    /// Walks a value that is expected to match `expected`, if anything.
+    fn value(&mut self, value: &MonValue, expected: Option<&Expected>) {
+        match &value.kind {
+            MonValueKind::Object(members) => self.object(value, members, expected),
+            MonValueKind::Array(items) => {
+                for item in items {
+                    self.value(item, None);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn object(&mut self, object: &MonValue, members: &[Member], expected: Option<&Expected>) {
+        let spans = self.index.member_spans(object);
+
+        // Written keys override what spreads before them contribute
+        let mut written: Vec<HashSet<&str>> = vec![HashSet::new(); members.len()];
+        let mut later = HashSet::new();
+        for (i, member) in members.iter().enumerate().rev() {
+            written[i] = later.clone();
+            if let Member::Pair(pair) = member {
+                later.insert(pair.key.as_str());
+            }
+        }
+
+        let mut spread_keys: Vec<Vec<(String, String)>> = Vec::new();
+        for member in members {
+            if let Member::Spread(name) = member {
+                let mut keys = Vec::new();
+                self.spread_keys(&self.root_path.clone(), name, 0, &mut keys);
+                spread_keys.push(keys);
+            }
+        }
+        // A key from a spread shows only on the last spread contributing it
+        let mut seen = HashSet::new();
+        for keys in spread_keys.iter_mut().rev() {
+            keys.retain(|(key, _)| seen.insert(key.clone()));
+        }
+        let mut spread_keys = spread_keys.into_iter();
+
+        for (i, (member, span)) in members.iter().zip(spans).enumerate() {
+            match member {
+                Member::Pair(pair) => {
+                    let field = expected.and_then(|expected| {
+                        let field = expected.def.fields.iter().find(|f| f.name == pair.key)?;
+                        Some((&expected.path, field))
+                    });
+
+                    match (&pair.validation, field) {
+                        (Some(type_spec), _) => {
+                            let expected = self.expected(&self.root_path.clone(), type_spec);
+                            self.annotated(&pair.value, type_spec, expected.as_ref());
+                        }
+                        (None, Some((path, field))) => {
+                            if let Some(name) = &span.name {
+                                self.hints.push(InlayHint {
+                                    position: Position::from_byte_offset(self.source, name.end),
+                                    label: format!(":: {}", field.type_spec),
+                                    kind: HintKind::Type,
+                                });
+                            }
+                            let expected = self.expected(path, &field.type_spec);
+                            self.annotated(&pair.value, &field.type_spec, expected.as_ref());
+                        }
+                        (None, None) => self.value(&pair.value, None),
+                    }
+                }
+                Member::Spread(_) => {
+                    let keys = spread_keys.next().unwrap_or_default();
+                    let keys: Vec<_> = keys
+                        .into_iter()
+                        .filter(|(key, _)| !written[i].contains(key.as_str()))
+                        .map(|(key, anchor)| format!("{} ← &{}", key, anchor))
+                        .collect();
+                    if !keys.is_empty() {
+                        self.hints.push(InlayHint {
+                            position: Position::from_byte_offset(self.source, span.span.end),
+                            label: keys.join(", "),
+                            kind: HintKind::Spread,
+                        });
+                    }
+                }
+                Member::TypeDefinition(_) | Member::Import(_) => {}
+            }
+        }
+    }
+
+    /// Walks a value declared as `type_spec`, which resolved to `expected`.
+    fn annotated(&mut self, value: &MonValue, type_spec: &TypeSpec, expected: Option<&Expected>) {
+        match (&value.kind, type_spec) {
+            (MonValueKind::Array(items), TypeSpec::Collection(..)) => {
+                for item in items {
+                    self.value(item, expected);
+                }
+            }
+            _ => self.value(value, expected),
+        }
+    }
+
+    /// Resolves the struct a type spec expects: `Struct`, `[Struct]` or
+    /// `[Struct...]`, as written in the file at `path`.
+    fn expected(&mut self, path: &Path, type_spec: &TypeSpec) -> Option<Expected> {
+        let name = match type_spec {
+            TypeSpec::Simple(name, _) => name,
+            TypeSpec::Collection(items, _) => match items.as_slice() {
+                [TypeSpec::Simple(name, _)] => name,
+                [TypeSpec::Spread(inner, _)] => match inner.as_ref() {
+                    TypeSpec::Simple(name, _) => name,
+                    _ => return None,
+                },
+                _ => return None,
+            },
+            TypeSpec::Spread(..) => return None,
+        };
+
+        let (path, typedef) = self.navigator.type_definition_in(path, name)?;
+        match typedef.def_type {
+            TypeDef::Struct(def) => Some(Expected { path, def }),
+            TypeDef::Enum(_) => None,
+        }
+    }
+
+    /// Collects the keys `...*name` (written in the file at `path`) adds,
+    /// each with the anchor it is written under.
+    fn spread_keys(
+        &mut self,
+        path: &Path,
+        name: &str,
+        depth: usize,
+        keys: &mut Vec<(String, String)>,
+    ) {
+        if depth > MAX_SPREAD_DEPTH {
+            return;
+        }
+        let Some((definition, value)) = self.navigator.anchor_value_in(path, name) else {
+            return;
+        };
+        let members = match &value.kind {
+            MonValueKind::Object(members) => members,
+            // `&name: *other` contributes what `other` does
+            MonValueKind::Alias(target) => {
+                return self.spread_keys(&definition.location.path, target, depth + 1, keys);
+            }
+            _ => return,
+        };
+
+        for member in members {
+            match member {
+                Member::Pair(pair) => {
+                    keys.retain(|(key, _)| key != &pair.key);
+                    keys.push((pair.key.clone(), definition.name.clone()));
+                }
+                Member::Spread(inner) => {
+                    let mut inner_keys = Vec::new();
+                    self.spread_keys(&definition.location.path, inner, depth + 1, &mut inner_keys);
+                    for (key, anchor) in inner_keys {
+                        keys.retain(|(existing, _)| existing != &key);
+                        keys.push((key, anchor));
+                    }
+                }
+                Member::TypeDefinition(_) | Member::Import(_) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintConfig;
+    use crate::linter::api::AnalysisService;
+
+    fn hints(source: &str) -> Vec<(String, String)> {
+        let result = AnalysisService::new(LintConfig::default())
+            .analyze_document(source, "/tmp/hints.mon")
+            .unwrap();
+        inlay_hints(&mut Navigator::new(&result), None)
+            .into_iter()
+            .map(|hint| {
+                let offset = hint.position.to_byte_offset(source);
+                let before = source[..offset].rsplit(|c: char| !c.is_alphanumeric() && c != '_');
+                (before.into_iter().next().unwrap_or_default().to_string(), hint.label)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_struct_field_types() {
+        let source = r#"{
+    Address: #struct { city(String) },
+    User: #struct { name(String), address(Address), tags([String...]) },
+    users :: [User] = [
+        { name: "a", address: { city: "x" }, tags: [] },
+    ],
+}"#;
+        assert_eq!(
+            hints(source),
+            vec![
+                ("name".to_string(), ":: String".to_string()),
+                ("address".to_string(), ":: Address".to_string()),
+                ("city".to_string(), ":: String".to_string()),
+                ("tags".to_string(), ":: [String...]".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spread_sources() {
+        let source = r#"{
+    &defaults: { port: 80, debug: false },
+    &base: { ...*defaults, host: "x" },
+    &alias: *base,
+    app: { ...*alias, debug: true },
+}"#;
+        let found = hints(source);
+        assert_eq!(
+            found[0],
+            ("defaults".to_string(), "port ← &defaults, debug ← &defaults".to_string())
+        );
+        assert_eq!(found[1], ("alias".to_string(), "port ← &defaults, host ← &base".to_string()));
+    }
+}
@@ -1,27 +1,52 @@
 // Linter module - Code quality analysis for MON files
 
 pub mod api;
+pub mod completion;
 pub mod complexity;
+pub mod config;
 pub mod diagnostic;
+pub mod edit;
+pub mod fix;
+pub mod hover;
+pub mod import_graph;
 pub mod imports;
+pub mod inlay_hints;
+pub mod naming;
+pub mod navigation;
+pub mod outline;
+pub mod overrides;
 pub mod position;
+pub mod rename;
+pub mod render;
 pub mod rules;
+pub mod semantic_tokens;
 pub mod smells;
+pub mod source_index;
+pub mod structure;
+pub mod suppression;
 pub mod symbol_table;
+pub mod validation;
+pub mod workspace;
 
 #[cfg(test)]
 mod tests;
 
 pub use complexity::ComplexityAnalyzer;
+pub use config::LintConfig;
 pub use diagnostic::{DiagnosticCode, DiagnosticSeverity};
+pub use edit::Fix;
 pub use imports::ImportAnalyzer;
-pub use position::{DiagnosticTag, Range, RelatedInformation};
+pub use naming::NamingAnalyzer;
+pub use overrides::OverrideAnalyzer;
+pub use position::{DiagnosticTag, Position, Range, RelatedInformation};
 pub use smells::SmellDetector;
 pub use symbol_table::{ReferenceKind, Symbol, SymbolKind, SymbolReference, SymbolTable};
+pub use validation::ValidationAnalyzer;
 
 use miette::Result;
 use mon_core::ast::MonDocument;
 use serde::Serialize;
+use std::path::Path;
 
 /// Result of linting a MON document.
 ///
@@ -48,6 +73,7 @@ pub struct LintResult {
 ///     range: Some(Range::new(Position::new(1, 5), Position::new(1, 8))),
 ///     related_information: vec![],
 ///     tags: vec![DiagnosticTag::Unnecessary],
+///     fixes: vec![],
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize)]
@@ -77,6 +103,10 @@ pub struct Diagnostic {
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub tags: Vec<DiagnosticTag>,
 
+    /// Automatic fixes, offered as quick fixes by the language server
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub fixes: Vec<Fix>,
+
     /// Legacy location string (deprecated, use range instead)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<String>,
@@ -99,6 +129,8 @@ impl LintResult {
     /// * `range` - Optional precise location in the source
     /// * `related_info` - Optional additional context
     /// * `tags` - Optional tags (unnecessary, deprecated)
+    ///
+    /// Returns the added diagnostic so fixes can be attached to it.
     pub fn add_diagnostic_with_range(
         &mut self,
         code: DiagnosticCode,
@@ -106,7 +138,7 @@ impl LintResult {
         range: Option<Range>,
         related_info: Vec<RelatedInformation>,
         tags: Vec<DiagnosticTag>,
-    ) {
+    ) -> &mut Diagnostic {
         self.diagnostics.push(Diagnostic {
             severity: code.severity(),
             code_name: format!("{:?}", code),
@@ -115,29 +147,10 @@ impl LintResult {
             range,
             related_information: related_info,
             tags,
+            fixes: vec![],
             location: None,
         });
-    }
-
-    /// Legacy method: adds a diagnostic with optional location string.
-    ///
-    /// Prefer `add_diagnostic_with_range` for new code.
-    pub fn add_diagnostic(
-        &mut self,
-        code: DiagnosticCode,
-        message: String,
-        location: Option<String>,
-    ) {
-        self.diagnostics.push(Diagnostic {
-            severity: code.severity(),
-            code_name: format!("{:?}", code),
-            code,
-            message,
-            range: None,
-            related_information: vec![],
-            tags: vec![],
-            location,
-        });
+        self.diagnostics.last_mut().expect("diagnostic was just pushed")
     }
 
     pub fn errors(&self) -> Vec<&Diagnostic> {
@@ -157,108 +170,62 @@ impl LintResult {
     pub fn infos(&self) -> Vec<&Diagnostic> {
         self.diagnostics.iter().filter(|d| matches!(d.severity, DiagnosticSeverity::Info)).collect()
     }
-
-    // Legacy compatibility methods
-    pub fn add_warning(&mut self, _rule: &str, message: String, location: Option<String>) {
-        // Map old warnings to new system - need to infer code from message
-        let code = if message.contains("nesting") {
-            DiagnosticCode::MaxNestingDepth
-        } else if message.contains("members") {
-            DiagnosticCode::MaxObjectMembers
-        } else if message.contains("items") {
-            DiagnosticCode::MaxArrayItems
-        } else {
-            DiagnosticCode::UnusedAnchor
-        };
-        self.add_diagnostic(code, message, location);
-    }
-
-    pub fn add_error(&mut self, _rule: &str, message: String, location: Option<String>) {
-        let code = if message.contains("Duplicate") {
-            DiagnosticCode::DuplicateKey
-        } else {
-            DiagnosticCode::CircularDependency
-        };
-        self.add_diagnostic(code, message, location);
-    }
-
-    pub fn add_info(&mut self, _rule: &str, message: String) {
-        self.add_diagnostic(DiagnosticCode::MagicNumber, message, None);
-    }
 }
 
 pub struct Linter {
     config: LintConfig,
 }
 
-#[derive(Debug, Clone)]
-pub struct LintConfig {
-    pub max_nesting_depth: usize,
-    pub max_object_members: usize,
-    pub max_array_items: usize,
-    pub max_import_chain_depth: usize,
-    pub warn_unused_anchors: bool,
-    pub warn_magic_numbers: bool,
-    pub suggest_type_validation: bool,
-}
-
-impl Default for LintConfig {
-    fn default() -> Self {
-        Self {
-            max_nesting_depth: 4,
-            max_object_members: 20,
-            max_array_items: 100,
-            max_import_chain_depth: 2,
-            warn_unused_anchors: true,
-            warn_magic_numbers: false,
-            suggest_type_validation: false,
-        }
-    }
-}
-
-impl LintConfig {
-    /// Create a config that only runs specified rules
-    pub fn with_only_rules(self, _rules: Vec<String>) -> Self {
-        // TODO: Implement rule filtering
-        // For MVP, just return self
-        self
+impl Linter {
+    pub fn new(config: LintConfig) -> Self {
+        Self { config }
     }
 
-    /// Create a config that disables specified rules
-    pub fn without_rules(self, _rules: Vec<String>) -> Self {
-        // TODO: Implement rule filtering
-        // For MVP, just return self
-        self
+    /// Lints a document on its own. Rules that read the files it imports
+    /// are skipped; use `lint_file` to run them.
+    pub fn lint(&self, doc: &MonDocument, source: &str) -> Result<LintResult> {
+        self.lint_at(doc, source, None)
     }
 
-    /// Load config from a MonDocument
-    pub fn from_document(_doc: &MonDocument) -> Result<Self> {
-        // TODO: Parse config from MonDocument
-        // For MVP, just return default
-        Ok(Self::default())
+    /// Lints the document at `path`, following its imports to other files.
+    pub fn lint_file(&self, doc: &MonDocument, source: &str, path: &Path) -> Result<LintResult> {
+        self.lint_at(doc, source, Some(path))
     }
-}
 
-impl Linter {
-    pub fn new(config: LintConfig) -> Self {
-        Self { config }
-    }
-
-    pub fn lint(&self, doc: &MonDocument, _source: &str) -> Result<LintResult> {
+    fn lint_at(&self, doc: &MonDocument, source: &str, path: Option<&Path>) -> Result<LintResult> {
         let mut result = LintResult::new();
 
         // Run complexity analysis
         let complexity_analyzer = ComplexityAnalyzer::new(self.config.clone());
-        complexity_analyzer.analyze(&doc.root, &mut result);
+        complexity_analyzer.analyze(&doc.root, source, &mut result);
 
         // Run smell detection
-        let smell_detector =
-            SmellDetector::new(self.config.warn_unused_anchors, self.config.warn_magic_numbers);
-        smell_detector.detect(&doc.root, &mut result);
+        let smell_detector = SmellDetector::new(self.config.clone());
+        smell_detector.detect(&doc.root, source, &mut result);
+
+        // Run overridden key detection
+        let override_analyzer = OverrideAnalyzer::new(self.config.clone());
+        override_analyzer.analyze(doc, source, path, &mut result);
+
+        // Run naming convention checks
+        let naming_analyzer = NamingAnalyzer::new(self.config.clone());
+        naming_analyzer.analyze(&doc.root, source, &mut result);
 
         // Run import analysis
         let import_analyzer = ImportAnalyzer::new(self.config.clone());
-        import_analyzer.analyze(&doc.root, &mut result);
+        import_analyzer.analyze(doc, source, path, &mut result);
+
+        // Run type validation suggestions
+        let validation_analyzer = ValidationAnalyzer::new(self.config.clone());
+        validation_analyzer.analyze(doc, source, path, &mut result);
+
+        // Drop what suppression comments silence
+        suppression::apply(source, &self.config, &mut result);
+
+        // Apply the configured severities
+        for diagnostic in &mut result.diagnostics {
+            diagnostic.severity = self.config.severity(diagnostic.code);
+        }
 
         Ok(result)
     }
@@ -0,0 +1,677 @@
+//! Naming convention checks (LINT3002).
+//!
+//! Without configured styles, the keys of each object only have to agree on
+//! snake_case or camelCase. With `enforce_naming_convention` set to an object,
+//! keys, anchors, type names, struct fields and enum variants are each held to
+//! their own style, and a violation comes with a fix that renames the name and
+//! its usages in the document. Other files may import anchors and types, so
+//! only key renames are applied by `mon lint --fix`. A `&key` is a key and an
+//! anchor at once, and gets one diagnostic whose fix renames both.
+//!
+//! Objects under an exempt key path are not checked, and neither are quoted
+//! keys unless asked for. Keys of values annotated with `::` follow the struct
+//! fields, so only the `fields` style applies to them.
+
+use crate::linter::config::NamingConvention;
+use crate::linter::edit::{Fix, TextEdit};
+use crate::linter::navigation::type_definitions;
+use crate::linter::source_index::{MemberSpan, SourceIndex};
+use crate::linter::{LintConfig, LintResult, Range, diagnostic::DiagnosticCode};
+use mon_core::ast::{Member, MonValue, MonValueKind, StructDef, TypeDef, TypeDefinition, TypeSpec};
+use std::collections::HashMap;
+use std::ops::Range as ByteRange;
+
+/// A way of writing names made of several words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamingStyle {
+    /// `max_retries`
+    Snake,
+    /// `maxRetries`
+    Camel,
+    /// `max-retries`, which only quoted keys can use
+    Kebab,
+    /// `MaxRetries`
+    Pascal,
+}
+
+impl NamingStyle {
+    pub const ALL: [NamingStyle; 4] =
+        [NamingStyle::Snake, NamingStyle::Camel, NamingStyle::Kebab, NamingStyle::Pascal];
+
+    /// Looks up a style by the name it is written as in the configuration.
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|style| style.label() == name)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NamingStyle::Snake => "snake_case",
+            NamingStyle::Camel => "camelCase",
+            NamingStyle::Kebab => "kebab-case",
+            NamingStyle::Pascal => "PascalCase",
+        }
+    }
+
+    /// Writes `name` in this style, keeping leading underscores
+    /// (`_maxRetries` -> `_max_retries`).
+    pub fn convert(self, name: &str) -> String {
+        let rest = name.trim_start_matches('_');
+        let mut out = name[..name.len() - rest.len()].to_string();
+        let words = words(rest);
+
+        for (i, word) in words.iter().enumerate() {
+            match self {
+                NamingStyle::Snake | NamingStyle::Kebab => {
+                    if i > 0 {
+                        out.push(if self == NamingStyle::Snake { '_' } else { '-' });
+                    }
+                    out.push_str(word);
+                }
+                NamingStyle::Camel if i == 0 => out.push_str(word),
+                NamingStyle::Camel | NamingStyle::Pascal => {
+                    let mut chars = word.chars();
+                    if let Some(first) = chars.next() {
+                        out.extend(first.to_uppercase());
+                        out.push_str(chars.as_str());
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// Whether `name` is already written in this style.
+    pub fn matches(self, name: &str) -> bool {
+        self.convert(name) == name
+    }
+}
+
+/// Splits a name into lowercase words at `_`, `-` and case changes, keeping
+/// acronyms together (`HTTPServer` -> `http`, `server`).
+fn words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' {
+            words.push(std::mem::take(&mut word));
+            continue;
+        }
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let after_lower = previous.is_lowercase() || previous.is_numeric();
+            let ends_acronym =
+                previous.is_uppercase() && chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if after_lower || ends_acronym {
+                words.push(std::mem::take(&mut word));
+            }
+        }
+        word.extend(c.to_lowercase());
+    }
+    words.push(word);
+    words.retain(|word| !word.is_empty());
+    words
+}
+
+/// Whether `name` can be written without quotes.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+pub struct NamingAnalyzer {
+    config: LintConfig,
+}
+
+impl NamingAnalyzer {
+    pub fn new(config: LintConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn analyze(&self, root: &MonValue, source: &str, result: &mut LintResult) {
+        let convention = &self.config.enforce_naming_convention;
+        if !convention.enabled || !self.config.is_enabled(DiagnosticCode::InconsistentNaming) {
+            return;
+        }
+
+        let index = SourceIndex::new(source);
+        let types = type_definitions(root);
+        let structs: HashMap<&str, &StructDef> = types
+            .iter()
+            .filter_map(|typedef| match &typedef.def_type {
+                TypeDef::Struct(def) => Some((typedef.name.as_str(), def)),
+                TypeDef::Enum(_) => None,
+            })
+            .collect();
+        let mut usages = Usages::default();
+        usages.collect(root, None, &index, &structs);
+        usages.defined_types = types.iter().map(|typedef| typedef.name.clone()).collect();
+
+        let mut checker = Checker {
+            convention,
+            source,
+            index: &index,
+            usages: &usages,
+            exempt: convention.exempt.iter().map(|path| path.split('.').collect()).collect(),
+            path: Vec::new(),
+            result,
+        };
+        checker.value(root, false);
+    }
+}
+
+/// Where the names defined in a document are used, for rename fixes.
+#[derive(Default)]
+struct Usages {
+    /// Names of the anchors defined
+    defined_anchors: Vec<String>,
+    /// Names of the types defined
+    defined_types: Vec<String>,
+    /// Aliases and spreads, by anchor
+    anchors: Vec<(String, ByteRange<usize>)>,
+    /// Type annotations, struct field types and enum values, by type
+    types: Vec<(String, ByteRange<usize>)>,
+    /// Keys of objects validated as a struct, by struct and field
+    fields: Vec<(String, String, ByteRange<usize>)>,
+    /// Variants in enum values, by enum and variant
+    variants: Vec<(String, String, ByteRange<usize>)>,
+}
+
+impl Usages {
+    /// Collects the usages in `value`, which is expected to match `expected`.
+    fn collect(
+        &mut self,
+        value: &MonValue,
+        expected: Option<&TypeSpec>,
+        index: &SourceIndex,
+        structs: &HashMap<&str, &StructDef>,
+    ) {
+        if let Some(anchor) = &value.anchor {
+            self.defined_anchors.push(anchor.clone());
+        }
+        match &value.kind {
+            MonValueKind::Alias(name) | MonValueKind::ArraySpread(name) => {
+                if let Some(span) = index.name_after(value.pos_start) {
+                    self.anchors.push((name.clone(), span));
+                }
+            }
+            MonValueKind::EnumValue { enum_name, variant_name } => {
+                if let Some(span) = index.name_after(value.pos_start) {
+                    let end = (span.start + enum_name.len()).min(span.end);
+                    self.types.push((enum_name.clone(), span.start..end));
+                }
+                let start = value.pos_end.saturating_sub(variant_name.len());
+                self.variants.push((enum_name.clone(), variant_name.clone(), start..value.pos_end));
+            }
+            MonValueKind::Object(members) => {
+                let spans = index.member_spans(value);
+                let validated = match expected {
+                    Some(TypeSpec::Simple(name, _)) => {
+                        structs.get(name.as_str()).map(|def| (name.as_str(), *def))
+                    }
+                    _ => None,
+                };
+                for (i, member) in members.iter().enumerate() {
+                    match member {
+                        Member::Pair(pair) => {
+                            let field = validated.and_then(|(name, def)| {
+                                Some((name, def.fields.iter().find(|f| f.name == pair.key)?))
+                            });
+                            if let (Some((name, _)), Some(span)) =
+                                (field, spans.get(i).and_then(|span| span.name.clone()))
+                            {
+                                self.fields.push((name.to_string(), pair.key.clone(), span));
+                            }
+                            if let Some(spec) = &pair.validation {
+                                self.type_spec(spec);
+                            }
+                            let expected = pair
+                                .validation
+                                .as_ref()
+                                .or(field.map(|(_, field)| &field.type_spec));
+                            self.collect(&pair.value, expected, index, structs);
+                        }
+                        Member::Spread(name) => {
+                            if let Some(span) = spans.get(i).and_then(|span| span.name.clone()) {
+                                self.anchors.push((name.clone(), span));
+                            }
+                        }
+                        Member::TypeDefinition(typedef) => {
+                            if let TypeDef::Struct(def) = &typedef.def_type {
+                                for field in &def.fields {
+                                    self.type_spec(&field.type_spec);
+                                    if let Some(default) = &field.default_value {
+                                        let expected = Some(&field.type_spec);
+                                        self.collect(default, expected, index, structs);
+                                    }
+                                }
+                            }
+                        }
+                        Member::Import(_) => {}
+                    }
+                }
+            }
+            MonValueKind::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    let expected = match expected {
+                        Some(TypeSpec::Collection(types, _)) => match types.as_slice() {
+                            [TypeSpec::Spread(inner, _)] => Some(inner.as_ref()),
+                            types => types.get(i),
+                        },
+                        _ => None,
+                    };
+                    self.collect(item, expected, index, structs);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn type_spec(&mut self, spec: &TypeSpec) {
+        match spec {
+            TypeSpec::Simple(name, span) => {
+                let start = span.offset();
+                self.types.push((name.clone(), start..start + span.len()));
+            }
+            TypeSpec::Collection(types, _) => {
+                for spec in types {
+                    self.type_spec(spec);
+                }
+            }
+            TypeSpec::Spread(inner, _) => self.type_spec(inner),
+        }
+    }
+}
+
+struct Checker<'a> {
+    convention: &'a NamingConvention,
+    source: &'a str,
+    index: &'a SourceIndex,
+    usages: &'a Usages,
+    /// Exempt key paths, split into keys
+    exempt: Vec<Vec<&'a str>>,
+    /// Keys leading to the value being checked
+    path: Vec<String>,
+    result: &'a mut LintResult,
+}
+
+impl Checker<'_> {
+    fn range(&self, span: ByteRange<usize>) -> Range {
+        Range::from_byte_offsets(self.source, span.start, span.end)
+    }
+
+    /// Checks the names defined in `value`. Keys of a `validated` value
+    /// belong to its struct.
+    fn value(&mut self, value: &MonValue, validated: bool) {
+        if self.is_exempt() {
+            return;
+        }
+        match &value.kind {
+            MonValueKind::Object(members) => {
+                let spans = self.index.member_spans(value);
+                if self.convention.keys.is_none() {
+                    self.mixed_keys(members, &spans);
+                }
+
+                for (i, member) in members.iter().enumerate() {
+                    let span = spans.get(i);
+                    let name = span.and_then(|span| span.name.clone());
+                    match member {
+                        Member::Pair(pair) => {
+                            let anchor = span.and_then(|span| span.anchor.clone());
+                            if let (Some(name), true) = (name.clone(), anchor == name) {
+                                // `&key: value` writes the key and the anchor once
+                                self.shared(&pair.key, name, members, validated);
+                            } else {
+                                if let (Some(style), Some(name), false) =
+                                    (self.convention.keys, name, validated)
+                                {
+                                    self.key(&pair.key, name, style, members);
+                                }
+                                if let (Some(anchor), Some(span)) = (&pair.value.anchor, anchor) {
+                                    self.anchor(anchor, span);
+                                }
+                            }
+
+                            self.path.push(pair.key.clone());
+                            self.value(&pair.value, validated || pair.validation.is_some());
+                            self.path.pop();
+                        }
+                        Member::TypeDefinition(typedef) => {
+                            if let (Some(style), Some(name)) = (self.convention.types, name) {
+                                let renamed = style.convert(&typedef.name);
+                                let taken = self.usages.defined_types.contains(&renamed);
+                                let usages = self.spans_of(&self.usages.types, &typedef.name);
+                                let what = format!("Type '{}'", typedef.name);
+                                self.report(what, &typedef.name, name, usages, style, taken);
+                            }
+                            self.type_members(typedef);
+                        }
+                        Member::Spread(_) | Member::Import(_) => {}
+                    }
+                }
+            }
+            MonValueKind::Array(items) => {
+                for item in items {
+                    if let (Some(anchor), Some(span)) =
+                        (&item.anchor, self.index.anchor_before(item))
+                    {
+                        self.anchor(anchor, span);
+                    }
+                    self.value(item, validated);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether the current path is under an exempt one.
+    fn is_exempt(&self) -> bool {
+        self.exempt.iter().any(|pattern| {
+            (1..=self.path.len()).any(|len| path_matches(pattern, &self.path[..len]))
+        })
+    }
+
+    fn is_quoted(&self, key: &str, span: &ByteRange<usize>) -> bool {
+        self.source.get(span.clone()) != Some(key)
+    }
+
+    fn key(&mut self, key: &str, span: ByteRange<usize>, style: NamingStyle, members: &[Member]) {
+        let quoted = self.is_quoted(key, &span);
+        if style.matches(key) || (quoted && self.convention.exempt_quoted_keys) {
+            return;
+        }
+        let renamed = style.convert(key);
+        let clashes = members
+            .iter()
+            .any(|member| matches!(member, Member::Pair(pair) if pair.key == renamed));
+        let text = if quoted || !is_identifier(&renamed) {
+            format!("\"{}\"", renamed)
+        } else {
+            renamed.clone()
+        };
+
+        let range = self.range(span);
+        let diagnostic = self.result.add_diagnostic_with_range(
+            DiagnosticCode::InconsistentNaming,
+            format!("Key '{}' is not {}", key, style.label()),
+            Some(range),
+            vec![],
+            vec![],
+        );
+        if !clashes {
+            diagnostic.fixes.push(Fix::new(
+                format!("Rename '{}' to '{}'", key, renamed),
+                vec![TextEdit::new(range, text)],
+            ));
+        }
+    }
+
+    fn anchor(&mut self, anchor: &str, span: ByteRange<usize>) {
+        let Some(style) = self.convention.anchors else {
+            return;
+        };
+        let renamed = style.convert(anchor);
+        let taken = self.usages.defined_anchors.contains(&renamed);
+        let usages = self.spans_of(&self.usages.anchors, anchor);
+        self.report(format!("Anchor '{}'", anchor), anchor, span, usages, style, taken);
+    }
+
+    /// Checks a `&key` that is a key and an anchor at once, with one
+    /// diagnostic and a fix renaming both, and the usages of the anchor, to a
+    /// name suiting both styles.
+    fn shared(&mut self, name: &str, span: ByteRange<usize>, members: &[Member], validated: bool) {
+        // Keys of a validated value follow the struct's fields, and renaming
+        // the anchor would rename such a key too
+        let key_style = self.convention.keys.filter(|_| !validated);
+        let anchor_style = self.convention.anchors;
+        let not_matching = |style: Option<NamingStyle>| style.filter(|style| !style.matches(name));
+        let (message, style) = match (not_matching(key_style), not_matching(anchor_style)) {
+            (None, None) => return,
+            (Some(key), None) => (format!("Key '{}' is not {}", name, key.label()), key),
+            (None, Some(anchor)) => {
+                (format!("Anchor '{}' is not {}", name, anchor.label()), anchor)
+            }
+            (Some(key), Some(anchor)) if key == anchor => {
+                (format!("Key and anchor '{}' are not {}", name, key.label()), key)
+            }
+            (Some(key), Some(anchor)) => {
+                let labels = (key.label(), anchor.label());
+                (format!("Key '{}' is not {}, and anchor is not {}", name, labels.0, labels.1), key)
+            }
+        };
+
+        let renamed = style.convert(name);
+        let fits = [key_style, anchor_style].into_iter().flatten().all(|s| s.matches(&renamed));
+        let clashes = members
+            .iter()
+            .any(|member| matches!(member, Member::Pair(pair) if pair.key == renamed));
+        let taken = clashes || self.usages.defined_anchors.contains(&renamed);
+        let edits: Vec<_> = std::iter::once(span.clone())
+            .chain(self.spans_of(&self.usages.anchors, name))
+            .map(|span| TextEdit::new(self.range(span), renamed.clone()))
+            .collect();
+
+        let diagnostic = self.result.add_diagnostic_with_range(
+            DiagnosticCode::InconsistentNaming,
+            message,
+            Some(self.range(span)),
+            vec![],
+            vec![],
+        );
+        if fits && !validated && !taken && is_identifier(&renamed) {
+            let title = format!("Rename '{}' to '{}'", name, renamed);
+            diagnostic.fixes.push(Fix::new(title, edits).affecting_importers());
+        }
+    }
+
+    fn type_members(&mut self, typedef: &TypeDefinition) {
+        let spans = self.index.type_member_spans(typedef);
+        match &typedef.def_type {
+            TypeDef::Struct(def) => {
+                let Some(style) = self.convention.fields else {
+                    return;
+                };
+                for (field, span) in def.fields.iter().zip(&spans) {
+                    let Some(name) = span.name.clone() else {
+                        continue;
+                    };
+                    let renamed = style.convert(&field.name);
+                    let taken = def.fields.iter().any(|other| other.name == renamed);
+                    let usages: Vec<_> = self
+                        .usages
+                        .fields
+                        .iter()
+                        .filter(|(owner, key, _)| *owner == typedef.name && *key == field.name)
+                        .map(|(_, _, span)| span.clone())
+                        .collect();
+                    let what = format!("Field '{}' of '{}'", field.name, typedef.name);
+                    self.report(what, &field.name, name, usages, style, taken);
+                }
+            }
+            TypeDef::Enum(def) => {
+                let Some(style) = self.convention.variants else {
+                    return;
+                };
+                for (variant, span) in def.variants.iter().zip(&spans) {
+                    let Some(name) = span.name.clone() else {
+                        continue;
+                    };
+                    let renamed = style.convert(variant);
+                    let taken = def.variants.contains(&renamed);
+                    let usages: Vec<_> = self
+                        .usages
+                        .variants
+                        .iter()
+                        .filter(|(owner, other, _)| *owner == typedef.name && other == variant)
+                        .map(|(_, _, span)| span.clone())
+                        .collect();
+                    let what = format!("Variant '{}' of '{}'", variant, typedef.name);
+                    self.report(what, variant, name, usages, style, taken);
+                }
+            }
+        }
+    }
+
+    /// Spans of the usages of `name` written without a namespace.
+    fn spans_of(&self, usages: &[(String, ByteRange<usize>)], name: &str) -> Vec<ByteRange<usize>> {
+        usages
+            .iter()
+            .filter(|(used, span)| used == name && self.source.get(span.clone()) == Some(name))
+            .map(|(_, span)| span.clone())
+            .collect()
+    }
+
+    /// Reports `name`, described by `what`, if it is not in `style`, with a
+    /// fix renaming it and its usages unless the new name is `taken`. Other
+    /// files may import the anchors and types reported here, and their
+    /// fields and variants, so the fix is marked as affecting importers.
+    fn report(
+        &mut self,
+        what: String,
+        name: &str,
+        span: ByteRange<usize>,
+        usages: Vec<ByteRange<usize>>,
+        style: NamingStyle,
+        taken: bool,
+    ) {
+        if style.matches(name) {
+            return;
+        }
+        let renamed = style.convert(name);
+        let edits: Vec<_> = std::iter::once(span.clone())
+            .chain(usages)
+            .map(|span| TextEdit::new(self.range(span), renamed.clone()))
+            .collect();
+        let diagnostic = self.result.add_diagnostic_with_range(
+            DiagnosticCode::InconsistentNaming,
+            format!("{} is not {}", what, style.label()),
+            Some(self.range(span)),
+            vec![],
+            vec![],
+        );
+        if !taken && is_identifier(&renamed) {
+            let title = format!("Rename '{}' to '{}'", name, renamed);
+            diagnostic.fixes.push(Fix::new(title, edits).affecting_importers());
+        }
+    }
+
+    /// Reports an object whose keys mix snake_case and camelCase, with a fix
+    /// converting the less common style to the more common one.
+    fn mixed_keys(&mut self, members: &[Member], spans: &[MemberSpan]) {
+        let mut snake_case_keys = Vec::new();
+        let mut camel_case_keys = Vec::new();
+
+        for (i, member) in members.iter().enumerate() {
+            if let Member::Pair(pair) = member {
+                let key = &pair.key;
+                let name = spans.get(i).and_then(|span| span.name.clone());
+                if self.convention.exempt_quoted_keys
+                    && name.is_some_and(|name| self.is_quoted(key, &name))
+                {
+                    continue;
+                }
+                if key.contains('_') {
+                    snake_case_keys.push((i, key.as_str()));
+                } else if key.chars().any(|c| c.is_uppercase()) {
+                    camel_case_keys.push((i, key.as_str()));
+                }
+            }
+        }
+
+        // If we have both styles in same object, warn
+        if snake_case_keys.is_empty() || camel_case_keys.is_empty() {
+            return;
+        }
+        let to_snake_case = snake_case_keys.len() > camel_case_keys.len();
+        let minority = if to_snake_case { &camel_case_keys } else { &snake_case_keys };
+        let range = spans.get(minority[0].0).and_then(|span| span.name.clone());
+
+        let style = if to_snake_case { NamingStyle::Snake } else { NamingStyle::Camel };
+        let edits: Vec<_> = minority
+            .iter()
+            .filter_map(|&(i, key)| {
+                let name = spans.get(i)?.name.clone()?;
+                let renamed = style.convert(key);
+                // Leave quoted keys, `&key` anchors and clashing names alone
+                let plain = self.source.get(name.clone()) == Some(key)
+                    && spans[i].anchor.as_ref() != Some(&name);
+                let clashes = members
+                    .iter()
+                    .any(|member| matches!(member, Member::Pair(pair) if pair.key == renamed));
+                (plain && !clashes).then(|| TextEdit::new(self.range(name), renamed))
+            })
+            .collect();
+
+        let diagnostic = self.result.add_diagnostic_with_range(
+            DiagnosticCode::InconsistentNaming,
+            format!(
+                "Object has mixed naming styles ({} snake_case, {} camelCase)",
+                snake_case_keys.len(),
+                camel_case_keys.len()
+            ),
+            range.map(|span| Range::from_byte_offsets(self.source, span.start, span.end)),
+            vec![],
+            vec![],
+        );
+        // Only a clear majority decides the style
+        if !edits.is_empty() && snake_case_keys.len() != camel_case_keys.len() {
+            diagnostic.fixes.push(Fix::new(
+                format!("Rename {} key(s) to {}", edits.len(), style.label()),
+                edits,
+            ));
+        }
+    }
+}
+
+/// Whether a key path matches a pattern, where `*` stands for any one key
+/// and `**` for any number of keys.
+fn path_matches(pattern: &[&str], path: &[String]) -> bool {
+    match (pattern.split_first(), path.split_first()) {
+        (None, None) => true,
+        (Some((&"**", rest)), _) => {
+            path_matches(rest, path) || (!path.is_empty() && path_matches(pattern, &path[1..]))
+        }
+        (Some((segment, rest)), Some((key, path))) => {
+            (*segment == "*" || segment == key) && path_matches(rest, path)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_converts_between_styles() {
+        let cases = [
+            ("maxRetries", "max_retries", "maxRetries", "max-retries", "MaxRetries"),
+            ("HTTPServer", "http_server", "httpServer", "http-server", "HttpServer"),
+            ("user-agent", "user_agent", "userAgent", "user-agent", "UserAgent"),
+            ("_private_key", "_private_key", "_privateKey", "_private-key", "_PrivateKey"),
+            ("v2_api", "v2_api", "v2Api", "v2-api", "V2Api"),
+        ];
+        for (name, snake, camel, kebab, pascal) in cases {
+            assert_eq!(NamingStyle::Snake.convert(name), snake);
+            assert_eq!(NamingStyle::Camel.convert(name), camel);
+            assert_eq!(NamingStyle::Kebab.convert(name), kebab);
+            assert_eq!(NamingStyle::Pascal.convert(name), pascal);
+        }
+        assert!(NamingStyle::Pascal.matches("User"));
+        assert!(!NamingStyle::Snake.matches("userID"));
+    }
+
+    #[test]
+    fn test_path_patterns() {
+        let path = |keys: &[&str]| keys.iter().map(|key| key.to_string()).collect::<Vec<_>>();
+        assert!(path_matches(&["headers"], &path(&["headers"])));
+        assert!(!path_matches(&["headers"], &path(&["api", "headers"])));
+        assert!(path_matches(&["*", "headers"], &path(&["api", "headers"])));
+        assert!(path_matches(&["**", "headers"], &path(&["headers"])));
+        assert!(path_matches(&["**", "headers"], &path(&["a", "b", "headers"])));
+        assert!(!path_matches(&["**", "headers"], &path(&["headers", "a"])));
+    }
+}
@@ -0,0 +1,480 @@
+//! Go-to-definition and find-references across imported files.
+//!
+//! A document's `SymbolTable` only knows about the names defined and used in
+//! that document. Usages of imported anchors and types (`import { &base }`,
+//! `*ns.default_tags`, `:: ns.User`) are resolved here by following the
+//! document's import statements into the imported files, the same way
+//! mon-core's resolver does.
+
+use crate::linter::api::{AnalysisResult, AnalysisService};
+use crate::linter::symbol_table::SymbolAt;
+use crate::linter::{Position, Range, Symbol, SymbolKind};
+use mon_core::ast::{ImportSpec, ImportStatement, Member, MonValue, MonValueKind, TypeDefinition};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Maximum number of import hops followed when resolving a name.
+const MAX_IMPORT_HOPS: usize = 16;
+
+/// A resolved location of a definition or usage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SymbolLocation {
+    /// File containing the symbol
+    pub path: PathBuf,
+
+    /// Range of the symbol's name in that file
+    pub range: Range,
+}
+
+/// A symbol definition found by following imports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Definition {
+    /// Name of the symbol in the file that defines it
+    pub name: String,
+
+    /// Kind of the symbol
+    pub kind: SymbolKind,
+
+    /// Where the symbol's name is written in its defining file
+    pub location: SymbolLocation,
+}
+
+/// Resolves definitions and references starting from an analyzed document.
+///
+/// Imported files are read from disk and indexed on demand; each file is
+/// indexed at most once per navigator.
+pub struct Navigator<'a> {
+    root: &'a AnalysisResult,
+    root_path: PathBuf,
+    files: HashMap<PathBuf, Option<AnalysisResult>>,
+}
+
+impl<'a> Navigator<'a> {
+    /// Creates a navigator for an analyzed document.
+    pub fn new(root: &'a AnalysisResult) -> Self {
+        Self { root, root_path: normalize(Path::new(&root.file_path)), files: HashMap::new() }
+    }
+
+    /// The document navigation starts from.
+    pub fn root(&self) -> &'a AnalysisResult {
+        self.root
+    }
+
+    /// Canonical path of the root document.
+    pub fn root_path(&self) -> &Path {
+        &self.root_path
+    }
+
+    /// Finds the definition of the symbol at `position` in the root document.
+    pub fn definition_at(&mut self, position: Position) -> Option<Definition> {
+        let at = self.root.find_symbol_at(position)?;
+        if let SymbolAt::Definition(symbol) = at {
+            return Some(Definition {
+                name: symbol.name.clone(),
+                kind: symbol.kind,
+                location: SymbolLocation {
+                    path: self.root_path.clone(),
+                    range: symbol.selection_range,
+                },
+            });
+        }
+
+        let root_path = self.root_path.clone();
+        self.resolve(&root_path, at.name(), at.kind(), 0)
+    }
+
+    /// Finds every usage of the symbol at `position`.
+    ///
+    /// Usages are collected from the file that defines the symbol and from
+    /// the root document. When `include_declaration` is set, the definition
+    /// itself is listed first.
+    pub fn references_at(
+        &mut self,
+        position: Position,
+        include_declaration: bool,
+    ) -> Vec<SymbolLocation> {
+        let Some(definition) = self.definition_at(position) else {
+            return Vec::new();
+        };
+
+        let mut locations = Vec::new();
+        if include_declaration {
+            locations.push(definition.location.clone());
+        }
+
+        let mut files = vec![definition.location.path.clone()];
+        if self.root_path != definition.location.path {
+            files.push(self.root_path.clone());
+        }
+
+        for path in files {
+            locations.extend(self.references_in(&path, &definition));
+        }
+
+        locations
+    }
+
+    /// Finds the usages in `path` that resolve to `definition`.
+    pub fn references_in(&mut self, path: &Path, definition: &Definition) -> Vec<SymbolLocation> {
+        let Some(result) = self.load(path) else {
+            return Vec::new();
+        };
+
+        let mut candidates: Vec<_> = result
+            .symbol_table
+            .all_references()
+            .filter(|reference| reference.symbol_kind == definition.kind)
+            .map(|reference| (reference.symbol_name.clone(), reference.range))
+            .collect();
+        candidates.sort_by_key(|(_, range)| (range.start.line, range.start.character));
+
+        let mut resolved: HashMap<String, bool> = HashMap::new();
+        let mut locations = Vec::new();
+        for (name, range) in candidates {
+            let matches = match resolved.get(&name) {
+                Some(matches) => *matches,
+                None => {
+                    let target = self.resolve(path, &name, definition.kind, 0);
+                    let matches = target.as_ref() == Some(definition);
+                    resolved.insert(name, matches);
+                    matches
+                }
+            };
+            if matches {
+                locations.push(SymbolLocation { path: path.to_path_buf(), range });
+            }
+        }
+
+        locations
+    }
+
+    /// Resolves a name as written in the root document, e.g. `User`,
+    /// `schemas.User` or an imported anchor name.
+    pub fn resolve_name(&mut self, name: &str, kind: SymbolKind) -> Option<Definition> {
+        let root_path = self.root_path.clone();
+        self.resolve(&root_path, name, kind, 0)
+    }
+
+    /// Resolves a name as written in the file at `path`.
+    pub fn resolve_in(&mut self, path: &Path, name: &str, kind: SymbolKind) -> Option<Definition> {
+        self.resolve(path, name, kind, 0)
+    }
+
+    /// Finds the `#struct` or `#enum` definition of a type name as written
+    /// in the root document.
+    pub fn type_definition(&mut self, name: &str) -> Option<TypeDefinition> {
+        let root_path = self.root_path.clone();
+        self.type_definition_in(&root_path, name).map(|(_, typedef)| typedef)
+    }
+
+    /// Finds the `#struct` or `#enum` definition of a type name as written
+    /// in the file at `path`, along with the path of the defining file.
+    pub fn type_definition_in(
+        &mut self,
+        path: &Path,
+        name: &str,
+    ) -> Option<(PathBuf, TypeDefinition)> {
+        let definition = self.resolve_in(path, name, SymbolKind::Type)?;
+        let result = self.load(&definition.location.path)?;
+        let typedef = find_type_definition(&result.document.root, &definition.name)?.clone();
+        Some((definition.location.path, typedef))
+    }
+
+    /// Finds the value an anchor name as written in the file at `path`
+    /// refers to, as written in its defining file (spreads and aliases
+    /// inside it are not expanded).
+    pub fn anchor_value_in(&mut self, path: &Path, name: &str) -> Option<(Definition, MonValue)> {
+        let definition = self.resolve_in(path, name, SymbolKind::Anchor)?;
+        let result = self.load(&definition.location.path)?;
+        let value = find_anchor_value(&result.document.root, &definition.name)?.clone();
+        Some((definition, value))
+    }
+
+    /// Gets the anchors and types a file exports: the anchored pairs and type
+    /// definitions at the top level of its root object.
+    ///
+    /// `import_path` is resolved relative to the root document.
+    pub fn exports(&mut self, import_path: &str) -> Vec<Symbol> {
+        let path = resolve_import_path(import_path, &self.root_path.clone());
+        let Some(result) = self.load(&path) else {
+            return Vec::new();
+        };
+        let MonValueKind::Object(members) = &result.document.root.kind else {
+            return Vec::new();
+        };
+
+        members
+            .iter()
+            .filter_map(|member| match member {
+                Member::Pair(pair) => {
+                    let anchor = pair.value.anchor.as_ref()?;
+                    result.symbol_table.find_symbol(anchor, SymbolKind::Anchor)
+                }
+                Member::TypeDefinition(typedef) => {
+                    result.symbol_table.find_symbol(&typedef.name, SymbolKind::Type)
+                }
+                _ => None,
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Gets the symbol table entry for a resolved definition.
+    pub fn symbol(&mut self, definition: &Definition) -> Option<&Symbol> {
+        self.load(&definition.location.path)?
+            .symbol_table
+            .find_symbol(&definition.name, definition.kind)
+    }
+
+    /// Resolves `name` as seen from the file at `path`.
+    fn resolve(
+        &mut self,
+        path: &Path,
+        name: &str,
+        kind: SymbolKind,
+        hops: usize,
+    ) -> Option<Definition> {
+        if hops > MAX_IMPORT_HOPS {
+            return None;
+        }
+
+        let result = self.load(path)?;
+        if let Some(symbol) = result.symbol_table.find_symbol(name, kind) {
+            return Some(Definition {
+                name: symbol.name.clone(),
+                kind,
+                location: SymbolLocation {
+                    path: path.to_path_buf(),
+                    range: symbol.selection_range,
+                },
+            });
+        }
+
+        let imports = result.document.imports.clone();
+
+        // `import { &name, Name } from "..."`
+        let named = imports.iter().find(|import| match &import.spec {
+            ImportSpec::Named(specifiers) => specifiers
+                .iter()
+                .any(|s| s.name == name && s.is_anchor == (kind == SymbolKind::Anchor)),
+            ImportSpec::Namespace(_) => false,
+        });
+        if let Some(import) = named {
+            let imported = resolve_import_path(&import.path, path);
+            return self.resolve(&imported, name, kind, hops + 1);
+        }
+
+        // `import * as ns from "..."` used as `ns.name`
+        let (namespace, rest) = name.split_once('.')?;
+        let import = imports.iter().find(|import| is_namespace(import, namespace))?;
+        let imported = resolve_import_path(&import.path, path);
+        self.resolve(&imported, rest, kind, hops + 1)
+    }
+
+    /// Gets the analysis of a file, indexing it from disk on first use.
+    fn load(&mut self, path: &Path) -> Option<&AnalysisResult> {
+        if path == self.root_path {
+            return Some(self.root);
+        }
+
+        self.files
+            .entry(path.to_path_buf())
+            .or_insert_with(|| {
+                let source = fs::read_to_string(path).ok()?;
+                AnalysisService::index_document(&source, &path.display().to_string()).ok()
+            })
+            .as_ref()
+    }
+}
+
+/// Finds a type definition by name anywhere in a value.
+pub fn find_type_definition<'v>(value: &'v MonValue, name: &str) -> Option<&'v TypeDefinition> {
+    match &value.kind {
+        MonValueKind::Object(members) => members.iter().find_map(|member| match member {
+            Member::TypeDefinition(typedef) if typedef.name == name => Some(typedef),
+            Member::Pair(pair) => find_type_definition(&pair.value, name),
+            _ => None,
+        }),
+        MonValueKind::Array(items) => {
+            items.iter().find_map(|item| find_type_definition(item, name))
+        }
+        _ => None,
+    }
+}
+
+/// Gets every type definition in a value, in source order.
+pub fn type_definitions(value: &MonValue) -> Vec<&TypeDefinition> {
+    let mut found = Vec::new();
+    collect_type_definitions(value, &mut found);
+    found
+}
+
+fn collect_type_definitions<'v>(value: &'v MonValue, found: &mut Vec<&'v TypeDefinition>) {
+    match &value.kind {
+        MonValueKind::Object(members) => {
+            for member in members {
+                match member {
+                    Member::TypeDefinition(typedef) => found.push(typedef),
+                    Member::Pair(pair) => collect_type_definitions(&pair.value, found),
+                    _ => {}
+                }
+            }
+        }
+        MonValueKind::Array(items) => {
+            for item in items {
+                collect_type_definitions(item, found);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Finds the value carrying anchor `name` anywhere in a value.
+pub fn find_anchor_value<'v>(value: &'v MonValue, name: &str) -> Option<&'v MonValue> {
+    if value.anchor.as_deref() == Some(name) {
+        return Some(value);
+    }
+    match &value.kind {
+        MonValueKind::Object(members) => members.iter().find_map(|member| match member {
+            Member::Pair(pair) => find_anchor_value(&pair.value, name),
+            _ => None,
+        }),
+        MonValueKind::Array(items) => items.iter().find_map(|item| find_anchor_value(item, name)),
+        _ => None,
+    }
+}
+
+fn is_namespace(import: &ImportStatement, namespace: &str) -> bool {
+    matches!(&import.spec, ImportSpec::Namespace(ns) if ns == namespace)
+}
+
+/// Resolves an import path the way mon-core's resolver does.
+///
+/// `mon:` paths point into the built-in schema directory; everything else is
+/// relative to the directory of the importing file.
+pub fn resolve_import_path(import_path: &str, importing_file: &Path) -> PathBuf {
+    if let Some(builtin) = import_path.strip_prefix("mon:") {
+        return normalize(&builtin_schemas_path().join(builtin).with_extension("mon"));
+    }
+
+    let dir = importing_file.parent().unwrap_or_else(|| Path::new("."));
+    normalize(&dir.join(import_path))
+}
+
+/// Location of the built-in schemas, looked up in the same order as mon-core.
+fn builtin_schemas_path() -> PathBuf {
+    if let Ok(path) = std::env::var("MON_BUILTIN_PATH") {
+        return PathBuf::from(path);
+    }
+
+    if let Some(home) = std::env::var_os("HOME") {
+        let user_schemas = PathBuf::from(home).join(".mon/schemas");
+        if user_schemas.exists() {
+            return user_schemas;
+        }
+    }
+
+    #[cfg(unix)]
+    {
+        let system_path = PathBuf::from("/usr/share/mon/schemas");
+        if system_path.exists() {
+            return system_path;
+        }
+    }
+
+    PathBuf::from(".")
+}
+
+/// Makes a path comparable: canonical if the file exists, as given otherwise.
+fn normalize(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintConfig;
+    use tempfile::TempDir;
+
+    fn write(dir: &TempDir, name: &str, source: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        fs::write(&path, source).unwrap();
+        fs::canonicalize(path).unwrap()
+    }
+
+    fn analyze(path: &Path) -> AnalysisResult {
+        let source = fs::read_to_string(path).unwrap();
+        AnalysisService::new(LintConfig::default())
+            .analyze_document(&source, &path.display().to_string())
+            .unwrap()
+    }
+
+    fn position_of(source: &str, needle: &str) -> Position {
+        let offset = source.find(needle).unwrap();
+        Position::from_byte_offset(source, offset)
+    }
+
+    #[test]
+    fn test_local_definition_and_references() {
+        let dir = TempDir::new().unwrap();
+        let source = "{\n    &base: { a: 1 },\n    copy: *base,\n    more: { ...*base },\n}";
+        let path = write(&dir, "local.mon", source);
+        let result = analyze(&path);
+        let mut navigator = Navigator::new(&result);
+
+        let definition = navigator.definition_at(position_of(source, "base,")).unwrap();
+        assert_eq!(definition.name, "base");
+        assert_eq!(definition.location.range.start, position_of(source, "base:"));
+
+        let references = navigator.references_at(position_of(source, "base:"), true);
+        assert_eq!(references.len(), 3);
+        assert_eq!(references[1].range.start, position_of(source, "base,"));
+        assert_eq!(references[2].range.start, position_of(source, "base }"));
+    }
+
+    #[test]
+    fn test_definition_follows_imports() {
+        let dir = TempDir::new().unwrap();
+        let base_source =
+            "{\n    &base_settings: { debug: false },\n    &default_tags: [\"a\"],\n}";
+        let base = write(&dir, "base.mon", base_source);
+        let source = "import { &base_settings } from \"./base.mon\"\nimport * as base from \"./base.mon\"\n{\n    settings: *base_settings,\n    tags: *base.default_tags,\n}";
+        let path = write(&dir, "main.mon", source);
+        let result = analyze(&path);
+        let mut navigator = Navigator::new(&result);
+
+        let named = navigator.definition_at(position_of(source, "base_settings,")).unwrap();
+        assert_eq!(named.location.path, base);
+        assert_eq!(named.location.range.start, position_of(base_source, "base_settings:"));
+
+        let from_import = navigator.definition_at(position_of(source, "base_settings }")).unwrap();
+        assert_eq!(from_import, named);
+
+        let namespaced = navigator.definition_at(position_of(source, "base.default_tags")).unwrap();
+        assert_eq!(namespaced.name, "default_tags");
+        assert_eq!(namespaced.location.range.start, position_of(base_source, "default_tags"));
+
+        // References to an imported anchor include the usage in the importing file
+        let references = navigator.references_at(position_of(source, "base_settings,"), false);
+        assert_eq!(references.len(), 2);
+        assert!(references.iter().all(|r| r.path == path));
+    }
+
+    #[test]
+    fn test_type_references() {
+        let dir = TempDir::new().unwrap();
+        let source = "{\n    Status: #enum { Active, Inactive },\n    User: #struct { status(Status) },\n    me :: User = { status: $Status.Active },\n}";
+        let path = write(&dir, "types.mon", source);
+        let result = analyze(&path);
+        let mut navigator = Navigator::new(&result);
+
+        let definition = navigator.definition_at(position_of(source, "User =")).unwrap();
+        assert_eq!(definition.kind, SymbolKind::Type);
+        assert_eq!(definition.location.range.start, position_of(source, "User:"));
+
+        let references = navigator.references_at(position_of(source, "Status:"), false);
+        assert_eq!(references.len(), 2);
+    }
+}
@@ -0,0 +1,328 @@
+//! Hierarchical outline of a MON document.
+//!
+//! The outline mirrors the document's structure: imports, then every key of
+//! the root object with nested objects and arrays as children, anchors, and
+//! `#struct`/`#enum` definitions with their fields and variants. It feeds the
+//! editor outline view and `mon symbols`.
+
+use crate::linter::api::AnalysisResult;
+use crate::linter::source_index::SourceIndex;
+use crate::linter::{Range, render};
+use mon_core::ast::{
+    ImportSpec, ImportStatement, Member, MonValue, MonValueKind, TypeDef, TypeDefinition,
+};
+use serde::Serialize;
+
+/// What an outline entry stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum OutlineKind {
+    /// An `import` statement
+    Import,
+
+    /// A key whose value is an object
+    Object,
+
+    /// A key whose value is an array
+    Array,
+
+    /// A key with a scalar, alias or enum value
+    Key,
+
+    /// A key or array item carrying an anchor (`&name`)
+    Anchor,
+
+    /// A `#struct` definition
+    Struct,
+
+    /// A field of a `#struct`
+    Field,
+
+    /// An `#enum` definition
+    Enum,
+
+    /// A variant of an `#enum`
+    EnumVariant,
+}
+
+impl OutlineKind {
+    /// Lowercase label used in text output.
+    pub fn label(&self) -> &'static str {
+        match self {
+            OutlineKind::Import => "import",
+            OutlineKind::Object => "object",
+            OutlineKind::Array => "array",
+            OutlineKind::Key => "key",
+            OutlineKind::Anchor => "anchor",
+            OutlineKind::Struct => "struct",
+            OutlineKind::Field => "field",
+            OutlineKind::Enum => "enum",
+            OutlineKind::EnumVariant => "variant",
+        }
+    }
+}
+
+/// One entry of the outline, with its nested entries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OutlineNode {
+    /// Key, type, field or variant name; `[i]` for array items
+    pub name: String,
+
+    /// What the entry stands for
+    pub kind: OutlineKind,
+
+    /// Short extra text: the anchor, type annotation, field type or scalar value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+
+    /// Range of the whole entry, including its value
+    pub range: Range,
+
+    /// Range of the entry's name
+    pub selection_range: Range,
+
+    /// Nested entries
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<OutlineNode>,
+}
+
+/// Builds the outline of an analyzed document.
+pub fn document_outline(result: &AnalysisResult) -> Vec<OutlineNode> {
+    let builder =
+        OutlineBuilder { source: &result.source, index: SourceIndex::new(&result.source) };
+
+    let mut nodes: Vec<_> =
+        result.document.imports.iter().map(|import| builder.import(import)).collect();
+    nodes.extend(builder.members(&result.document.root));
+    nodes
+}
+
+struct OutlineBuilder<'a> {
+    source: &'a str,
+    index: SourceIndex,
+}
+
+impl OutlineBuilder<'_> {
+    fn import(&self, import: &ImportStatement) -> OutlineNode {
+        let detail = match &import.spec {
+            ImportSpec::Namespace(namespace) => format!("* as {}", namespace),
+            ImportSpec::Named(specifiers) => {
+                let names: Vec<_> = specifiers
+                    .iter()
+                    .map(|s| if s.is_anchor { format!("&{}", s.name) } else { s.name.clone() })
+                    .collect();
+                format!("{{ {} }}", names.join(", "))
+            }
+        };
+        let range = self.range(import.pos_start, import.pos_end);
+
+        OutlineNode {
+            name: import.path.clone(),
+            kind: OutlineKind::Import,
+            detail: Some(detail),
+            range,
+            selection_range: range,
+            children: Vec::new(),
+        }
+    }
+
+    /// Entries for the members of an object (nothing for other values).
+    fn members(&self, object: &MonValue) -> Vec<OutlineNode> {
+        let MonValueKind::Object(members) = &object.kind else {
+            return Vec::new();
+        };
+        let spans = self.index.member_spans(object);
+
+        members
+            .iter()
+            .zip(spans)
+            .filter_map(|(member, span)| {
+                let range = self.range(span.span.start, span.span.end);
+                let selection = span.name.map_or(range, |name| self.range(name.start, name.end));
+
+                match member {
+                    Member::Pair(pair) => {
+                        let mut detail = Vec::new();
+                        if let Some(anchor) = &pair.value.anchor {
+                            detail.push(format!("&{}", anchor));
+                        }
+                        if let Some(type_spec) = &pair.validation {
+                            detail.push(format!(":: {}", type_spec));
+                        }
+                        if is_scalar(&pair.value) {
+                            detail.push(render::value(&pair.value));
+                        }
+
+                        Some(OutlineNode {
+                            name: pair.key.clone(),
+                            kind: value_kind(&pair.value),
+                            detail: (!detail.is_empty()).then(|| detail.join(" ")),
+                            range,
+                            selection_range: selection,
+                            children: self.children(&pair.value),
+                        })
+                    }
+                    Member::TypeDefinition(typedef) => Some(self.type_definition(typedef, range)),
+                    Member::Spread(_) | Member::Import(_) => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Entry for a `#struct` or `#enum`, with its fields or variants.
+    fn type_definition(&self, typedef: &TypeDefinition, range: Range) -> OutlineNode {
+        let (kind, detail, members): (_, _, Vec<_>) = match &typedef.def_type {
+            TypeDef::Struct(def) => (
+                OutlineKind::Struct,
+                "#struct",
+                def.fields
+                    .iter()
+                    .map(|f| (f.name.clone(), OutlineKind::Field, Some(f.type_spec.to_string())))
+                    .collect(),
+            ),
+            TypeDef::Enum(def) => (
+                OutlineKind::Enum,
+                "#enum",
+                def.variants.iter().map(|v| (v.clone(), OutlineKind::EnumVariant, None)).collect(),
+            ),
+        };
+
+        let children = members
+            .into_iter()
+            .zip(self.index.type_member_spans(typedef))
+            .map(|((name, kind, detail), span)| {
+                let range = self.range(span.span.start, span.span.end);
+                let selection = span.name.map_or(range, |name| self.range(name.start, name.end));
+                OutlineNode {
+                    name,
+                    kind,
+                    detail,
+                    range,
+                    selection_range: selection,
+                    children: Vec::new(),
+                }
+            })
+            .collect();
+
+        let name_start = typedef.name_span.offset();
+        OutlineNode {
+            name: typedef.name.clone(),
+            kind,
+            detail: Some(detail.to_string()),
+            range,
+            selection_range: self.range(name_start, name_start + typedef.name_span.len()),
+            children,
+        }
+    }
+
+    /// Nested entries of a value: the members of an object, or the object,
+    /// array and anchored items of an array.
+    fn children(&self, value: &MonValue) -> Vec<OutlineNode> {
+        match &value.kind {
+            MonValueKind::Object(_) => self.members(value),
+            MonValueKind::Array(items) => items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| item.anchor.is_some() || !is_scalar(item))
+                .map(|(i, item)| {
+                    let selection = self.range(item.pos_start, item.pos_start);
+                    let name_span = self.index.anchor_before(item);
+                    let start = name_span.map_or(item.pos_start, |span| span.start - 1);
+                    OutlineNode {
+                        name: format!("[{}]", i),
+                        kind: value_kind(item),
+                        detail: item.anchor.as_ref().map(|anchor| format!("&{}", anchor)),
+                        range: self.range(start, item.pos_end),
+                        selection_range: selection,
+                        children: self.children(item),
+                    }
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn range(&self, start: usize, end: usize) -> Range {
+        Range::from_byte_offsets(self.source, start, end)
+    }
+}
+
+fn value_kind(value: &MonValue) -> OutlineKind {
+    match &value.kind {
+        _ if value.anchor.is_some() => OutlineKind::Anchor,
+        MonValueKind::Object(_) => OutlineKind::Object,
+        MonValueKind::Array(_) => OutlineKind::Array,
+        _ => OutlineKind::Key,
+    }
+}
+
+fn is_scalar(value: &MonValue) -> bool {
+    !matches!(value.kind, MonValueKind::Object(_) | MonValueKind::Array(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::api::AnalysisService;
+    use crate::linter::{LintConfig, Position};
+
+    fn outline(source: &str) -> Vec<OutlineNode> {
+        let result = AnalysisService::new(LintConfig::default())
+            .analyze_document(source, "/tmp/outline.mon")
+            .unwrap();
+        document_outline(&result)
+    }
+
+    fn names(nodes: &[OutlineNode]) -> Vec<(&str, OutlineKind)> {
+        nodes.iter().map(|node| (node.name.as_str(), node.kind)).collect()
+    }
+
+    #[test]
+    fn test_outline_hierarchy() {
+        let source = r#"import * as schemas from "./schemas.mon"
+{
+    &base: { retries: 3 },
+    Status: #enum { On, Off },
+    User: #struct { id(Number), name(String) = "guest" },
+    app: {
+        name: "demo",
+        owner :: User = { id: 1 },
+        workers: [{ id: 1 }, 2],
+    },
+}"#;
+        let nodes = outline(source);
+        assert_eq!(
+            names(&nodes),
+            vec![
+                ("./schemas.mon", OutlineKind::Import),
+                ("base", OutlineKind::Anchor),
+                ("Status", OutlineKind::Enum),
+                ("User", OutlineKind::Struct),
+                ("app", OutlineKind::Object),
+            ]
+        );
+        assert_eq!(nodes[0].detail.as_deref(), Some("* as schemas"));
+        assert_eq!(nodes[1].detail.as_deref(), Some("&base"));
+        assert_eq!(names(&nodes[1].children), vec![("retries", OutlineKind::Key)]);
+        assert_eq!(
+            names(&nodes[2].children),
+            vec![("On", OutlineKind::EnumVariant), ("Off", OutlineKind::EnumVariant)]
+        );
+        assert_eq!(nodes[3].children[1].detail.as_deref(), Some("String"));
+        assert_eq!(nodes[3].selection_range.start, Position::new(4, 4));
+
+        let app = &nodes[4];
+        assert_eq!(
+            names(&app.children),
+            vec![
+                ("name", OutlineKind::Key),
+                ("owner", OutlineKind::Object),
+                ("workers", OutlineKind::Array)
+            ]
+        );
+        assert_eq!(app.children[0].detail.as_deref(), Some("\"demo\""));
+        assert_eq!(app.children[1].detail.as_deref(), Some(":: User"));
+        assert_eq!(names(&app.children[2].children), vec![("[0]", OutlineKind::Object)]);
+        assert_eq!(app.range.start, Position::new(5, 4));
+        assert_eq!(app.range.end, Position::new(9, 5));
+    }
+}
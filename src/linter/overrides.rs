@@ -0,0 +1,262 @@
+// Overridden key detection for MON files
+
+use crate::linter::import_graph::ImportGraph;
+use crate::linter::navigation::find_anchor_value;
+use crate::linter::position::Location;
+use crate::linter::source_index::SourceIndex;
+use crate::linter::{
+    LintConfig, LintResult, Range, RelatedInformation, diagnostic::DiagnosticCode,
+};
+use mon_core::ast::{Member, MonDocument, MonValue, MonValueKind};
+use std::collections::HashMap;
+use std::ops::Range as ByteRange;
+use std::path::{Path, PathBuf};
+
+pub struct OverrideAnalyzer {
+    config: LintConfig,
+}
+
+/// A key an object gets from a spread.
+struct SpreadKey {
+    name: String,
+    /// Where the key is written, in the document or a file it imports
+    location: Option<Location>,
+}
+
+/// The object a spread refers to.
+struct SpreadObject {
+    /// File it is written in, the document if `None`
+    file: Option<PathBuf>,
+    members: Vec<Member>,
+    /// Where the key of each member is written
+    keys: Vec<Option<Location>>,
+}
+
+/// The member of an object that last set a key.
+enum Setter {
+    /// Member `i`, written as `key: value`
+    Key(usize),
+    /// Member `i`, `...*anchor`, with where the key is written
+    Spread { member: usize, anchor: String, location: Option<Location> },
+}
+
+impl OverrideAnalyzer {
+    pub fn new(config: LintConfig) -> Self {
+        Self { config }
+    }
+
+    /// Reports keys that a spread sets again, or that are set again after a
+    /// spread. Spreads of imported anchors are only resolved when the
+    /// document's `path` is known.
+    pub fn analyze(
+        &self,
+        doc: &MonDocument,
+        source: &str,
+        path: Option<&Path>,
+        result: &mut LintResult,
+    ) {
+        if !self.config.is_enabled(DiagnosticCode::OverriddenKey) {
+            return;
+        }
+
+        let index = SourceIndex::new(source);
+        let graph = path
+            .filter(|_| !doc.imports.is_empty())
+            .map(|path| ImportGraph::new(path, doc, source));
+        let mut checker = Checker {
+            source,
+            index: &index,
+            root: &doc.root,
+            graph,
+            allow_explicit_overrides: self.config.allow_explicit_overrides,
+        };
+        checker.value(&doc.root, result);
+    }
+}
+
+struct Checker<'a> {
+    source: &'a str,
+    index: &'a SourceIndex,
+    root: &'a MonValue,
+    /// The files the document imports, when its path is known
+    graph: Option<ImportGraph>,
+    allow_explicit_overrides: bool,
+}
+
+impl Checker<'_> {
+    fn range(&self, span: ByteRange<usize>) -> Range {
+        Range::from_byte_offsets(self.source, span.start, span.end)
+    }
+
+    fn value(&mut self, value: &MonValue, result: &mut LintResult) {
+        match &value.kind {
+            MonValueKind::Object(members) => {
+                self.object(value, members, result);
+                for member in members {
+                    if let Member::Pair(pair) = member {
+                        self.value(&pair.value, result);
+                    }
+                }
+            }
+            MonValueKind::Array(items) => {
+                for item in items {
+                    self.value(item, result);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Follows the members of an object in order, as the resolver merges
+    /// them: a later member setting a key replaces the earlier one. Keys set
+    /// twice without a spread are left to LINT2002.
+    fn object(&mut self, value: &MonValue, members: &[Member], result: &mut LintResult) {
+        let spans = self.index.member_spans(value);
+        let mut setters: HashMap<String, Setter> = HashMap::new();
+
+        for (i, member) in members.iter().enumerate() {
+            let Some(span) = spans.get(i) else {
+                continue;
+            };
+            match member {
+                Member::Pair(pair) => {
+                    let earlier = setters.insert(pair.key.clone(), Setter::Key(i));
+                    if let Some(Setter::Spread { member, anchor, location }) = earlier
+                        && !self.allow_explicit_overrides
+                    {
+                        let mut related = vec![RelatedInformation::here(
+                            self.range(spans[member].span.clone()),
+                            format!("'...*{}' is spread here", anchor),
+                        )];
+                        related.extend(defined_here(&pair.key, &anchor, location));
+                        result.add_diagnostic_with_range(
+                            DiagnosticCode::OverriddenKey,
+                            format!("'{}' overrides the value spread from '*{}'", pair.key, anchor),
+                            span.name.clone().map(|name| self.range(name)),
+                            related,
+                            vec![],
+                        );
+                    }
+                }
+                Member::Spread(anchor) => {
+                    for key in self.keys(None, anchor, &mut Vec::new()) {
+                        let setter = Setter::Spread {
+                            member: i,
+                            anchor: anchor.clone(),
+                            location: key.location.clone(),
+                        };
+                        let (message, mut related) = match setters.insert(key.name.clone(), setter)
+                        {
+                            None => continue,
+                            Some(Setter::Key(j)) => {
+                                let message = format!(
+                                    "Spread of '*{}' overrides '{}' set before it",
+                                    anchor, key.name
+                                );
+                                let related = spans[j].name.clone().map(|name| {
+                                    RelatedInformation::here(
+                                        self.range(name),
+                                        format!("'{}' is set here", key.name),
+                                    )
+                                });
+                                (message, related.into_iter().collect::<Vec<_>>())
+                            }
+                            Some(Setter::Spread { member, anchor: earlier, location }) => {
+                                let message = format!(
+                                    "Spread of '*{}' overrides '{}' from '*{}'",
+                                    anchor, key.name, earlier
+                                );
+                                let mut related = vec![RelatedInformation::here(
+                                    self.range(spans[member].span.clone()),
+                                    format!("'...*{}' is spread here", earlier),
+                                )];
+                                related.extend(defined_here(&key.name, &earlier, location));
+                                (message, related)
+                            }
+                        };
+                        related.extend(defined_here(&key.name, anchor, key.location));
+                        result.add_diagnostic_with_range(
+                            DiagnosticCode::OverriddenKey,
+                            message,
+                            Some(self.range(span.span.clone())),
+                            related,
+                            vec![],
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The keys `...*name` adds to an object, its own spreads included.
+    /// `file` is the file the spread is written in, the document if `None`.
+    fn keys(
+        &mut self,
+        file: Option<&Path>,
+        name: &str,
+        seen: &mut Vec<(Option<PathBuf>, String)>,
+    ) -> Vec<SpreadKey> {
+        let file = file.map(Path::to_path_buf).or_else(|| Some(self.graph.as_ref()?.root().into()));
+        // Anchors that spread each other are an error of the resolver
+        let visit = (file.clone(), name.to_string());
+        if seen.contains(&visit) {
+            return Vec::new();
+        }
+        let Some(object) = self.anchored(file.as_deref(), name) else {
+            return Vec::new();
+        };
+
+        seen.push(visit);
+        let mut keys: Vec<SpreadKey> = Vec::new();
+        for (member, location) in object.members.iter().zip(object.keys) {
+            let added = match member {
+                Member::Pair(pair) => vec![SpreadKey { name: pair.key.clone(), location }],
+                Member::Spread(inner) => self.keys(object.file.as_deref(), inner, seen),
+                _ => continue,
+            };
+            for key in added {
+                keys.retain(|earlier| earlier.name != key.name);
+                keys.push(key);
+            }
+        }
+        seen.pop();
+        keys
+    }
+
+    /// The object anchored as `name`, as seen from `file`.
+    fn anchored(&mut self, file: Option<&Path>, name: &str) -> Option<SpreadObject> {
+        match (&mut self.graph, file) {
+            (Some(graph), Some(file)) => {
+                let object = graph.anchored_object(file, name)?;
+                let keys = object
+                    .names
+                    .iter()
+                    .map(|span| graph.location(&object.file, span.clone()?))
+                    .collect();
+                Some(SpreadObject { file: Some(object.file), members: object.members, keys })
+            }
+            _ => {
+                let value = find_anchor_value(self.root, name)?;
+                let MonValueKind::Object(members) = &value.kind else {
+                    return None;
+                };
+                let keys = self
+                    .index
+                    .member_spans(value)
+                    .into_iter()
+                    .map(|span| Some(Location::new(String::new(), self.range(span.name?))))
+                    .collect();
+                Some(SpreadObject { file: None, members: members.clone(), keys })
+            }
+        }
+    }
+}
+
+/// Related information pointing at where a spread key is written.
+fn defined_here(key: &str, anchor: &str, location: Option<Location>) -> Option<RelatedInformation> {
+    Some(RelatedInformation {
+        location: location?,
+        message: format!("'{}' of '*{}' is defined here", key, anchor),
+    })
+}
@@ -67,6 +67,30 @@ impl Position {
 
         Position { line, character }
     }
+
+    /// Converts this position back to a byte offset in source text.
+    ///
+    /// Positions past the end of a line clamp to the end of that line, and
+    /// positions past the last line clamp to the end of the source.
+    pub fn to_byte_offset(self, source: &str) -> usize {
+        let mut line = 0;
+        let mut character = 0;
+
+        for (idx, ch) in source.char_indices() {
+            if line == self.line && (character >= self.character || ch == '\n') {
+                return idx;
+            }
+
+            if ch == '\n' {
+                line += 1;
+                character = 0;
+            } else {
+                character += ch.len_utf16() as u32;
+            }
+        }
+
+        source.len()
+    }
 }
 
 /// A range in a text document, defined by start and end positions.
@@ -141,10 +165,17 @@ pub struct RelatedInformation {
     pub message: String,
 }
 
+impl RelatedInformation {
+    /// Related information at `range` in the document being linted.
+    pub fn here(range: Range, message: impl Into<String>) -> Self {
+        Self { location: Location::new(String::new(), range), message: message.into() }
+    }
+}
+
 /// A location in a source file, combining file path and range.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Location {
-    /// File URI (typically file:// path)
+    /// File URI (typically file:// path); empty for the document being linted
     pub uri: String,
 
     /// The range within the file
@@ -190,6 +221,20 @@ mod tests {
         assert_eq!(Position::from_byte_offset(source, 12), Position { line: 2, character: 0 });
     }
 
+    #[test]
+    fn test_position_to_byte_offset() {
+        let source = "hello\nwörld\nfoo";
+
+        for offset in [0, 2, 6, 9, 14] {
+            let position = Position::from_byte_offset(source, offset);
+            assert_eq!(position.to_byte_offset(source), offset);
+        }
+
+        // Clamped to the end of the line and of the source
+        assert_eq!(Position::new(0, 40).to_byte_offset(source), 5);
+        assert_eq!(Position::new(9, 0).to_byte_offset(source), source.len());
+    }
+
     #[test]
     fn test_range_contains() {
         let range = Range {
@@ -0,0 +1,230 @@
+//! Renaming anchors and types across files.
+//!
+//! A rename edits the definition's name and every usage that resolves to it:
+//! aliases and spreads (`*base`, `...*base`), type annotations and enum
+//! values, names listed in `import { ... }` in other files, and the `name`
+//! part of namespaced usages such as `*ns.base` or `:: ns.User`.
+//!
+//! Usages in other files are found by checking every `.mon` file in the
+//! workspace, since any of them may import the defining file.
+
+use crate::linter::edit::{TextEdit, WorkspaceEdit};
+use crate::linter::navigation::{Definition, Navigator};
+use crate::linter::{Position, Range, SymbolKind};
+use miette::Result;
+use std::path::{Path, PathBuf};
+
+/// Renames the anchor or type at `position` in the navigator's root document.
+pub fn rename_at(
+    navigator: &mut Navigator<'_>,
+    position: Position,
+    new_name: &str,
+    workspace: &[PathBuf],
+) -> Result<WorkspaceEdit> {
+    let definition = navigator
+        .definition_at(position)
+        .ok_or_else(|| miette::miette!("There is no anchor or type to rename here"))?;
+    rename(navigator, &definition, new_name, workspace)
+}
+
+/// Builds the edits that rename `definition` to `new_name` everywhere it is
+/// used in the root document and the `workspace` files.
+///
+/// Fails if `new_name` is not a valid name, if the definition lives outside
+/// the workspace (e.g. a `mon:` built-in schema), or if `new_name` already
+/// refers to another anchor or type in any file the rename touches.
+pub fn rename(
+    navigator: &mut Navigator<'_>,
+    definition: &Definition,
+    new_name: &str,
+    workspace: &[PathBuf],
+) -> Result<WorkspaceEdit> {
+    if !matches!(definition.kind, SymbolKind::Anchor | SymbolKind::Type) {
+        return Err(miette::miette!("Only anchors and types can be renamed"));
+    }
+
+    let kind = kind_label(definition.kind);
+    if !is_valid_name(new_name) {
+        return Err(miette::miette!("'{}' is not a valid {} name", new_name, kind));
+    }
+
+    let mut edit = WorkspaceEdit::default();
+    if new_name == definition.name {
+        return Ok(edit);
+    }
+
+    let defined_in = &definition.location.path;
+    if defined_in != navigator.root_path() && !workspace.contains(defined_in) {
+        return Err(miette::miette!(
+            "Cannot rename {} '{}': it is defined in {}, outside the workspace",
+            kind,
+            definition.name,
+            defined_in.display()
+        ));
+    }
+
+    let mut files = vec![defined_in.clone(), navigator.root_path().to_path_buf()];
+    files.extend(workspace.iter().cloned());
+    files.dedup();
+
+    let mut checked = Vec::new();
+    check_collision(navigator, defined_in, definition, new_name)?;
+    checked.push(defined_in.clone());
+    edit.insert(defined_in.clone(), TextEdit::new(definition.location.range, new_name));
+
+    for path in files {
+        for reference in navigator.references_in(&path, definition) {
+            let range = name_range(reference.range, &definition.name);
+
+            // `ns.name` cannot clash with names local to this file
+            let qualified = range != reference.range;
+            if !qualified && !checked.contains(&path) {
+                check_collision(navigator, &path, definition, new_name)?;
+                checked.push(path.clone());
+            }
+
+            edit.insert(path.clone(), TextEdit::new(range, new_name));
+        }
+    }
+
+    Ok(edit)
+}
+
+/// Fails if `new_name` already resolves to an anchor or type of the same
+/// kind in the file at `path`.
+fn check_collision(
+    navigator: &mut Navigator<'_>,
+    path: &Path,
+    definition: &Definition,
+    new_name: &str,
+) -> Result<()> {
+    match navigator.resolve_in(path, new_name, definition.kind) {
+        Some(existing) => Err(miette::miette!(
+            "Cannot rename {} '{}' to '{}': {} '{}' already exists at {}:{}:{}",
+            kind_label(definition.kind),
+            definition.name,
+            new_name,
+            kind_label(definition.kind),
+            new_name,
+            existing.location.path.display(),
+            existing.location.range.start.line + 1,
+            existing.location.range.start.character + 1
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Narrows a usage range to the trailing `name` (drops an `ns.` qualifier).
+fn name_range(range: Range, name: &str) -> Range {
+    let width = name.encode_utf16().count() as u32;
+    if range.start.line != range.end.line || range.end.character < range.start.character + width {
+        return range;
+    }
+    Range::new(Position::new(range.end.line, range.end.character - width), range.end)
+}
+
+/// Whether `name` can be written as a bare anchor or type name.
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn kind_label(kind: SymbolKind) -> &'static str {
+    match kind {
+        SymbolKind::Type => "type",
+        _ => "anchor",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintConfig;
+    use crate::linter::api::{AnalysisResult, AnalysisService};
+    use crate::linter::edit::apply;
+    use crate::linter::workspace::mon_files;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const SCHEMAS: &str = "{\n    &base: { retries: 3 },\n    User: #struct { id(Number) },\n}";
+    const NAMED: &str = "import { &base, User } from \"./schemas.mon\"\n{\n    a: *base,\n    u :: User = { id: 1 },\n}";
+    const NAMESPACED: &str = "import * as s from \"./schemas.mon\"\n{\n    b: { ...*s.base },\n}";
+
+    fn project() -> (TempDir, PathBuf, Vec<PathBuf>) {
+        let dir = TempDir::new().unwrap();
+        for (name, source) in
+            [("schemas.mon", SCHEMAS), ("named.mon", NAMED), ("ns.mon", NAMESPACED)]
+        {
+            fs::write(dir.path().join(name), source).unwrap();
+        }
+        let root = fs::canonicalize(dir.path()).unwrap();
+        let files = mon_files(&root);
+        (dir, root, files)
+    }
+
+    fn analyze(path: &Path) -> AnalysisResult {
+        let source = fs::read_to_string(path).unwrap();
+        AnalysisService::new(LintConfig::default())
+            .analyze_document(&source, &path.display().to_string())
+            .unwrap()
+    }
+
+    fn renamed(edit: &WorkspaceEdit, path: &Path) -> String {
+        apply(&fs::read_to_string(path).unwrap(), &edit.changes[path])
+    }
+
+    #[test]
+    fn test_rename_anchor_across_files() {
+        let (_dir, root, files) = project();
+        let analysis = analyze(&root.join("named.mon"));
+        let mut navigator = Navigator::new(&analysis);
+
+        // From a usage in an importing file
+        let edit = rename_at(&mut navigator, Position::new(2, 8), "defaults", &files).unwrap();
+        assert_eq!(edit.edit_count(), 4);
+        assert_eq!(
+            renamed(&edit, &root.join("schemas.mon")),
+            SCHEMAS.replace("&base", "&defaults")
+        );
+        assert_eq!(
+            renamed(&edit, &root.join("named.mon")),
+            NAMED.replace("&base", "&defaults").replace("*base", "*defaults")
+        );
+        assert_eq!(
+            renamed(&edit, &root.join("ns.mon")),
+            NAMESPACED.replace("s.base", "s.defaults")
+        );
+    }
+
+    #[test]
+    fn test_rename_type() {
+        let (_dir, root, files) = project();
+        let analysis = analyze(&root.join("schemas.mon"));
+        let mut navigator = Navigator::new(&analysis);
+
+        let edit = rename_at(&mut navigator, Position::new(2, 4), "Account", &files).unwrap();
+        assert_eq!(edit.edit_count(), 3);
+        assert_eq!(renamed(&edit, &root.join("named.mon")), NAMED.replace("User", "Account"));
+    }
+
+    #[test]
+    fn test_rename_refuses_collisions_and_bad_names() {
+        let (_dir, root, files) = project();
+        fs::write(root.join("named.mon"), NAMED.replace("a: *base", "&other: 1, a: *base"))
+            .unwrap();
+        let analysis = analyze(&root.join("schemas.mon"));
+        let mut navigator = Navigator::new(&analysis);
+
+        let error = rename_at(&mut navigator, Position::new(1, 6), "other", &files).unwrap_err();
+        assert!(error.to_string().contains("anchor 'other' already exists"), "{}", error);
+
+        let error = rename_at(&mut navigator, Position::new(1, 6), "1st", &files).unwrap_err();
+        assert!(error.to_string().contains("not a valid anchor name"));
+
+        // Names local to a file that only uses `s.base` do not clash
+        fs::write(root.join("ns.mon"), NAMESPACED.replace("b: {", "&fresh: 1, b: {")).unwrap();
+        let mut navigator = Navigator::new(&analysis);
+        assert!(rename_at(&mut navigator, Position::new(1, 6), "fresh", &files).is_ok());
+    }
+}
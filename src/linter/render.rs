@@ -0,0 +1,215 @@
+//! Renders AST values and type definitions back to MON source.
+//!
+//! Used for hover text and other editor-facing previews, where a value has to
+//! be shown as readable MON rather than as the AST's `Debug` output. Objects
+//! and arrays are laid out one member per line with four-space indentation;
+//! arrays of scalars stay on one line.
+
+use mon_core::ast::{FieldDef, Member, MonValue, MonValueKind, TypeDef, TypeDefinition};
+use std::fmt::Write;
+
+const INDENT: &str = "    ";
+
+/// Maximum length of an array of scalars kept on a single line.
+const MAX_INLINE_WIDTH: usize = 60;
+
+/// Renders a value as MON source, without the value's own anchor.
+pub fn value(value: &MonValue) -> String {
+    let mut out = String::new();
+    write_kind(&value.kind, 0, &mut out);
+    out
+}
+
+/// Renders a `#struct` or `#enum` definition as MON source.
+///
+/// ```text
+/// User: #struct {
+///     id(Number),
+///     name(String) = "Guest",
+/// }
+/// ```
+pub fn type_definition(typedef: &TypeDefinition) -> String {
+    let mut out = String::new();
+    write_type_definition(typedef, 0, &mut out);
+    out
+}
+
+/// Renders a struct field as written in its struct: `name(Type) = default`.
+pub fn field(field: &FieldDef) -> String {
+    let mut out = String::new();
+    write_field(field, 0, &mut out);
+    out
+}
+
+/// Renders a dotted or quoted object key the way it would be written.
+pub fn key(key: &str) -> String {
+    let is_identifier = !key.is_empty()
+        && !key.starts_with(|c: char| c.is_ascii_digit())
+        && key.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.');
+
+    if is_identifier { key.to_string() } else { format!("{:?}", key) }
+}
+
+fn write_type_definition(typedef: &TypeDefinition, indent: usize, out: &mut String) {
+    let pad = INDENT.repeat(indent + 1);
+    match &typedef.def_type {
+        TypeDef::Struct(def) => {
+            let _ = write!(out, "{}: #struct {{", key(&typedef.name));
+            if def.fields.is_empty() {
+                out.push('}');
+                return;
+            }
+            out.push('\n');
+            for field in &def.fields {
+                out.push_str(&pad);
+                write_field(field, indent + 1, out);
+                out.push_str(",\n");
+            }
+            let _ = write!(out, "{}}}", INDENT.repeat(indent));
+        }
+        TypeDef::Enum(def) => {
+            let _ = write!(out, "{}: #enum {{ {} }}", key(&typedef.name), def.variants.join(", "));
+        }
+    }
+}
+
+fn write_field(field: &FieldDef, indent: usize, out: &mut String) {
+    let _ = write!(out, "{}({})", key(&field.name), field.type_spec);
+    if let Some(default) = &field.default_value {
+        out.push_str(" = ");
+        write_value(default, indent, out);
+    }
+}
+
+fn write_value(value: &MonValue, indent: usize, out: &mut String) {
+    if let Some(anchor) = &value.anchor {
+        let _ = write!(out, "&{} ", anchor);
+    }
+    write_kind(&value.kind, indent, out);
+}
+
+fn write_kind(kind: &MonValueKind, indent: usize, out: &mut String) {
+    let pad = INDENT.repeat(indent + 1);
+    match kind {
+        MonValueKind::Object(members) if members.is_empty() => out.push_str("{}"),
+        MonValueKind::Object(members) => {
+            out.push_str("{\n");
+            for member in members {
+                out.push_str(&pad);
+                match member {
+                    Member::Pair(pair) => {
+                        out.push_str(&key(&pair.key));
+                        match &pair.validation {
+                            Some(type_spec) => {
+                                let _ = write!(out, " :: {} = ", type_spec);
+                            }
+                            None => out.push_str(": "),
+                        }
+                        write_value(&pair.value, indent + 1, out);
+                    }
+                    Member::Spread(name) => {
+                        let _ = write!(out, "...*{}", name);
+                    }
+                    Member::TypeDefinition(typedef) => {
+                        write_type_definition(typedef, indent + 1, out);
+                    }
+                    Member::Import(import) => {
+                        let _ = write!(out, "// import from {:?}", import.path);
+                    }
+                }
+                out.push_str(",\n");
+            }
+            let _ = write!(out, "{}}}", INDENT.repeat(indent));
+        }
+        MonValueKind::Array(items) if items.is_empty() => out.push_str("[]"),
+        MonValueKind::Array(items) => {
+            let inline = items.iter().all(|item| is_scalar(&item.kind) && item.anchor.is_none());
+            if inline {
+                let rendered: Vec<_> = items.iter().map(value).collect();
+                let line = format!("[{}]", rendered.join(", "));
+                if line.len() <= MAX_INLINE_WIDTH {
+                    out.push_str(&line);
+                    return;
+                }
+            }
+
+            out.push_str("[\n");
+            for item in items {
+                out.push_str(&pad);
+                write_value(item, indent + 1, out);
+                out.push_str(",\n");
+            }
+            let _ = write!(out, "{}]", INDENT.repeat(indent));
+        }
+        MonValueKind::String(s) => {
+            let _ = write!(out, "{:?}", s);
+        }
+        MonValueKind::Number(n) => {
+            let _ = write!(out, "{}", n);
+        }
+        MonValueKind::Boolean(b) => {
+            let _ = write!(out, "{}", b);
+        }
+        MonValueKind::Null => out.push_str("null"),
+        MonValueKind::Alias(name) => {
+            let _ = write!(out, "*{}", name);
+        }
+        MonValueKind::ArraySpread(name) => {
+            let _ = write!(out, "...*{}", name);
+        }
+        MonValueKind::EnumValue { enum_name, variant_name } => {
+            let _ = write!(out, "${}.{}", enum_name, variant_name);
+        }
+    }
+}
+
+fn is_scalar(kind: &MonValueKind) -> bool {
+    !matches!(kind, MonValueKind::Object(_) | MonValueKind::Array(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mon_core::parser::Parser;
+
+    fn parse(source: &str) -> MonValue {
+        Parser::new(source).unwrap().parse_document().unwrap().root
+    }
+
+    #[test]
+    fn test_render_value() {
+        let root = parse(
+            r#"{ name: "app", "my key": [1, 2.5, true], nested: { ...*base, inner: null }, status: $Status.On }"#,
+        );
+        assert_eq!(
+            value(&root),
+            r#"{
+    name: "app",
+    "my key": [1, 2.5, true],
+    nested: {
+        ...*base,
+        inner: null,
+    },
+    status: $Status.On,
+}"#
+        );
+    }
+
+    #[test]
+    fn test_render_type_definitions() {
+        let root = parse(
+            r#"{ User: #struct { id(Number), tags([String...]) = [] }, Status: #enum { On, Off } }"#,
+        );
+        let MonValueKind::Object(members) = &root.kind else { unreachable!() };
+        let rendered: Vec<_> = members
+            .iter()
+            .filter_map(|m| match m {
+                Member::TypeDefinition(t) => Some(type_definition(t)),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(rendered[0], "User: #struct {\n    id(Number),\n    tags([String...]) = [],\n}");
+        assert_eq!(rendered[1], "Status: #enum { On, Off }");
+    }
+}
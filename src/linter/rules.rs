@@ -1,41 +1,155 @@
 // Linting rules configuration
 
+use crate::linter::DiagnosticCode;
+use miette::Result;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LintRule {
     MaxNestingDepth,
     MaxObjectMembers,
     MaxArrayItems,
     UnusedAnchor,
-    MagicNumber,
-    MissingTypeValidation,
     DuplicateKey,
     ExcessiveSpreads,
+    MagicNumber,
+    UnusedSuppression,
+    OverriddenKey,
+    MissingTypeValidation,
+    InconsistentNaming,
+    EmptyStructure,
+    DeepImportChain,
+    CircularDependency,
+    UnusedImport,
 }
 
 impl LintRule {
+    /// Every rule, in code order.
+    pub const ALL: [LintRule; 15] = [
+        LintRule::MaxNestingDepth,
+        LintRule::MaxObjectMembers,
+        LintRule::MaxArrayItems,
+        LintRule::UnusedAnchor,
+        LintRule::DuplicateKey,
+        LintRule::ExcessiveSpreads,
+        LintRule::MagicNumber,
+        LintRule::UnusedSuppression,
+        LintRule::OverriddenKey,
+        LintRule::MissingTypeValidation,
+        LintRule::InconsistentNaming,
+        LintRule::EmptyStructure,
+        LintRule::DeepImportChain,
+        LintRule::CircularDependency,
+        LintRule::UnusedImport,
+    ];
+
     pub fn name(&self) -> &'static str {
         match self {
             LintRule::MaxNestingDepth => "max_nesting_depth",
             LintRule::MaxObjectMembers => "max_object_members",
             LintRule::MaxArrayItems => "max_array_items",
             LintRule::UnusedAnchor => "unused_anchor",
-            LintRule::MagicNumber => "magic_number",
-            LintRule::MissingTypeValidation => "missing_type_validation",
             LintRule::DuplicateKey => "duplicate_key",
             LintRule::ExcessiveSpreads => "excessive_spreads",
+            LintRule::MagicNumber => "magic_number",
+            LintRule::UnusedSuppression => "unused_suppression",
+            LintRule::OverriddenKey => "overridden_key",
+            LintRule::MissingTypeValidation => "missing_type_validation",
+            LintRule::InconsistentNaming => "inconsistent_naming",
+            LintRule::EmptyStructure => "empty_structure",
+            LintRule::DeepImportChain => "deep_import_chain",
+            LintRule::CircularDependency => "circular_dependency",
+            LintRule::UnusedImport => "unused_import",
         }
     }
 
-    pub fn description(&self) -> &'static str {
+    /// The diagnostic code the rule reports.
+    pub fn code(&self) -> DiagnosticCode {
         match self {
-            LintRule::MaxNestingDepth => "Objects/arrays are nested too deeply",
-            LintRule::MaxObjectMembers => "Object has too many members",
-            LintRule::MaxArrayItems => "Array has too many items",
-            LintRule::UnusedAnchor => "Anchor defined but never used",
-            LintRule::MagicNumber => "Literal number without context",
-            LintRule::MissingTypeValidation => "Data should have type validation",
-            LintRule::DuplicateKey => "Duplicate key in object",
-            LintRule::ExcessiveSpreads => "Too many spreads in single object",
+            LintRule::MaxNestingDepth => DiagnosticCode::MaxNestingDepth,
+            LintRule::MaxObjectMembers => DiagnosticCode::MaxObjectMembers,
+            LintRule::MaxArrayItems => DiagnosticCode::MaxArrayItems,
+            LintRule::UnusedAnchor => DiagnosticCode::UnusedAnchor,
+            LintRule::DuplicateKey => DiagnosticCode::DuplicateKey,
+            LintRule::ExcessiveSpreads => DiagnosticCode::ExcessiveSpreads,
+            LintRule::MagicNumber => DiagnosticCode::MagicNumber,
+            LintRule::UnusedSuppression => DiagnosticCode::UnusedSuppression,
+            LintRule::OverriddenKey => DiagnosticCode::OverriddenKey,
+            LintRule::MissingTypeValidation => DiagnosticCode::MissingTypeValidation,
+            LintRule::InconsistentNaming => DiagnosticCode::InconsistentNaming,
+            LintRule::EmptyStructure => DiagnosticCode::EmptyObject,
+            LintRule::DeepImportChain => DiagnosticCode::DeepImportChain,
+            LintRule::CircularDependency => DiagnosticCode::CircularDependency,
+            LintRule::UnusedImport => DiagnosticCode::UnusedImport,
         }
     }
 }
+
+/// Resolves rule ids to the diagnostic codes they select.
+///
+/// An id is a code (`LINT2001`), a rule name (`unused_anchor`, or
+/// `unused-anchor`), or a code prefix ending in `*` (`LINT1*` selects every
+/// complexity rule). Ids are case-insensitive; unknown ids are an error.
+pub fn select_rules(ids: &[String]) -> Result<Vec<DiagnosticCode>> {
+    let mut selected = Vec::new();
+
+    for id in ids.iter().map(|id| id.trim()).filter(|id| !id.is_empty()) {
+        let upper = id.to_ascii_uppercase();
+        let name = id.to_ascii_lowercase().replace('-', "_");
+
+        let matches: Vec<_> = LintRule::ALL
+            .iter()
+            .filter(|rule| match upper.strip_suffix('*') {
+                Some(prefix) => rule.code().code().starts_with(prefix),
+                None => rule.code().code() == upper || rule.name() == name,
+            })
+            .map(LintRule::code)
+            .collect();
+
+        if matches.is_empty() {
+            return Err(miette::miette!(
+                help =
+                    "Select rules by code (LINT2001), name (unused_anchor) or code prefix (LINT1*)",
+                "Unknown lint rule '{}'",
+                id
+            ));
+        }
+        for code in matches {
+            if !selected.contains(&code) {
+                selected.push(code);
+            }
+        }
+    }
+
+    Ok(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn select(ids: &[&str]) -> Result<Vec<DiagnosticCode>> {
+        select_rules(&ids.iter().map(|id| id.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_select_by_code_name_and_prefix() {
+        assert_eq!(
+            select(&["LINT2001", "duplicate-key", "lint1*"]).unwrap(),
+            vec![
+                DiagnosticCode::UnusedAnchor,
+                DiagnosticCode::DuplicateKey,
+                DiagnosticCode::MaxNestingDepth,
+                DiagnosticCode::MaxObjectMembers,
+                DiagnosticCode::MaxArrayItems,
+            ]
+        );
+        assert_eq!(select(&["LINT*"]).unwrap().len(), LintRule::ALL.len());
+    }
+
+    #[test]
+    fn test_unknown_rule_is_an_error() {
+        let error = select(&["unused_anchor", "LINT9*"]).unwrap_err();
+        assert_eq!(error.to_string(), "Unknown lint rule 'LINT9*'");
+        assert!(select(&["no_such_rule"]).is_err());
+    }
+}
@@ -0,0 +1,400 @@
+//! Semantic tokens for syntax highlighting.
+//!
+//! Regex-based editor grammars cannot tell an anchor definition from a key
+//! or an enum value from a string. This module classifies names using the
+//! parsed document instead: keys, anchors and their aliases and spreads,
+//! type names in definitions and `::` annotations, struct fields, enum
+//! variants, import names and namespaces, plus keywords and comments.
+
+use crate::linter::api::{AnalysisResult, BUILTIN_TYPES};
+use crate::linter::source_index::SourceIndex;
+use crate::linter::{Range, SymbolKind};
+use mon_core::ast::{
+    ImportSpec, ImportStatement, Member, MonValue, MonValueKind, TypeDef, TypeDefinition, TypeSpec,
+};
+use mon_core::lexer::TokenType;
+use std::ops::Range as ByteRange;
+
+/// What a highlighted name is. The variants map onto the standard LSP
+/// semantic token types of the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// An import namespace (`ns` in `import * as ns` and `*ns.base`)
+    Namespace,
+
+    /// A type that is not defined in this document, or a built-in type
+    Type,
+
+    /// A `#struct` name
+    Struct,
+
+    /// An `#enum` name
+    Enum,
+
+    /// An enum variant, in its definition or in `$Enum.Variant`
+    EnumMember,
+
+    /// An object key or struct field
+    Property,
+
+    /// An anchor, alias or spread name
+    Variable,
+
+    /// `import`, `from`, `as`, `#struct` and `#enum`
+    Keyword,
+
+    /// A `//` comment
+    Comment,
+}
+
+impl TokenKind {
+    /// Every kind, in the order used for the LSP legend.
+    pub const ALL: [TokenKind; 9] = [
+        TokenKind::Namespace,
+        TokenKind::Type,
+        TokenKind::Struct,
+        TokenKind::Enum,
+        TokenKind::EnumMember,
+        TokenKind::Property,
+        TokenKind::Variable,
+        TokenKind::Keyword,
+        TokenKind::Comment,
+    ];
+}
+
+/// Extra information about a highlighted name. The variants map onto LSP
+/// token modifiers; `Unused` is MON-specific.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenModifier {
+    /// The name is being defined here
+    Definition,
+
+    /// A built-in type such as `String`
+    DefaultLibrary,
+
+    /// An anchor that is never aliased or spread in this document
+    Unused,
+}
+
+impl TokenModifier {
+    /// Every modifier, in the order used for the LSP legend.
+    pub const ALL: [TokenModifier; 3] =
+        [TokenModifier::Definition, TokenModifier::DefaultLibrary, TokenModifier::Unused];
+}
+
+/// A highlighted span of a single line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemanticToken {
+    pub range: Range,
+    pub kind: TokenKind,
+    pub modifiers: Vec<TokenModifier>,
+}
+
+/// Classifies the names, keywords and comments of an analyzed document, in
+/// source order. With `range`, only tokens overlapping it are returned.
+pub fn semantic_tokens(result: &AnalysisResult, range: Option<Range>) -> Vec<SemanticToken> {
+    let mut builder =
+        TokenBuilder { result, index: SourceIndex::new(&result.source), spans: Vec::new() };
+
+    builder.keywords_and_comments();
+    for import in &result.document.imports {
+        builder.import(import);
+    }
+    builder.value(&result.document.root);
+
+    let mut spans = builder.spans;
+    spans.sort_by_key(|(span, _, _)| (span.start, span.end));
+    spans.dedup_by(|b, a| b.0.start < a.0.end);
+
+    spans
+        .into_iter()
+        .map(|(span, kind, modifiers)| SemanticToken {
+            range: Range::from_byte_offsets(&result.source, span.start, span.end),
+            kind,
+            modifiers,
+        })
+        .filter(|token| range.is_none_or(|range| overlaps(token.range, range)))
+        .collect()
+}
+
+struct TokenBuilder<'a> {
+    result: &'a AnalysisResult,
+    index: SourceIndex,
+    spans: Vec<(ByteRange<usize>, TokenKind, Vec<TokenModifier>)>,
+}
+
+impl TokenBuilder<'_> {
+    fn push(&mut self, span: ByteRange<usize>, kind: TokenKind, modifiers: Vec<TokenModifier>) {
+        if span.start < span.end {
+            self.spans.push((span, kind, modifiers));
+        }
+    }
+
+    /// Keywords and comments come straight from the tokens.
+    fn keywords_and_comments(&mut self) {
+        let mut found = Vec::new();
+        for pair in self.index.tokens().windows(2) {
+            match (&pair[0].ttype, &pair[1].ttype) {
+                (TokenType::Hash, TokenType::Identifier(name))
+                    if (name == "struct" || name == "enum")
+                        && pair[0].pos_end == pair[1].pos_start =>
+                {
+                    found.push(pair[0].pos_start..pair[1].pos_end);
+                }
+                (TokenType::Import | TokenType::From | TokenType::As, _) => {
+                    found.push(pair[0].pos_start..pair[0].pos_end);
+                }
+                _ => {}
+            }
+        }
+        for span in found {
+            self.push(span, TokenKind::Keyword, vec![]);
+        }
+
+        // A comment token's span starts at `//`; it never spans lines
+        let comments: Vec<_> =
+            self.index.comments().iter().map(|c| c.pos_start..c.pos_end).collect();
+        for span in comments {
+            let end = self.result.source[span.clone()].trim_end().len() + span.start;
+            self.push(span.start..end, TokenKind::Comment, vec![]);
+        }
+    }
+
+    fn import(&mut self, import: &ImportStatement) {
+        let spans = self.index.import_name_spans(import);
+        match &import.spec {
+            ImportSpec::Namespace(_) => {
+                for span in spans {
+                    self.push(span, TokenKind::Namespace, vec![TokenModifier::Definition]);
+                }
+            }
+            ImportSpec::Named(specifiers) => {
+                for (specifier, span) in specifiers.iter().zip(spans) {
+                    let kind =
+                        if specifier.is_anchor { TokenKind::Variable } else { TokenKind::Type };
+                    self.push(span, kind, vec![]);
+                }
+            }
+        }
+    }
+
+    fn value(&mut self, value: &MonValue) {
+        match &value.kind {
+            MonValueKind::Alias(_) | MonValueKind::ArraySpread(_) => {
+                if let Some(span) = self.index.name_after(value.pos_start) {
+                    self.qualified(span, TokenKind::Variable);
+                }
+            }
+            MonValueKind::EnumValue { .. } => {
+                if let Some(span) = self.index.name_after(value.pos_start) {
+                    let text = &self.result.source[span.clone()];
+                    let dot = text.rfind('.').map_or(span.end, |i| span.start + i);
+                    let kind = self.type_kind(&self.result.source[span.start..dot]);
+                    self.qualified(span.start..dot, kind);
+                    self.push(dot + 1..span.end, TokenKind::EnumMember, vec![]);
+                }
+            }
+            MonValueKind::Object(members) => {
+                let spans = self.index.member_spans(value);
+                for (member, span) in members.iter().zip(spans) {
+                    match member {
+                        Member::Pair(pair) => {
+                            let anchor = pair.value.anchor.as_deref();
+                            match (span.name, span.anchor) {
+                                // `&name: value` names the key and the anchor
+                                (Some(name), Some(anchor_span)) if name == anchor_span => {
+                                    self.anchor(anchor_span, anchor.unwrap_or_default());
+                                }
+                                (name, anchor_span) => {
+                                    if let Some(name) = name {
+                                        self.push(name, TokenKind::Property, vec![]);
+                                    }
+                                    if let Some(anchor_span) = anchor_span {
+                                        self.anchor(anchor_span, anchor.unwrap_or_default());
+                                    }
+                                }
+                            }
+                            if let Some(type_spec) = &pair.validation {
+                                self.type_spec(type_spec);
+                            }
+                            self.value(&pair.value);
+                        }
+                        Member::Spread(_) => {
+                            if let Some(name) = span.name {
+                                self.qualified(name, TokenKind::Variable);
+                            }
+                        }
+                        Member::TypeDefinition(typedef) => self.type_definition(typedef),
+                        Member::Import(_) => {}
+                    }
+                }
+            }
+            MonValueKind::Array(items) => {
+                for item in items {
+                    if let (Some(anchor), Some(span)) =
+                        (&item.anchor, self.index.anchor_before(item))
+                    {
+                        self.anchor(span, anchor);
+                    }
+                    self.value(item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn anchor(&mut self, span: ByteRange<usize>, name: &str) {
+        let mut modifiers = vec![TokenModifier::Definition];
+        if self.result.symbol_table.is_unused(name, SymbolKind::Anchor) {
+            modifiers.push(TokenModifier::Unused);
+        }
+        self.push(span, TokenKind::Variable, modifiers);
+    }
+
+    fn type_definition(&mut self, typedef: &TypeDefinition) {
+        let start = typedef.name_span.offset();
+        let name = start..start + typedef.name_span.len();
+        let members = self.index.type_member_spans(typedef);
+
+        match &typedef.def_type {
+            TypeDef::Struct(def) => {
+                self.push(name, TokenKind::Struct, vec![TokenModifier::Definition]);
+                for (field, span) in def.fields.iter().zip(members) {
+                    if let Some(name) = span.name {
+                        self.push(name, TokenKind::Property, vec![TokenModifier::Definition]);
+                    }
+                    self.type_spec(&field.type_spec);
+                    if let Some(default) = &field.default_value {
+                        self.value(default);
+                    }
+                }
+            }
+            TypeDef::Enum(def) => {
+                self.push(name, TokenKind::Enum, vec![TokenModifier::Definition]);
+                for (_, span) in def.variants.iter().zip(members) {
+                    if let Some(name) = span.name {
+                        self.push(name, TokenKind::EnumMember, vec![TokenModifier::Definition]);
+                    }
+                }
+            }
+        }
+    }
+
+    fn type_spec(&mut self, type_spec: &TypeSpec) {
+        match type_spec {
+            TypeSpec::Simple(name, span) => {
+                let span = span.offset()..span.offset() + span.len();
+                if BUILTIN_TYPES.contains(&name.as_str()) {
+                    self.push(span, TokenKind::Type, vec![TokenModifier::DefaultLibrary]);
+                } else {
+                    let kind = self.type_kind(name);
+                    self.qualified(span, kind);
+                }
+            }
+            TypeSpec::Collection(items, _) => items.iter().for_each(|item| self.type_spec(item)),
+            TypeSpec::Spread(inner, _) => self.type_spec(inner),
+        }
+    }
+
+    /// `Struct` or `Enum` for types defined in this document, else `Type`.
+    fn type_kind(&self, name: &str) -> TokenKind {
+        let symbol = self.result.symbol_table.find_symbol(name, SymbolKind::Type);
+        match symbol.and_then(|symbol| symbol.detail.as_deref()) {
+            Some("#struct") => TokenKind::Struct,
+            Some("#enum") => TokenKind::Enum,
+            _ => TokenKind::Type,
+        }
+    }
+
+    /// Pushes `ns.name` as a namespace followed by `kind`.
+    fn qualified(&mut self, span: ByteRange<usize>, kind: TokenKind) {
+        let text = &self.result.source[span.clone()];
+        match text.rfind('.') {
+            Some(dot) => {
+                self.push(span.start..span.start + dot, TokenKind::Namespace, vec![]);
+                self.push(span.start + dot + 1..span.end, kind, vec![]);
+            }
+            None => self.push(span, kind, vec![]),
+        }
+    }
+}
+
+fn overlaps(a: Range, b: Range) -> bool {
+    let key = |p: crate::linter::Position| (p.line, p.character);
+    key(a.start) <= key(b.end) && key(b.start) <= key(a.end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::api::AnalysisService;
+    use crate::linter::{LintConfig, Position};
+
+    fn tokens(source: &str) -> Vec<(String, TokenKind, Vec<TokenModifier>)> {
+        let result = AnalysisService::new(LintConfig::default())
+            .analyze_document(source, "/tmp/tokens.mon")
+            .unwrap();
+        semantic_tokens(&result, None)
+            .into_iter()
+            .map(|token| {
+                let start = token.range.start.to_byte_offset(source);
+                let end = token.range.end.to_byte_offset(source);
+                (source[start..end].to_string(), token.kind, token.modifiers)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_classifies_names() {
+        use TokenKind::*;
+        use TokenModifier::*;
+
+        let source = r#"import * as s from "./s.mon"
+{
+    // Defaults
+    &base: { port: 1 },
+    Mode: #enum { On, Off },
+    Cfg: #struct { mode(Mode), name(String) },
+    app :: Cfg = { mode: $Mode.On, name: "x" },
+    copy: { ...*base, extra: *s.other },
+    spare: &unused 2,
+}"#;
+        let found = tokens(source);
+        let find = |text: &str| {
+            found.iter().find(|(t, _, _)| t == text).map(|(_, kind, mods)| (*kind, mods.clone()))
+        };
+
+        assert_eq!(find("import"), Some((Keyword, vec![])));
+        assert_eq!(find("s"), Some((Namespace, vec![Definition])));
+        assert_eq!(find("// Defaults"), Some((Comment, vec![])));
+        assert_eq!(find("base"), Some((Variable, vec![Definition])));
+        assert_eq!(find("port"), Some((Property, vec![])));
+        assert_eq!(find("#enum"), Some((Keyword, vec![])));
+        assert_eq!(find("Mode"), Some((Enum, vec![Definition])));
+        assert_eq!(find("On"), Some((EnumMember, vec![Definition])));
+        assert_eq!(find("Cfg"), Some((Struct, vec![Definition])));
+        assert_eq!(find("mode"), Some((Property, vec![Definition])));
+        assert_eq!(find("String"), Some((Type, vec![DefaultLibrary])));
+        assert_eq!(find("unused"), Some((Variable, vec![Definition, Unused])));
+        assert_eq!(find("other"), Some((Variable, vec![])));
+
+        // `:: Cfg` and `$Mode.On` use the definitions' kinds
+        let app_line: Vec<_> = found.iter().filter(|(t, _, _)| t == "Cfg").collect();
+        assert_eq!(app_line[1].1, Struct);
+        let on: Vec<_> = found.iter().filter(|(t, _, _)| t == "On").collect();
+        assert_eq!(on[1].1, EnumMember);
+    }
+
+    #[test]
+    fn test_range_filter() {
+        let source = "{\n    a: 1,\n    b: 2,\n}";
+        let result = AnalysisService::new(LintConfig::default())
+            .analyze_document(source, "/tmp/tokens.mon")
+            .unwrap();
+        let line = Range::new(Position::new(2, 0), Position::new(2, 9));
+
+        let tokens = semantic_tokens(&result, Some(line));
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].range.start, Position::new(2, 4));
+    }
+}
@@ -1,72 +1,124 @@
 // Code smell detection for MON files
 
-use crate::linter::{LintResult, diagnostic::DiagnosticCode};
+use crate::linter::edit::{Fix, TextEdit};
+use crate::linter::source_index::{MemberSpan, SourceIndex, value_head};
+use crate::linter::{
+    DiagnosticTag, LintConfig, LintResult, Range, RelatedInformation, diagnostic::DiagnosticCode,
+};
 use mon_core::ast::{Member, MonValue, MonValueKind};
 use std::collections::{HashMap, HashSet};
+use std::ops::Range as ByteRange;
 
 pub struct SmellDetector {
-    warn_unused_anchors: bool,
-    warn_magic_numbers: bool,
+    config: LintConfig,
 }
 
 impl SmellDetector {
-    pub fn new(warn_unused_anchors: bool, warn_magic_numbers: bool) -> Self {
-        Self { warn_unused_anchors, warn_magic_numbers }
+    pub fn new(config: LintConfig) -> Self {
+        Self { config }
     }
 
-    pub fn detect(&self, root: &MonValue, result: &mut LintResult) {
-        if self.warn_unused_anchors {
-            self.detect_unused_anchors(root, result);
+    pub fn detect(&self, root: &MonValue, source: &str, result: &mut LintResult) {
+        let source = Source { text: source, index: SourceIndex::new(source) };
+
+        let enabled = |code| self.config.is_enabled(code);
+
+        if self.config.warn_unused_anchors && enabled(DiagnosticCode::UnusedAnchor) {
+            self.detect_unused_anchors(root, &source, result);
         }
 
-        if self.warn_magic_numbers {
-            self.detect_magic_numbers(root, result);
+        if self.config.warn_magic_numbers && enabled(DiagnosticCode::MagicNumber) {
+            self.detect_magic_numbers(root, &source, result);
         }
 
-        self.detect_duplicate_keys(root, result);
-        self.detect_excessive_spreads(root, result);
-        self.detect_empty_structures(root, result);
-        self.detect_inconsistent_naming(root, result);
+        if enabled(DiagnosticCode::DuplicateKey) {
+            self.detect_duplicate_keys(root, &source, result);
+        }
+        if enabled(DiagnosticCode::ExcessiveSpreads) {
+            self.detect_excessive_spreads(root, value_head(root, None), &source, result);
+        }
+        if self.config.warn_empty_structures && enabled(DiagnosticCode::EmptyObject) {
+            self.detect_empty_structures(root, None, &source, result);
+        }
     }
 
-    fn detect_unused_anchors(&self, root: &MonValue, result: &mut LintResult) {
-        let mut defined_anchors = HashSet::new();
+    fn detect_unused_anchors(&self, root: &MonValue, source: &Source, result: &mut LintResult) {
+        let mut defined_anchors = Vec::new();
         let mut used_anchors = HashSet::new();
 
-        // Collect defined anchors
-        self.collect_anchors(root, &mut defined_anchors);
+        // Collect defined anchors, in source order
+        self.collect_anchors(root, source, &mut defined_anchors);
 
         // Collect used aliases and spreads
         self.collect_aliases(root, &mut used_anchors);
 
         // Find unused
-        for anchor in &defined_anchors {
-            if !used_anchors.contains(anchor) {
-                result.add_diagnostic(
-                    DiagnosticCode::UnusedAnchor,
-                    format!("Anchor '{}' is defined but never used", anchor),
-                    None,
+        for anchor in defined_anchors.iter().filter(|a| !used_anchors.contains(&a.name)) {
+            // Every other definition of the name is unused as well
+            let related = defined_anchors
+                .iter()
+                .filter(|other| other.name == anchor.name && other.name_span != anchor.name_span)
+                .filter_map(|other| {
+                    let range = source.range(other.name_span.clone()?);
+                    Some(RelatedInformation::here(
+                        range,
+                        format!("'&{}' is also defined here", other.name),
+                    ))
+                })
+                .collect();
+            let diagnostic = result.add_diagnostic_with_range(
+                DiagnosticCode::UnusedAnchor,
+                format!("Anchor '{}' is defined but never used", anchor.name),
+                anchor.name_span.clone().map(|span| source.range(span)),
+                related,
+                vec![DiagnosticTag::Unnecessary],
+            );
+            if let Some(sigil) = &anchor.sigil_span {
+                // Other files may import the anchor
+                let fix = Fix::new(
+                    format!("Remove unused anchor '&{}'", anchor.name),
+                    vec![TextEdit::new(source.range(sigil.clone()), "")],
                 );
+                diagnostic.fixes.push(fix.affecting_importers());
             }
         }
     }
 
-    fn collect_anchors(&self, value: &MonValue, anchors: &mut HashSet<String>) {
-        if let Some(anchor) = &value.anchor {
-            anchors.insert(anchor.clone());
-        }
-
+    fn collect_anchors(&self, value: &MonValue, source: &Source, anchors: &mut Vec<AnchorDef>) {
         match &value.kind {
             MonValueKind::Object(members) => {
-                for member in members {
-                    if let Member::Pair(pair) = member {
-                        self.collect_anchors(&pair.value, anchors);
+                let spans = source.index.member_spans(value);
+                for (i, member) in members.iter().enumerate() {
+                    let Member::Pair(pair) = member else {
+                        continue;
+                    };
+                    if let Some(name) = &pair.value.anchor {
+                        let span = spans.get(i);
+                        let name_span = span.and_then(|span| span.anchor.clone());
+                        let sigil_span = span.zip(name_span.clone()).map(|(span, anchor)| {
+                            if span.name.as_ref() == Some(&anchor) {
+                                // `&key: value` keeps the key, only `&` goes
+                                span.span.start..anchor.start
+                            } else {
+                                // `key: &anchor value` loses `&anchor `
+                                source.sigil_before(anchor.start)..pair.value.pos_start
+                            }
+                        });
+                        anchors.push(AnchorDef { name: name.clone(), name_span, sigil_span });
                     }
+                    self.collect_anchors(&pair.value, source, anchors);
                 }
             }
             MonValueKind::Array(items) => {
                 for item in items {
-                    self.collect_anchors(item, anchors);
+                    if let Some(name) = &item.anchor {
+                        let name_span = source.index.anchor_before(item);
+                        let sigil_span = name_span
+                            .clone()
+                            .map(|anchor| source.sigil_before(anchor.start)..item.pos_start);
+                        anchors.push(AnchorDef { name: name.clone(), name_span, sigil_span });
+                    }
+                    self.collect_anchors(item, source, anchors);
                 }
             }
             _ => {}
@@ -103,86 +155,158 @@ impl SmellDetector {
         }
     }
 
-    fn detect_magic_numbers(&self, value: &MonValue, result: &mut LintResult) {
+    fn detect_magic_numbers(&self, value: &MonValue, source: &Source, result: &mut LintResult) {
         match &value.kind {
             MonValueKind::Number(n) => {
                 // Skip common values (0, 1, -1, 100, 1000)
                 let abs_n = n.abs();
                 if abs_n > 1.0 && abs_n != 10.0 && abs_n != 100.0 && abs_n != 1000.0 {
-                    result.add_diagnostic(
+                    result.add_diagnostic_with_range(
                         DiagnosticCode::MagicNumber,
                         format!("Consider extracting magic number {} into a named constant", n),
-                        None,
+                        Some(source.range(value.pos_start..value.pos_end)),
+                        vec![],
+                        vec![],
                     );
                 }
             }
             MonValueKind::Object(members) => {
                 for member in members {
                     if let Member::Pair(pair) = member {
-                        self.detect_magic_numbers(&pair.value, result);
+                        self.detect_magic_numbers(&pair.value, source, result);
                     }
                 }
             }
             MonValueKind::Array(items) => {
                 for item in items {
-                    self.detect_magic_numbers(item, result);
+                    self.detect_magic_numbers(item, source, result);
                 }
             }
             _ => {}
         }
     }
 
-    fn detect_duplicate_keys(&self, value: &MonValue, result: &mut LintResult) {
+    fn detect_duplicate_keys(&self, value: &MonValue, source: &Source, result: &mut LintResult) {
         if let MonValueKind::Object(members) = &value.kind {
+            let spans = source.index.member_spans(value);
+            // Key -> index of its first occurrence
+            let mut first_keys = HashMap::new();
+            // Key -> index of its latest occurrence
             let mut seen_keys = HashMap::new();
 
-            for member in members {
+            for (i, member) in members.iter().enumerate() {
                 if let Member::Pair(pair) = member {
-                    if seen_keys.insert(pair.key.clone(), ()).is_some() {
-                        result.add_diagnostic(
+                    let first = *first_keys.entry(pair.key.as_str()).or_insert(i);
+                    if let Some(earlier) = seen_keys.insert(pair.key.as_str(), i) {
+                        let name = |i: usize| spans.get(i).and_then(|span| span.name.clone());
+                        let related = name(first)
+                            .map(|span| {
+                                RelatedInformation::here(
+                                    source.range(span),
+                                    format!("'{}' is first defined here", pair.key),
+                                )
+                            })
+                            .into_iter()
+                            .collect();
+                        let diagnostic = result.add_diagnostic_with_range(
                             DiagnosticCode::DuplicateKey,
                             format!("Duplicate key '{}' in object", pair.key),
-                            None,
+                            name(i).map(|span| source.range(span)),
+                            related,
+                            vec![],
                         );
+
+                        // The later occurrence wins, so dropping the earlier one
+                        // keeps the meaning; an anchored one may still be aliased
+                        let anchored = matches!(
+                            &members[earlier],
+                            Member::Pair(earlier) if earlier.value.anchor.is_some()
+                        );
+                        if !anchored && let Some(removal) = source.member_removal(&spans, earlier) {
+                            diagnostic.fixes.push(Fix::new(
+                                format!("Remove the earlier '{}'", pair.key),
+                                vec![TextEdit::new(source.range(removal), "")],
+                            ));
+                        }
                     }
 
                     // Recurse
-                    self.detect_duplicate_keys(&pair.value, result);
+                    self.detect_duplicate_keys(&pair.value, source, result);
                 }
             }
         } else if let MonValueKind::Array(items) = &value.kind {
             for item in items {
-                self.detect_duplicate_keys(item, result);
+                self.detect_duplicate_keys(item, source, result);
             }
         }
     }
 
-    fn detect_excessive_spreads(&self, value: &MonValue, result: &mut LintResult) {
+    /// `head` is the span diagnostics about `value` point at.
+    fn detect_excessive_spreads(
+        &self,
+        value: &MonValue,
+        head: ByteRange<usize>,
+        source: &Source,
+        result: &mut LintResult,
+    ) {
         if let MonValueKind::Object(members) = &value.kind {
-            let spread_count = members.iter().filter(|m| matches!(m, Member::Spread(_))).count();
+            let spans = source.index.member_spans(value);
+            let spreads: Vec<_> = members
+                .iter()
+                .zip(&spans)
+                .filter_map(|(member, span)| match member {
+                    Member::Spread(name) => Some((name, span)),
+                    _ => None,
+                })
+                .collect();
 
-            if spread_count > 3 {
-                result.add_diagnostic(
+            if spreads.len() > self.config.max_spreads_per_object {
+                let related = spreads
+                    .iter()
+                    .map(|(name, span)| {
+                        RelatedInformation::here(
+                            source.range(span.span.clone()),
+                            format!("Spread of '{}'", name),
+                        )
+                    })
+                    .collect();
+                result.add_diagnostic_with_range(
                     DiagnosticCode::ExcessiveSpreads,
-                    format!("Object has {} spreads, consider simplifying", spread_count),
-                    None,
+                    format!("Object has {} spreads, consider simplifying", spreads.len()),
+                    Some(source.range(head)),
+                    related,
+                    vec![],
                 );
             }
 
             // Recurse
-            for member in members {
+            for (member, span) in members.iter().zip(&spans) {
                 if let Member::Pair(pair) = member {
-                    self.detect_excessive_spreads(&pair.value, result);
+                    let head = value_head(&pair.value, span.name.clone());
+                    self.detect_excessive_spreads(&pair.value, head, source, result);
                 }
             }
         } else if let MonValueKind::Array(items) = &value.kind {
             for item in items {
-                self.detect_excessive_spreads(item, result);
+                self.detect_excessive_spreads(item, value_head(item, None), source, result);
             }
         }
     }
 
-    fn detect_empty_structures(&self, value: &MonValue, result: &mut LintResult) {
+    /// `removal` is the member holding `value` (its key and the span that
+    /// deletes it), if the value can be removed along with its key.
+    fn detect_empty_structures(
+        &self,
+        value: &MonValue,
+        removal: Option<(&str, ByteRange<usize>)>,
+        source: &Source,
+        result: &mut LintResult,
+    ) {
+        let range = Some(source.range(value.pos_start..value.pos_end));
+        let removal = removal.filter(|_| value.anchor.is_none()).map(|(key, span)| {
+            Fix::new(format!("Remove empty '{}'", key), vec![TextEdit::new(source.range(span), "")])
+        });
+
         match &value.kind {
             MonValueKind::Object(members) => {
                 // Filter out type definitions
@@ -190,67 +314,110 @@ impl SmellDetector {
                     members.iter().filter(|m| !matches!(m, Member::TypeDefinition(_))).collect();
 
                 if regular_members.is_empty() {
-                    result.add_diagnostic(
+                    let diagnostic = result.add_diagnostic_with_range(
                         DiagnosticCode::EmptyObject,
                         "Empty object found - verify this is intentional".to_string(),
-                        None,
+                        range,
+                        vec![],
+                        vec![],
                     );
+                    // Type definitions would go with the object
+                    if members.is_empty() {
+                        diagnostic.fixes.extend(removal);
+                    }
                 }
 
                 // Recurse
-                for member in members {
+                let spans = source.index.member_spans(value);
+                for (i, member) in members.iter().enumerate() {
                     if let Member::Pair(pair) = member {
-                        self.detect_empty_structures(&pair.value, result);
+                        let removal =
+                            source.member_removal(&spans, i).map(|span| (pair.key.as_str(), span));
+                        self.detect_empty_structures(&pair.value, removal, source, result);
                     }
                 }
             }
             MonValueKind::Array(items) => {
                 if items.is_empty() {
-                    result.add_diagnostic(
-                        DiagnosticCode::EmptyObject,
-                        "Empty array found - verify this is intentional".to_string(),
-                        None,
-                    );
+                    result
+                        .add_diagnostic_with_range(
+                            DiagnosticCode::EmptyObject,
+                            "Empty array found - verify this is intentional".to_string(),
+                            range,
+                            vec![],
+                            vec![],
+                        )
+                        .fixes
+                        .extend(removal);
                 }
 
                 for item in items {
-                    self.detect_empty_structures(item, result);
+                    self.detect_empty_structures(item, None, source, result);
                 }
             }
             _ => {}
         }
     }
+}
+
+/// An anchor definition found in the document.
+struct AnchorDef {
+    name: String,
+
+    /// Span of the anchor name
+    name_span: Option<ByteRange<usize>>,
+
+    /// Span that removes the anchor but keeps the value
+    sigil_span: Option<ByteRange<usize>>,
+}
+
+/// Source text of the document being checked, for ranges and fixes.
+struct Source<'a> {
+    text: &'a str,
+    index: SourceIndex,
+}
+
+impl Source<'_> {
+    fn range(&self, span: ByteRange<usize>) -> Range {
+        Range::from_byte_offsets(self.text, span.start, span.end)
+    }
 
-    fn detect_inconsistent_naming(&self, value: &MonValue, result: &mut LintResult) {
-        if let MonValueKind::Object(members) = &value.kind {
-            let mut snake_case_count = 0;
-            let mut camel_case_count = 0;
+    /// Offset of the `&` written before the anchor name at `name_start`.
+    fn sigil_before(&self, name_start: usize) -> usize {
+        self.text[..name_start].rfind('&').unwrap_or(name_start)
+    }
 
-            for member in members {
-                if let Member::Pair(pair) = member {
-                    let key = &pair.key;
-                    if key.contains('_') {
-                        snake_case_count += 1;
-                    } else if key.chars().any(|c| c.is_uppercase()) {
-                        camel_case_count += 1;
-                    }
+    /// Span that deletes member `i` of an object along with one separating
+    /// comma, so the remaining members stay well-formed. Comments in front
+    /// of the next member belong to it and stay.
+    fn member_removal(&self, spans: &[MemberSpan], i: usize) -> Option<ByteRange<usize>> {
+        let span = &spans.get(i)?.span;
+        let text = self.text;
+        let after_comma =
+            text[span.end..].trim_start().strip_prefix(',').map(|after| text.len() - after.len());
 
-                    // Recurse
-                    self.detect_inconsistent_naming(&pair.value, result);
-                }
-            }
+        // A member on lines of its own goes with them, and with a comment
+        // trailing it
+        let line_start = text[..span.start].rfind('\n').map_or(0, |newline| newline + 1);
+        let end = after_comma.unwrap_or(span.end);
+        let line_end = text[end..].find('\n').map_or(text.len(), |newline| end + newline + 1);
+        let tail = text[end..line_end].trim();
+        if text[line_start..span.start].trim().is_empty()
+            && (tail.is_empty() || tail.starts_with("//"))
+        {
+            return Some(line_start..line_end);
+        }
 
-            // If we have both styles in same object, warn
-            if snake_case_count > 0 && camel_case_count > 0 {
-                result.add_diagnostic(
-                    DiagnosticCode::InconsistentNaming,
-                    format!(
-                        "Object has mixed naming styles ({} snake_case, {} camelCase)",
-                        snake_case_count, camel_case_count
-                    ),
-                    None,
-                );
-            }
+        if let Some(after_comma) = after_comma {
+            // Up to the next member on the same line
+            let rest = &text[after_comma..];
+            let spaces = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+            return Some(span.start..after_comma + spaces);
+        }
+        if let Some(previous) = i.checked_sub(1).and_then(|i| spans.get(i)) {
+            // From the end of the previous member, taking its comma
+            return Some(previous.span.end..span.end);
         }
+        Some(span.start..span.end)
     }
 }
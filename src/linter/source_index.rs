@@ -0,0 +1,401 @@
+//! Token-level index of a source file for precise name ranges.
+//!
+//! mon-core's AST records byte spans for values, imports and type definitions,
+//! but not for object keys, object spreads (`...*name`) or anchor names. This
+//! module re-lexes the source and recovers those spans by walking the tokens
+//! that make up each object, so diagnostics and LSP features can point at the
+//! exact name instead of the surrounding value.
+
+use mon_core::ast::{ImportStatement, MonValue, MonValueKind, TypeDefinition};
+use mon_core::lexer::{Lexer, Token, TokenType};
+use std::ops::Range as ByteRange;
+
+/// Byte spans for one member of an object, in source order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberSpan {
+    /// Span of the whole member, from its first to its last token
+    pub span: ByteRange<usize>,
+
+    /// Span of the member's name: the key of a pair, the alias name of a
+    /// spread (without `...*`), or the name of a type definition
+    pub name: Option<ByteRange<usize>>,
+
+    /// Span of the anchor name (without `&`) if the member defines one,
+    /// either as `&key: value` or `key: &anchor value`
+    pub anchor: Option<ByteRange<usize>>,
+}
+
+/// Lexed tokens of a source file, with trivia separated out.
+#[derive(Debug, Clone, Default)]
+pub struct SourceIndex {
+    /// Significant tokens (no whitespace or comments), ordered by position
+    tokens: Vec<Token>,
+
+    /// Comment tokens, ordered by position
+    comments: Vec<Token>,
+}
+
+impl SourceIndex {
+    /// Lexes `source` and builds the index.
+    pub fn new(source: &str) -> Self {
+        let mut tokens = Vec::new();
+        let mut comments = Vec::new();
+
+        for token in Lexer::new(source).lex() {
+            match token.ttype {
+                TokenType::Whitespace | TokenType::Eof => {}
+                TokenType::Comment(_) => comments.push(token),
+                _ => tokens.push(token),
+            }
+        }
+
+        Self { tokens, comments }
+    }
+
+    /// Gets the significant tokens (no whitespace or comments), in order.
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    /// Gets the comment tokens, in order.
+    pub fn comments(&self) -> &[Token] {
+        &self.comments
+    }
+
+    /// Gets the byte spans of every member of an object value.
+    ///
+    /// The returned spans line up one-to-one with the object's `members`.
+    /// Returns an empty list for non-object values.
+    pub fn member_spans(&self, object: &MonValue) -> Vec<MemberSpan> {
+        if !matches!(object.kind, MonValueKind::Object(_)) {
+            return Vec::new();
+        }
+
+        // Tokens strictly between the object's braces
+        let first = self.token_index_at(object.pos_start) + 1;
+        let last = self.token_index_at(object.pos_end.saturating_sub(1)).max(first);
+        self.split_members(first, last)
+    }
+
+    /// Gets the byte spans of the fields of a `#struct` or the variants of an
+    /// `#enum`, in order.
+    ///
+    /// The returned spans line up one-to-one with `StructDef::fields` or
+    /// `EnumDef::variants`; `name` is the field or variant name.
+    pub fn type_member_spans(&self, typedef: &TypeDefinition) -> Vec<MemberSpan> {
+        let start = self.token_index_at(typedef.pos_start);
+        let Some(open) = self.tokens[start..].iter().position(|t| t.ttype == TokenType::LBrace)
+        else {
+            return Vec::new();
+        };
+
+        let first = start + open + 1;
+        let last = self.token_index_at(typedef.pos_end.saturating_sub(1)).max(first);
+        self.split_members(first, last)
+    }
+
+    /// Splits the tokens in `first..last` into comma-separated members.
+    fn split_members(&self, first: usize, last: usize) -> Vec<MemberSpan> {
+        let inner = &self.tokens[first.min(self.tokens.len())..last.min(self.tokens.len())];
+
+        let mut members = Vec::new();
+        let mut depth = 0usize;
+        let mut segment_start = 0usize;
+
+        for (i, token) in inner.iter().enumerate() {
+            match token.ttype {
+                TokenType::LBrace | TokenType::LBracket | TokenType::LParen => depth += 1,
+                TokenType::RBrace | TokenType::RBracket | TokenType::RParen => {
+                    depth = depth.saturating_sub(1)
+                }
+                TokenType::Comma if depth == 0 => {
+                    if i > segment_start {
+                        members.push(Self::member_span(&inner[segment_start..i]));
+                    }
+                    segment_start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        if inner.len() > segment_start {
+            members.push(Self::member_span(&inner[segment_start..]));
+        }
+
+        members
+    }
+
+    /// Gets the span of a dotted name (`a` or `ns.a`) that follows a sigil
+    /// such as `*`, `...*` or `$`, starting the search at `offset`.
+    pub fn name_after(&self, offset: usize) -> Option<ByteRange<usize>> {
+        let first = self.token_index_at(offset);
+        let start = self.tokens[first..]
+            .iter()
+            .position(|t| matches!(t.ttype, TokenType::Identifier(_)))?;
+        Some(Self::dotted_name(&self.tokens[first + start..]))
+    }
+
+    /// Gets the span of an anchor name written directly before a value
+    /// (`key: &anchor value` or `[&anchor value]`).
+    pub fn anchor_before(&self, value: &MonValue) -> Option<ByteRange<usize>> {
+        let index = self.token_index_at(value.pos_start);
+        if index < 2 {
+            return None;
+        }
+
+        let name = &self.tokens[index - 1];
+        let sigil = &self.tokens[index - 2];
+        match (&sigil.ttype, &name.ttype) {
+            (TokenType::Ampersand, TokenType::Identifier(_)) => Some(name.pos_start..name.pos_end),
+            _ => None,
+        }
+    }
+
+    /// Gets the spans of the names listed in an import statement.
+    ///
+    /// For `import { &a, B } from "..."` this returns the spans of `a` and `B`
+    /// in order; for `import * as ns from "..."` it returns the span of `ns`.
+    pub fn import_name_spans(&self, import: &ImportStatement) -> Vec<ByteRange<usize>> {
+        let first = self.token_index_at(import.pos_start);
+        let last = self.token_index_at(import.pos_end).min(self.tokens.len());
+        let tokens = &self.tokens[first.min(last)..last];
+
+        let mut spans = Vec::new();
+        let mut in_braces = false;
+        let mut after_as = false;
+
+        for token in tokens {
+            match &token.ttype {
+                TokenType::LBrace => in_braces = true,
+                TokenType::RBrace => in_braces = false,
+                TokenType::As => after_as = true,
+                TokenType::From => break,
+                TokenType::Identifier(_) if in_braces || after_as => {
+                    spans.push(token.pos_start..token.pos_end);
+                    after_as = false;
+                }
+                _ => {}
+            }
+        }
+
+        spans
+    }
+
+    /// Gets the `//` comment block written directly above the line containing
+    /// `offset`, one comment per line with the `//` stripped.
+    ///
+    /// The block must end on the line right before the definition, and every
+    /// comment in it must sit on a line of its own; a blank line or a trailing
+    /// comment after code ends the block.
+    pub fn doc_comment_before(&self, source: &str, offset: usize) -> Option<String> {
+        let mut start = line_start(source, offset);
+        let mut index = self.comments.partition_point(|c| c.pos_start < start);
+        let mut lines = Vec::new();
+
+        while index > 0 && start > 0 {
+            let previous_line = line_start(source, start - 1);
+            let comment = &self.comments[index - 1];
+            if comment.pos_start < previous_line
+                || !source[previous_line..comment.pos_start].trim().is_empty()
+            {
+                break;
+            }
+
+            if let TokenType::Comment(text) = &comment.ttype {
+                lines.push(text.as_str());
+            }
+            start = previous_line;
+            index -= 1;
+        }
+
+        if lines.is_empty() {
+            return None;
+        }
+        lines.reverse();
+        Some(lines.join("\n"))
+    }
+
+    /// Index of the first significant token starting at or after `offset`.
+    fn token_index_at(&self, offset: usize) -> usize {
+        self.tokens.partition_point(|t| t.pos_start < offset)
+    }
+
+    fn member_span(tokens: &[Token]) -> MemberSpan {
+        let span = tokens[0].pos_start..tokens[tokens.len() - 1].pos_end;
+
+        let (name, anchor) = match &tokens[0].ttype {
+            // `...*name`
+            TokenType::Spread => {
+                let name = tokens
+                    .iter()
+                    .position(|t| matches!(t.ttype, TokenType::Identifier(_)))
+                    .map(|i| Self::dotted_name(&tokens[i..]));
+                (name, None)
+            }
+            // `&name: value`
+            TokenType::Ampersand if tokens.len() > 1 => {
+                let name = Self::dotted_name(&tokens[1..]);
+                (Some(name.clone()), Some(name))
+            }
+            // `key: value`, `key: &anchor value`, `Name: #struct { ... }`,
+            // or a struct field `name(Type) = default`
+            TokenType::Identifier(_) | TokenType::String(_) => {
+                let name = Self::dotted_name(tokens);
+                let anchor = tokens
+                    .windows(2)
+                    .skip_while(|w| !matches!(w[0].ttype, TokenType::Colon | TokenType::Equals))
+                    .nth(1)
+                    .filter(|w| {
+                        matches!(w[0].ttype, TokenType::Ampersand)
+                            && matches!(w[1].ttype, TokenType::Identifier(_))
+                    })
+                    .map(|w| w[1].pos_start..w[1].pos_end);
+                (Some(name), anchor)
+            }
+            _ => (None, None),
+        };
+
+        MemberSpan { span, name, anchor }
+    }
+
+    /// Span of `ident { "." ident }` at the start of `tokens`.
+    fn dotted_name(tokens: &[Token]) -> ByteRange<usize> {
+        let start = tokens[0].pos_start;
+        let mut end = tokens[0].pos_end;
+
+        let mut rest = tokens[1..].chunks_exact(2);
+        for pair in &mut rest {
+            match (&pair[0].ttype, &pair[1].ttype) {
+                (TokenType::Dot, TokenType::Identifier(_)) => end = pair[1].pos_end,
+                _ => break,
+            }
+        }
+
+        start..end
+    }
+}
+
+/// Gets the span a diagnostic about `value` points at: `key`, the name it is
+/// written under, if there is one; otherwise the opening bracket of an object
+/// or array, or the whole of any other value.
+pub fn value_head(value: &MonValue, key: Option<ByteRange<usize>>) -> ByteRange<usize> {
+    key.unwrap_or_else(|| match value.kind {
+        MonValueKind::Object(_) | MonValueKind::Array(_) => value.pos_start..value.pos_start + 1,
+        _ => value.pos_start..value.pos_end,
+    })
+}
+
+/// The text to delete with `span`: its whole lines if nothing else is written
+/// on them, otherwise `span` and the whitespace before it.
+pub fn removal_span(source: &str, span: &ByteRange<usize>) -> ByteRange<usize> {
+    let start = line_start(source, span.start);
+    let end = source[span.end..].find('\n').map_or(source.len(), |i| span.end + i);
+    if source[start..span.start].trim().is_empty() && source[span.end..end].trim().is_empty() {
+        start..(end + 1).min(source.len())
+    } else {
+        source[..span.start].trim_end().len()..span.end
+    }
+}
+
+/// Byte offset of the start of the line containing `offset`.
+fn line_start(source: &str, offset: usize) -> usize {
+    source[..offset.min(source.len())].rfind('\n').map_or(0, |i| i + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mon_core::parser::Parser;
+
+    fn parse(source: &str) -> MonValue {
+        Parser::new(source).unwrap().parse_document().unwrap().root
+    }
+
+    fn text(source: &str, span: &ByteRange<usize>) -> String {
+        source[span.clone()].to_string()
+    }
+
+    #[test]
+    fn test_member_spans() {
+        let source = r#"{
+    &base: { a: 1 },
+    "quoted key": [1, 2],
+    schemas.User: 3,
+    ...*base,
+    copy: &again { b: 2 },
+    Point: #struct { x(Number) },
+}"#;
+        let root = parse(source);
+        let spans = SourceIndex::new(source).member_spans(&root);
+
+        assert_eq!(spans.len(), 6);
+        assert_eq!(text(source, spans[0].name.as_ref().unwrap()), "base");
+        assert_eq!(text(source, spans[0].anchor.as_ref().unwrap()), "base");
+        assert_eq!(text(source, &spans[0].span), "&base: { a: 1 }");
+        assert_eq!(text(source, spans[1].name.as_ref().unwrap()), "\"quoted key\"");
+        assert_eq!(text(source, spans[2].name.as_ref().unwrap()), "schemas.User");
+        assert_eq!(text(source, spans[3].name.as_ref().unwrap()), "base");
+        assert_eq!(text(source, &spans[3].span), "...*base");
+        assert_eq!(text(source, spans[4].name.as_ref().unwrap()), "copy");
+        assert_eq!(text(source, spans[4].anchor.as_ref().unwrap()), "again");
+        assert_eq!(text(source, spans[5].name.as_ref().unwrap()), "Point");
+    }
+
+    #[test]
+    fn test_type_member_spans() {
+        let source = "{\n    User: #struct { id(Number), tags([String...]) = [\"a\", \"b\"] },\n    Status: #enum { On, Off },\n}";
+        let root = parse(source);
+        let MonValueKind::Object(members) = &root.kind else { unreachable!() };
+        let index = SourceIndex::new(source);
+        let spans: Vec<_> = members
+            .iter()
+            .filter_map(|m| match m {
+                mon_core::ast::Member::TypeDefinition(t) => Some(index.type_member_spans(t)),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(spans[0].len(), 2);
+        assert_eq!(text(source, spans[0][1].name.as_ref().unwrap()), "tags");
+        assert_eq!(text(source, &spans[0][1].span), "tags([String...]) = [\"a\", \"b\"]");
+        assert_eq!(spans[1].len(), 2);
+        assert_eq!(text(source, &spans[1][1].span), "Off");
+    }
+
+    #[test]
+    fn test_name_after_sigil() {
+        let source = "{ a: *base.default_tags }";
+        let index = SourceIndex::new(source);
+        let span = index.name_after(5).unwrap();
+        assert_eq!(text(source, &span), "base.default_tags");
+    }
+
+    #[test]
+    fn test_doc_comment_before() {
+        let source = "{\n    // Shared defaults.\n    // Used by every service.\n    &base: { a: 1 },\n\n    // Not attached\n\n    plain: 1, // trailing\n    next: 2,\n}";
+        let index = SourceIndex::new(source);
+
+        let base = source.find("&base").unwrap();
+        assert_eq!(
+            index.doc_comment_before(source, base).as_deref(),
+            Some("Shared defaults.\nUsed by every service.")
+        );
+        assert_eq!(index.doc_comment_before(source, source.find("plain").unwrap()), None);
+        assert_eq!(index.doc_comment_before(source, source.find("next").unwrap()), None);
+    }
+
+    #[test]
+    fn test_import_name_spans() {
+        let source = "import { &base_settings, Timestamp } from \"./a.mon\"\nimport * as base from \"./a.mon\"\n{}";
+        let doc = Parser::new(source).unwrap().parse_document().unwrap();
+        let index = SourceIndex::new(source);
+
+        let named = index.import_name_spans(&doc.imports[0]);
+        assert_eq!(named.len(), 2);
+        assert_eq!(text(source, &named[0]), "base_settings");
+        assert_eq!(text(source, &named[1]), "Timestamp");
+
+        let namespace = index.import_name_spans(&doc.imports[1]);
+        assert_eq!(namespace.len(), 1);
+        assert_eq!(text(source, &namespace[0]), "base");
+    }
+}
@@ -0,0 +1,280 @@
+//! Folding ranges and selection ranges.
+//!
+//! Both follow the nesting of the document: objects, arrays and type
+//! definitions fold, and selection grows from a name or value to its member,
+//! then to the enclosing object or array, up to the whole document.
+
+use crate::linter::api::AnalysisResult;
+use crate::linter::source_index::SourceIndex;
+use crate::linter::{Position, Range};
+use mon_core::ast::{Member, MonValue, MonValueKind, TypeDefinition};
+use std::ops::Range as ByteRange;
+
+/// What a folding range covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoldKind {
+    /// An object, array or type definition
+    Region,
+
+    /// A block of `//` comments on consecutive lines
+    Comment,
+
+    /// Consecutive `import` statements
+    Imports,
+}
+
+/// Lines that can be collapsed, both zero-based and inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fold {
+    pub start_line: u32,
+    pub end_line: u32,
+    pub kind: FoldKind,
+}
+
+/// Gets the folding ranges of a document, ordered by start line.
+///
+/// Objects, arrays and type definitions fold up to the line before their
+/// closing bracket, so the bracket stays visible.
+pub fn folding_ranges(result: &AnalysisResult) -> Vec<Fold> {
+    let source = result.source.as_str();
+    let line = |offset: usize| Position::from_byte_offset(source, offset).line;
+    let mut folds = Vec::new();
+
+    let mut push = |start: u32, end: u32, kind| {
+        if end > start {
+            folds.push(Fold { start_line: start, end_line: end, kind });
+        }
+    };
+
+    let mut regions = Vec::new();
+    collect_regions(&result.document.root, &mut regions);
+    for span in regions {
+        push(
+            line(span.start),
+            line(span.end.saturating_sub(1)).saturating_sub(1),
+            FoldKind::Region,
+        );
+    }
+
+    let imports = &result.document.imports;
+    if let (Some(first), Some(last)) = (imports.first(), imports.last()) {
+        push(line(first.pos_start), line(last.pos_end.saturating_sub(1)), FoldKind::Imports);
+    }
+
+    // Comments that sit on their own, consecutive lines
+    let index = SourceIndex::new(source);
+    let mut block: Option<(u32, u32)> = None;
+    for comment in index.comments() {
+        let start = comment.pos_start;
+        let own_line = source[..start].rsplit('\n').next().is_none_or(|s| s.trim().is_empty());
+        let comment_line = line(start);
+        block = match block {
+            Some((first, previous)) if own_line && comment_line == previous + 1 => {
+                Some((first, comment_line))
+            }
+            _ => {
+                if let Some((first, last)) = block {
+                    push(first, last, FoldKind::Comment);
+                }
+                own_line.then_some((comment_line, comment_line))
+            }
+        };
+    }
+    if let Some((first, last)) = block {
+        push(first, last, FoldKind::Comment);
+    }
+
+    folds.sort_by_key(|fold| (fold.start_line, fold.end_line));
+    folds
+}
+
+/// Byte spans of every object, array and type definition in a value.
+fn collect_regions(value: &MonValue, regions: &mut Vec<ByteRange<usize>>) {
+    match &value.kind {
+        MonValueKind::Object(members) => {
+            regions.push(value.pos_start..value.pos_end);
+            for member in members {
+                match member {
+                    Member::Pair(pair) => collect_regions(&pair.value, regions),
+                    Member::TypeDefinition(typedef) => {
+                        regions.push(typedef.pos_start..typedef.pos_end)
+                    }
+                    Member::Spread(_) | Member::Import(_) => {}
+                }
+            }
+        }
+        MonValueKind::Array(items) => {
+            regions.push(value.pos_start..value.pos_end);
+            items.iter().for_each(|item| collect_regions(item, regions));
+        }
+        _ => {}
+    }
+}
+
+/// Gets the ranges a selection at `position` expands through, innermost
+/// first: the name or scalar under the cursor, its member, the enclosing
+/// object or array, and so on up to the whole document.
+pub fn selection_ranges(result: &AnalysisResult, position: Position) -> Vec<Range> {
+    let source = result.source.as_str();
+    let offset = position.to_byte_offset(source);
+    let builder = SelectionBuilder { index: SourceIndex::new(source), offset };
+
+    let mut spans = Vec::new();
+    spans.push(0..source.len());
+    builder.value(&result.document.root, &mut spans);
+
+    let mut ranges: Vec<Range> = Vec::new();
+    for span in spans.into_iter().rev() {
+        let range = Range::from_byte_offsets(source, span.start, span.end);
+        if ranges.last() != Some(&range) {
+            ranges.push(range);
+        }
+    }
+    ranges
+}
+
+struct SelectionBuilder {
+    index: SourceIndex,
+    offset: usize,
+}
+
+impl SelectionBuilder {
+    fn contains(&self, span: &ByteRange<usize>) -> bool {
+        span.start <= self.offset && self.offset <= span.end
+    }
+
+    /// Pushes the spans enclosing the offset inside `value`, outermost first.
+    fn value(&self, value: &MonValue, spans: &mut Vec<ByteRange<usize>>) {
+        if !self.contains(&(value.pos_start..value.pos_end)) {
+            return;
+        }
+        spans.push(value.pos_start..value.pos_end);
+
+        match &value.kind {
+            MonValueKind::Object(members) => {
+                let member_spans = self.index.member_spans(value);
+                let Some((member, span)) =
+                    members.iter().zip(member_spans).find(|(_, span)| self.contains(&span.span))
+                else {
+                    return;
+                };
+                spans.push(span.span.clone());
+
+                match member {
+                    Member::Pair(pair) => {
+                        let name = span.name.filter(|name| self.contains(name));
+                        let anchor = span.anchor.filter(|anchor| self.contains(anchor));
+                        match name.or(anchor) {
+                            Some(name) => spans.push(name),
+                            None => self.value(&pair.value, spans),
+                        }
+                    }
+                    Member::TypeDefinition(typedef) => self.type_definition(typedef, spans),
+                    Member::Spread(_) | Member::Import(_) => {
+                        spans.extend(span.name.filter(|name| self.contains(name)))
+                    }
+                }
+            }
+            MonValueKind::Array(items) => {
+                let item = items.iter().find(|item| {
+                    let start =
+                        self.index.anchor_before(item).map_or(item.pos_start, |a| a.start - 1);
+                    self.contains(&(start..item.pos_end))
+                });
+                if let Some(item) = item {
+                    if let Some(anchor) = self.index.anchor_before(item) {
+                        spans.push(anchor.start - 1..item.pos_end);
+                    }
+                    self.value(item, spans);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn type_definition(&self, typedef: &TypeDefinition, spans: &mut Vec<ByteRange<usize>>) {
+        let name = typedef.name_span.offset()..typedef.name_span.offset() + typedef.name_span.len();
+        if self.contains(&name) {
+            spans.push(name);
+            return;
+        }
+
+        let member =
+            self.index.type_member_spans(typedef).into_iter().find(|s| self.contains(&s.span));
+        if let Some(member) = member {
+            spans.push(member.span.clone());
+            spans.extend(member.name.filter(|name| self.contains(name)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintConfig;
+    use crate::linter::api::AnalysisService;
+
+    fn analyze(source: &str) -> AnalysisResult {
+        AnalysisService::new(LintConfig::default())
+            .analyze_document(source, "/tmp/structure.mon")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_folding_ranges() {
+        let source = r#"import { &a } from "./a.mon"
+import { B } from "./b.mon"
+// Server settings,
+// shared by every service.
+{
+    server: {
+        port: 80,
+    },
+    Mode: #enum {
+        On,
+    },
+    tags: ["a"],
+}"#;
+        let folds: Vec<_> = folding_ranges(&analyze(source))
+            .into_iter()
+            .map(|fold| (fold.start_line, fold.end_line, fold.kind))
+            .collect();
+
+        assert_eq!(
+            folds,
+            vec![
+                (0, 1, FoldKind::Imports),
+                (2, 3, FoldKind::Comment),
+                (4, 11, FoldKind::Region),
+                (5, 6, FoldKind::Region),
+                (8, 9, FoldKind::Region),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_selection_expands_from_value_to_document() {
+        let source = "{\n    server: { port: 80, host: \"x\" },\n}";
+        let result = analyze(source);
+        let text = |range: &Range| {
+            let start = range.start.to_byte_offset(source);
+            source[start..range.end.to_byte_offset(source)].to_string()
+        };
+
+        let ranges = selection_ranges(&result, Position::new(1, 21));
+        let texts: Vec<_> = ranges.iter().map(text).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "80",
+                "port: 80",
+                "{ port: 80, host: \"x\" }",
+                "server: { port: 80, host: \"x\" }",
+                source,
+            ]
+        );
+
+        let ranges = selection_ranges(&result, Position::new(1, 6));
+        assert_eq!(text(&ranges[0]), "server");
+    }
+}
@@ -0,0 +1,275 @@
+//! Suppression comments.
+//!
+//! - `// mon-lint-disable-next-line LINT2004` silences the line below
+//! - `// mon-lint-disable LINT1002` silences every line up to a matching
+//!   `// mon-lint-enable`, or to the end of the file
+//! - `// mon-lint-disable-file` silences the whole file
+//!
+//! Rules are named as for `mon lint --rules`, separated by spaces or commas;
+//! a directive that names none silences every rule. Anything after ` -- ` is
+//! the reason for the suppression. Diagnostics are matched by the line their
+//! range starts on, so diagnostics without a range are only silenced by
+//! `mon-lint-disable-file`.
+
+use crate::linter::edit::{Fix, TextEdit};
+use crate::linter::rules::select_rules;
+use crate::linter::source_index::{SourceIndex, removal_span};
+use crate::linter::{DiagnosticCode, DiagnosticTag, LintConfig, LintResult, Position, Range};
+use mon_core::lexer::TokenType;
+use std::ops::Range as ByteRange;
+
+const PREFIX: &str = "mon-lint-";
+
+/// Lines a directive applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    /// Zero-based lines, inclusive
+    Lines(u32, u32),
+    File,
+}
+
+#[derive(Debug)]
+struct Suppression {
+    /// The directive as written, without its reason
+    directive: String,
+    /// Byte span of the comment
+    span: ByteRange<usize>,
+    /// Silenced codes, or `None` for every rule
+    codes: Option<Vec<DiagnosticCode>>,
+    scope: Scope,
+    used: bool,
+}
+
+impl Suppression {
+    fn matches(&self, code: DiagnosticCode, line: Option<u32>) -> bool {
+        let in_scope = match (self.scope, line) {
+            (Scope::File, _) => true,
+            (Scope::Lines(first, last), Some(line)) => first <= line && line <= last,
+            (Scope::Lines(..), None) => false,
+        };
+        in_scope && self.codes.as_ref().is_none_or(|codes| codes.contains(&code))
+    }
+}
+
+/// A comment that looks like a directive but cannot be used.
+struct Invalid {
+    span: ByteRange<usize>,
+    message: String,
+}
+
+/// Removes the diagnostics silenced by suppression comments in `source`,
+/// then reports the comments that silenced nothing.
+pub fn apply(source: &str, config: &LintConfig, result: &mut LintResult) {
+    let (mut suppressions, invalid) = parse(source);
+
+    result.diagnostics.retain(|diagnostic| {
+        let line = diagnostic.range.map(|range| range.start.line);
+        let mut suppressed = false;
+        for suppression in suppressions.iter_mut() {
+            if suppression.matches(diagnostic.code, line) {
+                suppression.used = true;
+                suppressed = true;
+            }
+        }
+        !suppressed
+    });
+
+    if !config.is_enabled(DiagnosticCode::UnusedSuppression) {
+        return;
+    }
+
+    // A suppression of a rule that did not run may still be needed
+    let unused = suppressions.into_iter().filter(|suppression| {
+        !suppression.used
+            && suppression
+                .codes
+                .as_ref()
+                .is_none_or(|codes| codes.iter().all(|code| config.is_enabled(*code)))
+    });
+    let mut reports: Vec<_> = unused
+        .map(|suppression| Invalid {
+            message: format!("'{}' does not suppress anything", suppression.directive),
+            span: suppression.span,
+        })
+        .chain(invalid)
+        .collect();
+    reports.sort_by_key(|report| report.span.start);
+
+    for report in reports {
+        let removal = removal_span(source, &report.span);
+        let diagnostic = result.add_diagnostic_with_range(
+            DiagnosticCode::UnusedSuppression,
+            report.message,
+            Some(Range::from_byte_offsets(source, report.span.start, report.span.end)),
+            vec![],
+            vec![DiagnosticTag::Unnecessary],
+        );
+        diagnostic.fixes.push(Fix::new(
+            "Remove suppression comment",
+            vec![TextEdit::new(Range::from_byte_offsets(source, removal.start, removal.end), "")],
+        ));
+    }
+}
+
+/// Reads the directives of every comment, pairing `disable` with `enable`.
+fn parse(source: &str) -> (Vec<Suppression>, Vec<Invalid>) {
+    let index = SourceIndex::new(source);
+    let last_line = Position::from_byte_offset(source, source.len()).line;
+    let mut suppressions = Vec::new();
+    let mut invalid = Vec::new();
+    // Indices of `disable` regions that are still open
+    let mut open: Vec<usize> = Vec::new();
+
+    for comment in index.comments() {
+        let TokenType::Comment(text) = &comment.ttype else {
+            continue;
+        };
+        let directive = text.split(" -- ").next().unwrap_or_default().trim();
+        let Some(rest) = directive.strip_prefix(PREFIX) else {
+            continue;
+        };
+        let span = comment.pos_start..comment.pos_end;
+        let line = Position::from_byte_offset(source, comment.pos_start).line;
+
+        let (kind, ids) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let ids: Vec<String> = ids
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|id| !id.is_empty())
+            .map(String::from)
+            .collect();
+
+        let mut codes = Vec::new();
+        for id in &ids {
+            match select_rules(std::slice::from_ref(id)) {
+                Ok(selected) => codes.extend(selected),
+                Err(_) => invalid.push(Invalid {
+                    span: span.clone(),
+                    message: format!("Unknown lint rule '{}' in suppression comment", id),
+                }),
+            }
+        }
+        if !ids.is_empty() && codes.is_empty() {
+            continue;
+        }
+        let codes = (!ids.is_empty()).then_some(codes);
+
+        let scope = match kind {
+            "disable-next-line" => Scope::Lines(line + 1, line + 1),
+            "disable" => {
+                open.push(suppressions.len());
+                Scope::Lines(line, last_line)
+            }
+            "disable-file" => Scope::File,
+            "enable" => {
+                // Closes the regions whose rules are all re-enabled
+                open.retain(|&i| {
+                    let region: &mut Suppression = &mut suppressions[i];
+                    let closes = match (&codes, &region.codes) {
+                        (None, _) => true,
+                        (Some(enabled), Some(disabled)) => {
+                            disabled.iter().all(|code| enabled.contains(code))
+                        }
+                        (Some(_), None) => false,
+                    };
+                    if closes && let Scope::Lines(first, _) = region.scope {
+                        region.scope = Scope::Lines(first, line);
+                    }
+                    !closes
+                });
+                continue;
+            }
+            _ => {
+                invalid.push(Invalid {
+                    span,
+                    message: format!("Unknown suppression directive '{}{}'", PREFIX, kind),
+                });
+                continue;
+            }
+        };
+
+        suppressions.push(Suppression {
+            directive: directive.to_string(),
+            span,
+            codes,
+            scope,
+            used: false,
+        });
+    }
+
+    (suppressions, invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::Linter;
+    use crate::linter::edit;
+    use mon_core::parser::Parser;
+
+    fn lint(source: &str) -> LintResult {
+        let doc = Parser::new(source).unwrap().parse_document().unwrap();
+        Linter::new(LintConfig::default()).lint(&doc, source).unwrap()
+    }
+
+    fn codes(result: &LintResult) -> Vec<(String, u32)> {
+        let mut codes: Vec<_> = result
+            .diagnostics
+            .iter()
+            .map(|d| (d.code.to_string(), d.range.map_or(u32::MAX, |r| r.start.line)))
+            .collect();
+        codes.sort();
+        codes
+    }
+
+    #[test]
+    fn test_directives_silence_matching_diagnostics() {
+        let source = r#"{
+    // mon-lint-disable-next-line LINT2001 -- kept for consumers
+    &base: { a: 1 },
+    // mon-lint-disable duplicate_key
+    k: 1,
+    k: 2,
+    // mon-lint-enable duplicate_key
+    j: 1,
+    j: 2,
+    &other: { b: 1 },
+}"#;
+        assert_eq!(
+            codes(&lint(source)),
+            vec![("LINT2001".to_string(), 9), ("LINT2002".to_string(), 8)]
+        );
+
+        let file = format!("// mon-lint-disable-file LINT2*\n{}", source);
+        assert!(lint(&file).diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_reports_unused_suppressions() {
+        let source = r#"{
+    // mon-lint-disable-next-line LINT2001
+    port: 80, // mon-lint-disable-next-line LINT2002
+    // mon-lint-disable-next-line no_such_rule
+    // mon-lint-disable-nextline
+    name: "x",
+}"#;
+        let result = lint(source);
+        let messages: Vec<_> = result.diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "'mon-lint-disable-next-line LINT2001' does not suppress anything",
+                "'mon-lint-disable-next-line LINT2002' does not suppress anything",
+                "Unknown lint rule 'no_such_rule' in suppression comment",
+                "Unknown suppression directive 'mon-lint-disable-nextline'",
+            ]
+        );
+
+        // The fixes remove standalone comments with their line
+        let edits: Vec<_> =
+            result.diagnostics[..2].iter().map(|d| d.fixes[0].edits[0].clone()).collect();
+        let expected = source
+            .replace("    // mon-lint-disable-next-line LINT2001\n", "")
+            .replace(" // mon-lint-disable-next-line LINT2002", "");
+        assert_eq!(edit::apply(source, &edits), expected);
+    }
+}
@@ -4,7 +4,7 @@
 //! and imports in a MON document along with their source locations. This enables
 //! LSP features like go-to-definition, find references, and hover information.
 
-use crate::linter::position::Range;
+use crate::linter::position::{Position, Range};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -15,8 +15,11 @@ use std::collections::HashMap;
 /// let symbol = Symbol {
 ///     name: "base_user".to_string(),
 ///     kind: SymbolKind::Anchor,
-///     range: Range::new(Position::new(2, 4), Position::new(2, 13)),
-///     detail: Some("{ id: 1, name: \"Alice\" }".to_string()),
+///     range: Range::new(Position::new(2, 4), Position::new(2, 40)),
+///     selection_range: Range::new(Position::new(2, 5), Position::new(2, 14)),
+///     detail: Some("{ ... }".to_string()),
+///     documentation: Some("The default user".to_string()),
+///     value: Some("{\n    id: 1,\n    name: \"Alice\",\n}".to_string()),
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,16 +30,24 @@ pub struct Symbol {
     /// The kind of symbol
     pub kind: SymbolKind,
 
-    /// The range where this symbol is defined
+    /// The full range of the definition (e.g., from `&name` to the end of its value)
     pub range: Range,
 
+    /// The range of the symbol's name within `range`, used for navigation
+    pub selection_range: Range,
+
     /// Optional detail text (e.g., type signature, value preview)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub detail: Option<String>,
 
-    /// Optional documentation string
+    /// Optional documentation string (the `//` comment block above the definition)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub documentation: Option<String>,
+
+    /// MON source shown on hover: the resolved value of an anchor, or the
+    /// full definition of a type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
 }
 
 /// The kind of symbol.
@@ -88,8 +99,47 @@ pub enum ReferenceKind {
     /// Type annotation (:: Type)
     TypeAnnotation,
 
-    /// Import usage
+    /// Import usage (a name listed in `import { ... }`)
     Import,
+
+    /// Enum value usage ($Enum.Variant)
+    EnumValue,
+}
+
+/// What a position in the document points at: a definition or a usage.
+#[derive(Debug, Clone, Copy)]
+pub enum SymbolAt<'a> {
+    /// The position is on the name of a definition
+    Definition(&'a Symbol),
+
+    /// The position is on a reference to a symbol
+    Reference(&'a SymbolReference),
+}
+
+impl SymbolAt<'_> {
+    /// Name of the symbol defined or referenced.
+    pub fn name(&self) -> &str {
+        match self {
+            SymbolAt::Definition(symbol) => &symbol.name,
+            SymbolAt::Reference(reference) => &reference.symbol_name,
+        }
+    }
+
+    /// Kind of the symbol defined or referenced.
+    pub fn kind(&self) -> SymbolKind {
+        match self {
+            SymbolAt::Definition(symbol) => symbol.kind,
+            SymbolAt::Reference(reference) => reference.symbol_kind,
+        }
+    }
+
+    /// Range of the name under the position.
+    pub fn range(&self) -> Range {
+        match self {
+            SymbolAt::Definition(symbol) => symbol.selection_range,
+            SymbolAt::Reference(reference) => reference.range,
+        }
+    }
 }
 
 /// Symbol table tracking all definitions and references in a document.
@@ -108,7 +158,8 @@ pub enum ReferenceKind {
 /// table.add_symbol(Symbol {
 ///     name: "base_user".to_string(),
 ///     kind: SymbolKind::Anchor,
-///     range: Range::new(Position::new(2, 4), Position::new(2, 13)),
+///     range: Range::new(Position::new(2, 4), Position::new(2, 25)),
+///     selection_range: Range::new(Position::new(2, 5), Position::new(2, 14)),
 ///     detail: Some("{ id: 1 }".to_string()),
 /// });
 ///
@@ -168,6 +219,36 @@ impl SymbolTable {
         self.references.get(&(name.to_string(), kind)).map(|v| v.as_slice()).unwrap_or(&[])
     }
 
+    /// Finds the definition or reference whose name contains `position`.
+    ///
+    /// This is the entry point for "go to definition" and "find references":
+    /// the cursor can be on either an anchor/type name or on a usage of one.
+    pub fn find_symbol_at(&self, position: Position) -> Option<SymbolAt<'_>> {
+        let reference = self
+            .references
+            .values()
+            .flatten()
+            .find(|reference| reference.range.contains(position))
+            .map(SymbolAt::Reference);
+
+        reference.or_else(|| {
+            self.symbols
+                .values()
+                .find(|symbol| symbol.selection_range.contains(position))
+                .map(SymbolAt::Definition)
+        })
+    }
+
+    /// Gets every symbol definition in the table, in no particular order.
+    pub fn all_symbols(&self) -> impl Iterator<Item = &Symbol> {
+        self.symbols.values()
+    }
+
+    /// Gets every reference in the table, in no particular order.
+    pub fn all_references(&self) -> impl Iterator<Item = &SymbolReference> {
+        self.references.values().flatten()
+    }
+
     /// Gets all symbols of a specific kind.
     ///
     /// Useful for "document symbols" LSP feature (outline view).
@@ -183,6 +264,7 @@ impl SymbolTable {
     /// Gets all unused symbols.
     ///
     /// Useful for "unused anchor" and "unused import" diagnostics.
+    #[cfg(test)]
     pub fn find_unused_symbols(&self, kind: SymbolKind) -> Vec<&Symbol> {
         self.symbols
             .iter()
@@ -191,18 +273,14 @@ impl SymbolTable {
             .collect()
     }
 
-    /// Clears all symbols and references.
-    pub fn clear(&mut self) {
-        self.symbols.clear();
-        self.references.clear();
-    }
-
     /// Gets the total number of symbols.
+    #[cfg(test)]
     pub fn symbol_count(&self) -> usize {
         self.symbols.len()
     }
 
     /// Gets the total number of references.
+    #[cfg(test)]
     pub fn reference_count(&self) -> usize {
         self.references.values().map(|v| v.len()).sum()
     }
@@ -211,7 +289,6 @@ impl SymbolTable {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::linter::position::Position;
 
     #[test]
     fn test_symbol_table_basic() {
@@ -221,8 +298,10 @@ mod tests {
             name: "test_anchor".to_string(),
             kind: SymbolKind::Anchor,
             range: Range::new(Position::new(0, 0), Position::new(0, 5)),
+            selection_range: Range::new(Position::new(0, 0), Position::new(0, 5)),
             detail: None,
             documentation: None,
+            value: None,
         };
 
         table.add_symbol(symbol.clone());
@@ -240,8 +319,10 @@ mod tests {
             name: "anchor".to_string(),
             kind: SymbolKind::Anchor,
             range: Range::new(Position::new(0, 0), Position::new(0, 5)),
+            selection_range: Range::new(Position::new(0, 0), Position::new(0, 5)),
             detail: None,
             documentation: None,
+            value: None,
         });
 
         table.add_reference(SymbolReference {
@@ -272,8 +353,10 @@ mod tests {
             name: "used".to_string(),
             kind: SymbolKind::Anchor,
             range: Range::new(Position::new(0, 0), Position::new(0, 4)),
+            selection_range: Range::new(Position::new(0, 0), Position::new(0, 4)),
             detail: None,
             documentation: None,
+            value: None,
         });
         table.add_reference(SymbolReference {
             symbol_name: "used".to_string(),
@@ -287,8 +370,10 @@ mod tests {
             name: "unused".to_string(),
             kind: SymbolKind::Anchor,
             range: Range::new(Position::new(2, 0), Position::new(2, 6)),
+            selection_range: Range::new(Position::new(2, 0), Position::new(2, 6)),
             detail: None,
             documentation: None,
+            value: None,
         });
 
         assert!(!table.is_unused("used", SymbolKind::Anchor));
@@ -54,7 +54,7 @@ fn test_linter_large_object() {
     for i in 0..25 {
         source.push_str(&format!("    key{}: {},\n", i, i));
     }
-    source.push_str("}");
+    source.push('}');
 
     let config = LintConfig { max_object_members: 20, ..Default::default() };
 
@@ -209,3 +209,508 @@ fn test_lint_result_filtering() {
     assert_eq!(result.warnings().len(), 1, "Should have 1 warning");
     assert_eq!(result.infos().len(), 0, "Should have 0 infos");
 }
+
+// Helper to apply the first fix of every diagnostic with the given code
+fn apply_fixes(source: &str, result: &LintResult, code: DiagnosticCode) -> String {
+    let edits: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.code == code)
+        .filter_map(|d| d.fixes.first())
+        .flat_map(|fix| fix.edits.clone())
+        .collect();
+    edit::apply(source, &edits)
+}
+
+#[test]
+fn test_unused_anchor_fix_keeps_value() {
+    let source = "{\n    &unused: { x: 1 },\n    copy: &spare { y: 2 },\n    list: [&item 3],\n}";
+
+    let result = lint_source(source, LintConfig::default());
+
+    // Verify behavior: anchors are removed, keys and values stay
+    assert_eq!(
+        apply_fixes(source, &result, DiagnosticCode::UnusedAnchor),
+        "{\n    unused: { x: 1 },\n    copy: { y: 2 },\n    list: [3],\n}"
+    );
+}
+
+#[test]
+fn test_duplicate_key_fix_removes_earlier() {
+    let source = "{\n    key: \"v1\",\n    other: 1,\n    key: \"v2\",\n}";
+
+    let result = lint_source(source, LintConfig::default());
+    let diagnostic = &result.errors()[0];
+
+    // Verify behavior: points at the later key, fix drops the earlier one
+    assert_eq!(diagnostic.range.unwrap().start, Position::new(3, 4));
+    assert_eq!(
+        apply_fixes(source, &result, DiagnosticCode::DuplicateKey),
+        "{\n    other: 1,\n    key: \"v2\",\n}"
+    );
+}
+
+#[test]
+fn test_duplicate_key_fix_keeps_comments_of_next_member() {
+    let source = "{\n    timeout: 30, // old\n    // Timeout in seconds, agreed with ops\n    timeout: 60,\n    retries: { a: 1, a: 2 },\n}";
+
+    let result = lint_source(source, LintConfig::default());
+
+    // Verify behavior: the earlier line goes, with its trailing comment only
+    assert_eq!(
+        apply_fixes(source, &result, DiagnosticCode::DuplicateKey),
+        "{\n    // Timeout in seconds, agreed with ops\n    timeout: 60,\n    retries: { a: 2 },\n}"
+    );
+}
+
+#[test]
+fn test_empty_structure_fix_removes_member() {
+    let source = "{\n    name: \"app\",\n    tags: [],\n}";
+
+    let result = lint_source(source, LintConfig::default());
+
+    // Verify behavior: the empty member goes with its comma
+    assert_eq!(
+        apply_fixes(source, &result, DiagnosticCode::EmptyObject),
+        "{\n    name: \"app\",\n}"
+    );
+}
+
+#[test]
+fn test_inconsistent_naming_fix_uses_dominant_style() {
+    let source = "{\n    max_retries: 3,\n    retry_delay: 5,\n    timeoutMs: 100,\n}";
+
+    let result = lint_source(source, LintConfig::default());
+    let diagnostic = &result.infos()[0];
+
+    // Verify behavior: the camelCase key is converted to snake_case
+    assert_eq!(diagnostic.fixes[0].title, "Rename 1 key(s) to snake_case");
+    assert_eq!(
+        apply_fixes(source, &result, DiagnosticCode::InconsistentNaming),
+        source.replace("timeoutMs", "timeout_ms")
+    );
+
+    // A tie has no dominant style, so there is nothing to convert to
+    let tie = "{ a_b: 1, aB2: 2 }";
+    assert!(lint_source(tie, LintConfig::default()).infos()[0].fixes.is_empty());
+}
+
+#[test]
+fn test_only_rules_runs_selected_checks() {
+    let source = "{\n    &unused: 1,\n    k: 1,\n    k: 2,\n    n: 8080,\n}";
+
+    let config = LintConfig::default().with_only_rules(vec!["LINT2002".to_string()]).unwrap();
+    let result = lint_source(source, config);
+
+    // Verify behavior: only duplicate keys are reported
+    assert_eq!(result.diagnostics.len(), 1);
+    assert_eq!(count_by_code(&result, DiagnosticCode::DuplicateKey), 1);
+
+    // Selecting an opt-in rule turns it on
+    let config = LintConfig::default().with_only_rules(vec!["magic_number".to_string()]).unwrap();
+    let result = lint_source(source, config);
+    assert_eq!(count_by_code(&result, DiagnosticCode::MagicNumber), 2);
+    assert_eq!(count_by_code(&result, DiagnosticCode::DuplicateKey), 0);
+}
+
+#[test]
+fn test_without_rules_skips_checks() {
+    let source = "{\n    &unused: 1,\n    k: 1,\n    k: 2,\n}";
+
+    let config = LintConfig::default().without_rules(vec!["unused-anchor".to_string()]).unwrap();
+    let result = lint_source(source, config);
+
+    // Verify behavior: the unused anchor is no longer reported
+    assert_eq!(count_by_code(&result, DiagnosticCode::UnusedAnchor), 0);
+    assert_eq!(count_by_code(&result, DiagnosticCode::DuplicateKey), 1);
+
+    assert!(LintConfig::default().without_rules(vec!["LINT7*".to_string()]).is_err());
+}
+
+#[test]
+fn test_severity_overrides() {
+    let source = "{\n    k: 1,\n    k: 2,\n    port: 8080,\n}";
+
+    let config = LintConfig::default()
+        .with_level(&["LINT2002".to_string()], Some(DiagnosticSeverity::Info))
+        .unwrap()
+        .with_level(&["magic_number".to_string()], Some(DiagnosticSeverity::Error))
+        .unwrap();
+    let result = lint_source(source, config);
+
+    // Verify behavior: the duplicate key is downgraded, magic numbers are errors
+    assert!(result.errors().iter().all(|d| d.code == DiagnosticCode::MagicNumber));
+    assert_eq!(result.errors().len(), 2);
+    assert_eq!(result.infos()[0].code, DiagnosticCode::DuplicateKey);
+}
+
+#[test]
+fn test_diagnostics_carry_ranges_and_related_information() {
+    let source = r#"{
+    k: 1,
+    k: 2,
+    k: 3,
+    port: 8080,
+    &a: { x: 1 },
+    &b: { y: 2 },
+    &c: { z: 3 },
+    &d: { w: 4 },
+    result: { ...*a, ...*b, ...*c, ...*d },
+}"#;
+    let config = LintConfig { warn_magic_numbers: true, ..Default::default() };
+    let result = lint_source(source, config);
+    let text = |range: Range| {
+        source[range.start.to_byte_offset(source)..range.end.to_byte_offset(source)].to_string()
+    };
+
+    // Verify behavior: every diagnostic has a range
+    assert!(result.diagnostics.iter().all(|d| d.range.is_some()));
+
+    // Verify behavior: each duplicate refers back to the first occurrence
+    let duplicates: Vec<_> =
+        result.diagnostics.iter().filter(|d| d.code == DiagnosticCode::DuplicateKey).collect();
+    assert_eq!(duplicates.len(), 2);
+    for duplicate in duplicates {
+        assert_eq!(duplicate.related_information[0].location.range.start, Position::new(1, 4));
+    }
+
+    let magic = result.diagnostics.iter().find(|d| d.message.contains("8080")).unwrap();
+    assert_eq!(text(magic.range.unwrap()), "8080");
+
+    // Verify behavior: spreads point at the object's key and list each spread
+    let spreads =
+        result.diagnostics.iter().find(|d| d.code == DiagnosticCode::ExcessiveSpreads).unwrap();
+    assert_eq!(text(spreads.range.unwrap()), "result");
+    let related: Vec<_> =
+        spreads.related_information.iter().map(|info| text(info.location.range)).collect();
+    assert_eq!(related, vec!["...*a", "...*b", "...*c", "...*d"]);
+}
+
+#[test]
+fn test_unused_imports() {
+    let source = r#"import { &defaults, &limits, User, Status } from "./shared.mon"
+import * as extra from "./extra.mon"
+import * as base from "./base.mon"
+{
+    server: { ...*defaults, mode: $Status.On },
+    admin :: User = { port: *base.port },
+}"#;
+    let config = LintConfig { warn_unused_imports: true, ..LintConfig::default() };
+    let result = lint_source(source, config.clone());
+
+    // Verify behavior: unused items are reported at the item, tagged unnecessary
+    let unused: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.code == DiagnosticCode::UnusedImport)
+        .map(|d| (d.message.as_str(), d.range.unwrap().start, d.tags.clone()))
+        .collect();
+    assert_eq!(
+        unused,
+        vec![
+            (
+                "Imported anchor '&limits' is never used",
+                Position::new(0, 20),
+                vec![DiagnosticTag::Unnecessary]
+            ),
+            (
+                "Namespace 'extra' is never used",
+                Position::new(1, 12),
+                vec![DiagnosticTag::Unnecessary]
+            ),
+        ]
+    );
+    assert_eq!(
+        apply_fixes(source, &result, DiagnosticCode::UnusedImport),
+        source.replace("&limits, ", "").replace("import * as extra from \"./extra.mon\"\n", "")
+    );
+
+    // Off by default
+    assert_eq!(
+        count_by_code(&lint_source(source, LintConfig::default()), DiagnosticCode::UnusedImport),
+        0
+    );
+
+    // Removing every item of a statement removes the statement
+    let source = "import { &a, B } from \"./x.mon\"\n{\n    k: 1,\n}";
+    let result = lint_source(source, config.clone());
+    assert_eq!(apply_fixes(source, &result, DiagnosticCode::UnusedImport), "{\n    k: 1,\n}");
+
+    // Files importing this one may get '&a' through it, so `--fix` keeps it
+    let outcome = Linter::new(config).fix(source, None).unwrap();
+    assert_eq!(outcome.source, source);
+    assert_eq!(count_by_code(&outcome.remaining, DiagnosticCode::UnusedImport), 2);
+}
+
+#[test]
+fn test_import_chains_and_cycles() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let write = |name: &str, source: &str| {
+        let path = dir.path().join(name);
+        std::fs::write(&path, source).unwrap();
+        path
+    };
+    write("defaults.mon", "{\n    &server: { port: 80 },\n}");
+    write("shared.mon", "import { &server } from \"./defaults.mon\"\n{}");
+    write("base.mon", "import { &server } from \"./shared.mon\"\n{}");
+    write("b.mon", "import * as app from \"./app.mon\"\n{}");
+    let source = r#"import { &server } from "./base.mon"
+import * as b from "./b.mon"
+{
+    app: *server,
+}"#;
+    let path = write("app.mon", source);
+    let doc = Parser::new(source).unwrap().parse_document().unwrap();
+    let result = Linter::new(LintConfig::default()).lint_file(&doc, source, &path).unwrap();
+
+    // Verify behavior: '&server' goes through 3 imports
+    let chains: Vec<_> =
+        result.diagnostics.iter().filter(|d| d.code == DiagnosticCode::DeepImportChain).collect();
+    assert_eq!(chains.len(), 1);
+    assert_eq!(
+        chains[0].message,
+        "'&server' is imported through 3 imports (max 2): \
+         app.mon -> base.mon -> shared.mon -> defaults.mon"
+    );
+    assert_eq!(chains[0].range.unwrap().start, Position::new(0, 10));
+    let related: Vec<_> =
+        chains[0].related_information.iter().map(|info| info.message.as_str()).collect();
+    assert_eq!(
+        related,
+        vec![
+            "Imported by 'base.mon' from here",
+            "Imported by 'shared.mon' from here",
+            "'&server' is defined here"
+        ]
+    );
+    assert!(chains[0].related_information[0].location.uri.starts_with("file://"));
+
+    // The cycle is reported at the import that starts it
+    let cycles: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.code == DiagnosticCode::CircularDependency)
+        .collect();
+    assert_eq!(cycles.len(), 1);
+    assert_eq!(cycles[0].message, "Import cycle: app.mon -> b.mon -> app.mon");
+    assert_eq!(cycles[0].range.unwrap().start, Position::new(1, 0));
+    assert_eq!(cycles[0].related_information[0].message, "'b.mon' imports 'app.mon' here");
+
+    // Without a path, imported files are not read
+    let result = Linter::new(LintConfig::default()).lint(&doc, source).unwrap();
+    assert_eq!(count_by_code(&result, DiagnosticCode::DeepImportChain), 0);
+    assert_eq!(count_by_code(&result, DiagnosticCode::CircularDependency), 0);
+}
+
+#[test]
+fn test_missing_type_validation() {
+    let source = r#"{
+    User: #struct { name(String), admin(Boolean) = false },
+    Team: #struct { name(String), size(Number) },
+    alice :: User = { name: "alice" },
+    bob: { name: "bob", admin: true },
+    users: [{ name: "carol" }, { name: "dave" }],
+    labels: { name: "x", color: "red" },
+}"#;
+
+    let config = LintConfig { suggest_type_validation: true, ..Default::default() };
+    let result = lint_source(source, config.clone());
+
+    // Verify behavior: shapes that fit 'User' are flagged, other objects are not
+    let messages: Vec<_> = result.diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "'bob' matches struct 'User' but has no type validation",
+            "'users' holds objects matching struct 'User' but has no type validation",
+        ]
+    );
+    assert_eq!(
+        result.diagnostics[0].related_information[0].message,
+        "'alice' is validated as 'User' here"
+    );
+    assert_eq!(
+        apply_fixes(source, &result, DiagnosticCode::MissingTypeValidation),
+        source.replace("bob: {", "bob :: User: {").replace("users: [", "users :: [User...]: [")
+    );
+
+    // Two matching structs offer both, and neither is applied by --fix
+    let source =
+        "{\n    A: #struct { id(Number) },\n    B: #struct { id(Number) },\n    x: { id: 1 },\n}";
+    let result = lint_source(source, config.clone());
+    assert_eq!(
+        result.diagnostics[0].message,
+        "'x' matches structs 'A', 'B' but has no type validation"
+    );
+    assert_eq!(result.diagnostics[0].fixes.len(), 2);
+    assert_eq!(Linter::new(config).fix(source, None).unwrap().source, source);
+}
+
+#[test]
+fn test_configured_naming_styles() {
+    let source = r#"{
+    Status: #enum { active, Inactive },
+    user_profile: #struct { userName(String) },
+    &BaseConfig: { retries: 3 },
+    maxRetries: 5,
+    copy: *BaseConfig,
+    state: $Status.active,
+    owner :: user_profile = { userName: "root" },
+    headers: { "user-agent": "mon", acceptLanguage: "en" },
+}"#;
+    let doc = Parser::new(source).unwrap().parse_document().unwrap();
+    let config_source = r#"{
+    enforce_naming_convention: {
+        keys: "snake_case",
+        anchors: "snake_case",
+        types: "PascalCase",
+        fields: "snake_case",
+        variants: "PascalCase",
+        exempt: ["headers"],
+    },
+}"#;
+    let config_doc = Parser::new(config_source).unwrap().parse_document().unwrap();
+    let config = LintConfig::from_document(&config_doc, config_source).unwrap();
+    let result = Linter::new(config).lint(&doc, source).unwrap();
+
+    // Verify behavior: each kind of name has its own style, 'headers' is exempt
+    let mut messages: Vec<_> = result.diagnostics.iter().map(|d| d.message.as_str()).collect();
+    messages.sort();
+    assert_eq!(
+        messages,
+        vec![
+            "Field 'userName' of 'user_profile' is not snake_case",
+            "Key 'maxRetries' is not snake_case",
+            "Key and anchor 'BaseConfig' are not snake_case",
+            "Type 'user_profile' is not PascalCase",
+            "Variant 'active' of 'Status' is not PascalCase",
+        ]
+    );
+
+    // Renames reach the usages in the document; `&BaseConfig:` is key and anchor at once
+    let edits: Vec<_> = result.diagnostics.iter().flat_map(|d| d.fixes.first()).collect();
+    assert_eq!(edits.len(), 5);
+    let apply = |title: &str| {
+        let fix = edits.iter().find(|fix| fix.title == title).unwrap();
+        edit::apply(source, &fix.edits)
+    };
+    assert_eq!(
+        apply("Rename 'user_profile' to 'UserProfile'"),
+        source.replace("user_profile", "UserProfile")
+    );
+    assert_eq!(apply("Rename 'userName' to 'user_name'"), source.replace("userName", "user_name"));
+    assert_eq!(
+        apply("Rename 'active' to 'Active'"),
+        source.replace("active,", "Active,").replace(".active", ".Active")
+    );
+    assert_eq!(
+        apply("Rename 'maxRetries' to 'max_retries'"),
+        source.replace("maxRetries", "max_retries")
+    );
+    assert_eq!(
+        apply("Rename 'BaseConfig' to 'base_config'"),
+        source.replace("BaseConfig", "base_config")
+    );
+
+    // Quoted keys are only checked on request, and stay quoted
+    let source = r#"{ "userAgent": "mon", base: &Item 1, copy: *Item }"#;
+    let config = LintConfig {
+        enforce_naming_convention: config::NamingConvention {
+            keys: Some(naming::NamingStyle::Kebab),
+            anchors: Some(naming::NamingStyle::Camel),
+            exempt_quoted_keys: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let result = lint_source(source, config);
+    assert_eq!(
+        apply_fixes(source, &result, DiagnosticCode::InconsistentNaming),
+        r#"{ "user-agent": "mon", base: &item 1, copy: *item }"#
+    );
+
+    // No name suits both styles of a `&key`
+    let source = "{ &MyBase: 1, copy: *MyBase }";
+    let config = LintConfig {
+        enforce_naming_convention: config::NamingConvention {
+            keys: Some(naming::NamingStyle::Snake),
+            anchors: Some(naming::NamingStyle::Camel),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let result = lint_source(source, config);
+    assert_eq!(result.diagnostics.len(), 1);
+    assert_eq!(
+        result.diagnostics[0].message,
+        "Key 'MyBase' is not snake_case, and anchor is not camelCase"
+    );
+    assert!(result.diagnostics[0].fixes.is_empty());
+}
+
+#[test]
+fn test_overridden_keys() {
+    let source = r#"{
+    &base: { retries: 3, log: "info" },
+    &defaults: { ...*base, timeout: 30 },
+    &extra: { timeout: 10 },
+    job: {
+        timeout: 60,
+        ...*defaults,
+        ...*extra,
+        log: "debug",
+    },
+}"#;
+    let messages = |result: &LintResult| -> Vec<String> {
+        result
+            .diagnostics
+            .iter()
+            .filter(|d| d.code == DiagnosticCode::OverriddenKey)
+            .map(|d| d.message.clone())
+            .collect()
+    };
+
+    // Verify behavior: spreads overriding a key or another spread are reported
+    let result = lint_source(source, LintConfig::default());
+    assert_eq!(
+        messages(&result),
+        vec![
+            "Spread of '*defaults' overrides 'timeout' set before it",
+            "Spread of '*extra' overrides 'timeout' from '*defaults'",
+        ]
+    );
+    let related: Vec<_> = result.diagnostics[1]
+        .related_information
+        .iter()
+        .map(|info| info.message.as_str())
+        .collect();
+    assert_eq!(
+        related,
+        vec![
+            "'...*defaults' is spread here",
+            "'timeout' of '*defaults' is defined here",
+            "'timeout' of '*extra' is defined here",
+        ]
+    );
+    assert_eq!(result.diagnostics[1].related_information[1].location.range.start.line, 2);
+
+    // Keys written after a spread are reported when not allowed, through nested spreads
+    let config = LintConfig { allow_explicit_overrides: false, ..Default::default() };
+    let result = lint_source(source, config);
+    assert_eq!(messages(&result)[2], "'log' overrides the value spread from '*defaults'");
+    assert_eq!(result.diagnostics[2].related_information[1].location.range.start.line, 1);
+
+    // Spreads of imported anchors are resolved when the file is known
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("base.mon"), "{\n    &base: { retries: 3 },\n}").unwrap();
+    let source = r#"import { &base } from "./base.mon"
+{
+    job: { retries: 5, ...*base },
+}"#;
+    let path = dir.path().join("app.mon");
+    let doc = Parser::new(source).unwrap().parse_document().unwrap();
+    let result = Linter::new(LintConfig::default()).lint_file(&doc, source, &path).unwrap();
+    assert_eq!(messages(&result), vec!["Spread of '*base' overrides 'retries' set before it"]);
+    assert!(result.diagnostics[0].related_information[1].location.uri.ends_with("base.mon"));
+    assert!(messages(&lint_source(source, LintConfig::default())).is_empty());
+}
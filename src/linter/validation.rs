@@ -0,0 +1,339 @@
+// Type validation suggestions for MON files
+
+use crate::linter::edit::{Fix, TextEdit};
+use crate::linter::import_graph::{Hop, ImportGraph};
+use crate::linter::navigation::{resolve_import_path, type_definitions};
+use crate::linter::position::Location;
+use crate::linter::source_index::{MemberSpan, SourceIndex};
+use crate::linter::{
+    LintConfig, LintResult, Range, RelatedInformation, SymbolKind, diagnostic::DiagnosticCode,
+};
+use mon_core::ast::{
+    ImportSpec, Member, MonDocument, MonValue, MonValueKind, Pair, StructDef, TypeDef,
+    TypeDefinition, TypeSpec,
+};
+use std::collections::HashSet;
+use std::ops::Range as ByteRange;
+use std::path::Path;
+
+pub struct ValidationAnalyzer {
+    config: LintConfig,
+}
+
+/// A struct in scope that values can be validated against.
+struct Candidate {
+    /// Name as written after `::`, `ns.Type` for namespace imports
+    name: String,
+    def: StructDef,
+    /// Where the struct is defined
+    location: Option<Location>,
+}
+
+impl ValidationAnalyzer {
+    pub fn new(config: LintConfig) -> Self {
+        Self { config }
+    }
+
+    /// Suggests `:: Struct` annotations for unvalidated objects, and arrays
+    /// of objects, shaped like a struct in scope. Imported structs are only
+    /// known when the document's `path` is.
+    pub fn analyze(
+        &self,
+        doc: &MonDocument,
+        source: &str,
+        path: Option<&Path>,
+        result: &mut LintResult,
+    ) {
+        if !self.config.suggest_type_validation
+            || !self.config.is_enabled(DiagnosticCode::MissingTypeValidation)
+        {
+            return;
+        }
+
+        let index = SourceIndex::new(source);
+        let mut candidates: Vec<_> = type_definitions(&doc.root)
+            .into_iter()
+            .filter_map(|typedef| {
+                let span = name_span(typedef);
+                let range = Range::from_byte_offsets(source, span.start, span.end);
+                candidate(typedef.name.clone(), typedef, Some(Location::new(String::new(), range)))
+            })
+            .collect();
+        if let Some(path) = path.filter(|_| !doc.imports.is_empty()) {
+            candidates.extend(imported_structs(doc, source, &index, path));
+        }
+        if candidates.is_empty() {
+            return;
+        }
+
+        let checker = Checker { source, index: &index, candidates: &candidates };
+        checker.value(&doc.root, result);
+    }
+}
+
+/// The structs `doc` imports by name, and those of the files it imports as a
+/// namespace.
+fn imported_structs(
+    doc: &MonDocument,
+    source: &str,
+    index: &SourceIndex,
+    path: &Path,
+) -> Vec<Candidate> {
+    let mut graph = ImportGraph::new(path, doc, source);
+    let root = graph.root().to_path_buf();
+    let mut candidates: Vec<Candidate> = Vec::new();
+
+    for import in &doc.imports {
+        let target = resolve_import_path(&import.path, &root);
+        match &import.spec {
+            ImportSpec::Named(specifiers) => {
+                let spans = index.import_name_spans(import);
+                for (specifier, span) in specifiers.iter().zip(spans) {
+                    if specifier.is_anchor {
+                        continue;
+                    }
+                    let hop = Hop { file: root.clone(), span, target: target.clone() };
+                    let Some(chain) = graph.chain(hop, &specifier.name, SymbolKind::Type) else {
+                        continue;
+                    };
+                    let Some(typedef) = graph.type_definition(&chain) else {
+                        continue;
+                    };
+                    let defining = &chain.hops[chain.hops.len() - 1].target;
+                    let location = graph.location(defining, chain.definition.clone());
+                    candidates.extend(candidate(specifier.name.clone(), &typedef, location));
+                }
+            }
+            ImportSpec::Namespace(namespace) => {
+                for typedef in graph.type_definitions(&target) {
+                    let location = graph.location(&target, name_span(&typedef));
+                    let name = format!("{}.{}", namespace, typedef.name);
+                    candidates.extend(candidate(name, &typedef, location));
+                }
+            }
+        }
+    }
+    // A struct imported both by name and through a namespace is offered once
+    let mut seen = Vec::new();
+    candidates.retain(|candidate| match &candidate.location {
+        Some(location) => {
+            let key = (location.uri.clone(), location.range);
+            let first = !seen.contains(&key);
+            seen.push(key);
+            first
+        }
+        None => true,
+    });
+    candidates
+}
+
+fn candidate(
+    name: String,
+    typedef: &TypeDefinition,
+    location: Option<Location>,
+) -> Option<Candidate> {
+    match &typedef.def_type {
+        TypeDef::Struct(def) => Some(Candidate { name, def: def.clone(), location }),
+        TypeDef::Enum(_) => None,
+    }
+}
+
+fn name_span(typedef: &TypeDefinition) -> ByteRange<usize> {
+    let start = typedef.name_span.offset();
+    start..start + typedef.name_span.len()
+}
+
+struct Checker<'a> {
+    source: &'a str,
+    index: &'a SourceIndex,
+    candidates: &'a [Candidate],
+}
+
+impl Checker<'_> {
+    fn range(&self, span: ByteRange<usize>) -> Range {
+        Range::from_byte_offsets(self.source, span.start, span.end)
+    }
+
+    /// Checks the unvalidated pairs under `value`. A validated value is left
+    /// alone, since its type decides what it holds.
+    fn value(&self, value: &MonValue, result: &mut LintResult) {
+        match &value.kind {
+            MonValueKind::Object(members) => {
+                let spans = self.index.member_spans(value);
+                for (i, member) in members.iter().enumerate() {
+                    let Member::Pair(pair) = member else {
+                        continue;
+                    };
+                    if pair.validation.is_some() {
+                        continue;
+                    }
+                    if let Some(key) = spans.get(i).and_then(|span| span.name.clone()) {
+                        self.pair(pair, key, members, &spans, result);
+                    }
+                    self.value(&pair.value, result);
+                }
+            }
+            MonValueKind::Array(items) => {
+                for item in items {
+                    self.value(item, result);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn pair(
+        &self,
+        pair: &Pair,
+        key: ByteRange<usize>,
+        members: &[Member],
+        spans: &[MemberSpan],
+        result: &mut LintResult,
+    ) {
+        let array = match &pair.value.kind {
+            MonValueKind::Object(_) => false,
+            MonValueKind::Array(items) if !items.is_empty() => true,
+            _ => return,
+        };
+        let mut matching: Vec<&Candidate> = self
+            .candidates
+            .iter()
+            .filter(|candidate| match &pair.value.kind {
+                MonValueKind::Array(items) => {
+                    items.iter().all(|item| matches_struct(item, &candidate.def))
+                }
+                _ => matches_struct(&pair.value, &candidate.def),
+            })
+            .collect();
+        if matching.is_empty() {
+            return;
+        }
+
+        // Siblings validated as one of the matching structs settle which
+        let siblings: Vec<_> = members
+            .iter()
+            .zip(spans)
+            .filter_map(|(member, span)| match member {
+                Member::Pair(sibling) => {
+                    let name = validated_struct(sibling.validation.as_ref()?)?;
+                    matching.iter().any(|c| c.name == name).then_some((sibling, name, span))
+                }
+                _ => None,
+            })
+            .collect();
+        if !siblings.is_empty() {
+            matching.retain(|c| siblings.iter().any(|(_, name, _)| *name == c.name));
+        }
+
+        let names: Vec<_> = matching.iter().map(|c| format!("'{}'", c.name)).collect();
+        let structs = if names.len() == 1 { "struct" } else { "structs" };
+        let message = if array {
+            format!(
+                "'{}' holds objects matching {} {} but has no type validation",
+                pair.key,
+                structs,
+                names.join(", ")
+            )
+        } else {
+            format!(
+                "'{}' matches {} {} but has no type validation",
+                pair.key,
+                structs,
+                names.join(", ")
+            )
+        };
+
+        let mut related: Vec<_> = siblings
+            .iter()
+            .filter_map(|(sibling, name, span)| {
+                let message = format!("'{}' is validated as '{}' here", sibling.key, name);
+                Some(RelatedInformation::here(self.range(span.name.clone()?), message))
+            })
+            .collect();
+        related.extend(matching.iter().filter_map(|c| {
+            Some(RelatedInformation {
+                location: c.location.clone()?,
+                message: format!("Struct '{}' is defined here", c.name),
+            })
+        }));
+
+        let diagnostic = result.add_diagnostic_with_range(
+            DiagnosticCode::MissingTypeValidation,
+            message,
+            Some(self.range(key.clone())),
+            related,
+            vec![],
+        );
+        for candidate in matching {
+            let annotation =
+                if array { format!("[{}...]", candidate.name) } else { candidate.name.clone() };
+            diagnostic.fixes.push(Fix::new(
+                format!("Add ':: {}'", annotation),
+                vec![TextEdit::new(self.range(key.end..key.end), format!(" :: {}", annotation))],
+            ));
+        }
+    }
+}
+
+/// The struct name of a `:: Struct` or `:: [Struct...]` annotation.
+fn validated_struct(spec: &TypeSpec) -> Option<&str> {
+    match spec {
+        TypeSpec::Simple(name, _) => Some(name),
+        TypeSpec::Collection(types, _) => match types.as_slice() {
+            [TypeSpec::Spread(inner, _)] => validated_struct(inner),
+            _ => None,
+        },
+        TypeSpec::Spread(..) => None,
+    }
+}
+
+/// Whether an object has only fields of `def`, every field without a
+/// default, and values that fit the field types.
+fn matches_struct(value: &MonValue, def: &StructDef) -> bool {
+    let MonValueKind::Object(members) = &value.kind else {
+        return false;
+    };
+    let mut keys = HashSet::new();
+    for member in members {
+        match member {
+            Member::Pair(pair) => {
+                let Some(field) = def.fields.iter().find(|field| field.name == pair.key) else {
+                    return false;
+                };
+                if !fits(&pair.value, &field.type_spec) {
+                    return false;
+                }
+                keys.insert(pair.key.as_str());
+            }
+            // The keys of a spread are not known without resolving it
+            Member::Spread(_) => return false,
+            _ => {}
+        }
+    }
+    !keys.is_empty()
+        && def
+            .fields
+            .iter()
+            .all(|field| field.default_value.is_some() || keys.contains(field.name.as_str()))
+}
+
+/// Whether a value can be of a declared type, as far as it shows on its own.
+fn fits(value: &MonValue, spec: &TypeSpec) -> bool {
+    let kind = &value.kind;
+    match spec {
+        _ if matches!(kind, MonValueKind::Alias(_)) => true,
+        TypeSpec::Simple(name, _) => match name.as_str() {
+            "String" => matches!(kind, MonValueKind::String(_)),
+            "Number" => matches!(kind, MonValueKind::Number(_)),
+            "Boolean" => matches!(kind, MonValueKind::Boolean(_)),
+            "Null" => matches!(kind, MonValueKind::Null),
+            "Object" => matches!(kind, MonValueKind::Object(_)),
+            "Array" => matches!(kind, MonValueKind::Array(_)),
+            "Any" => true,
+            // A struct or an enum
+            _ => matches!(kind, MonValueKind::Object(_) | MonValueKind::EnumValue { .. }),
+        },
+        TypeSpec::Collection(..) => matches!(kind, MonValueKind::Array(_)),
+        TypeSpec::Spread(..) => true,
+    }
+}
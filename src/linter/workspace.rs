@@ -0,0 +1,154 @@
+//! Discovery of the `.mon` files that make up a project, and the symbol
+//! index built from them.
+
+use crate::linter::SymbolKind;
+use crate::linter::api::AnalysisService;
+use crate::linter::navigation::SymbolLocation;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directories never searched for `.mon` files.
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules"];
+
+/// Finds every `.mon` file under `root`, recursively.
+///
+/// Hidden directories and build output (`target`, `node_modules`) are
+/// skipped. Paths are canonical and sorted.
+pub fn mon_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    collect(root, &mut files);
+    files.sort();
+    files
+}
+
+/// An anchor, type, struct field or enum variant defined somewhere in the
+/// workspace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorkspaceSymbol {
+    /// Symbol name; fields and variants are qualified as `Type.member`
+    pub name: String,
+
+    /// Kind of the symbol
+    pub kind: SymbolKind,
+
+    /// Short description, e.g. a value preview or a field's type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+
+    /// Where the symbol's name is written
+    pub location: SymbolLocation,
+}
+
+/// Indexes `files` and returns the symbols whose name matches `query`.
+///
+/// A name matches when it contains the query's characters in order,
+/// ignoring case (`usrid` matches `User.id`); an empty query matches
+/// everything. Files that do not parse are skipped. Results are sorted by
+/// name, then path.
+pub fn workspace_symbols(files: &[PathBuf], query: &str) -> Vec<WorkspaceSymbol> {
+    let mut symbols = Vec::new();
+    for path in files {
+        let Ok(source) = fs::read_to_string(path) else {
+            continue;
+        };
+        let Ok(result) = AnalysisService::index_document(&source, &path.display().to_string())
+        else {
+            continue;
+        };
+
+        symbols.extend(
+            result
+                .symbol_table
+                .all_symbols()
+                .filter(|symbol| symbol.kind != SymbolKind::Import)
+                .filter(|symbol| fuzzy_match(&symbol.name, query))
+                .map(|symbol| WorkspaceSymbol {
+                    name: symbol.name.clone(),
+                    kind: symbol.kind,
+                    detail: symbol.detail.clone(),
+                    location: SymbolLocation { path: path.clone(), range: symbol.selection_range },
+                }),
+        );
+    }
+
+    symbols.sort_by(|a, b| {
+        (&a.name, &a.location.path, a.location.range.start.line).cmp(&(
+            &b.name,
+            &b.location.path,
+            b.location.range.start.line,
+        ))
+    });
+    symbols
+}
+
+/// Whether `name` contains the characters of `query` in order, ignoring case.
+fn fuzzy_match(name: &str, query: &str) -> bool {
+    let mut name = name.chars().flat_map(char::to_lowercase);
+    query.chars().flat_map(char::to_lowercase).all(|q| name.any(|c| c == q))
+}
+
+fn collect(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_ref()) {
+                collect(&path, files);
+            }
+        } else if path.extension().is_some_and(|ext| ext == "mon") {
+            files.push(fs::canonicalize(&path).unwrap_or(path));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_mon_files_skips_hidden_and_build_dirs() {
+        let dir = TempDir::new().unwrap();
+        for sub in ["nested", ".git", "target"] {
+            fs::create_dir(dir.path().join(sub)).unwrap();
+            fs::write(dir.path().join(sub).join("a.mon"), "{}").unwrap();
+        }
+        fs::write(dir.path().join("b.mon"), "{}").unwrap();
+        fs::write(dir.path().join("notes.txt"), "").unwrap();
+
+        let root = fs::canonicalize(dir.path()).unwrap();
+        assert_eq!(mon_files(&root), vec![root.join("b.mon"), root.join("nested/a.mon")]);
+    }
+
+    #[test]
+    fn test_workspace_symbols() {
+        let dir = TempDir::new().unwrap();
+        let root = fs::canonicalize(dir.path()).unwrap();
+        fs::write(root.join("a.mon"), "{\n    User: #struct { id(Number) },\n    &user: 1,\n}")
+            .unwrap();
+        fs::write(root.join("b.mon"), "{\n    Status: #enum { Active },\n}").unwrap();
+        fs::write(root.join("broken.mon"), "{ a: ").unwrap();
+        let files = mon_files(&root);
+
+        let all = workspace_symbols(&files, "");
+        let names: Vec<_> = all.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Status", "Status.Active", "User", "User.id", "user"]);
+
+        let found = workspace_symbols(&files, "usrid");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].kind, SymbolKind::Field);
+        assert_eq!(found[0].detail.as_deref(), Some("Number"));
+        assert_eq!(found[0].location.path, root.join("a.mon"));
+        assert_eq!(found[0].location.range.start.line, 1);
+    }
+}
@@ -0,0 +1,180 @@
+//! Conversions between linter types and `lsp_types`.
+//!
+//! The linter's `Position`/`Range` already use zero-based lines and UTF-16
+//! columns, so conversion is a field-by-field copy.
+
+use crate::linter::edit::{TextEdit, WorkspaceEdit};
+use crate::linter::navigation::SymbolLocation;
+use crate::linter::{Diagnostic, DiagnosticSeverity, DiagnosticTag, Position, Range};
+use lsp_types::{DiagnosticRelatedInformation, NumberOrString, Url};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Name reported as the `source` of every published diagnostic.
+pub const DIAGNOSTIC_SOURCE: &str = "mon";
+
+pub fn to_lsp_position(position: Position) -> lsp_types::Position {
+    lsp_types::Position::new(position.line, position.character)
+}
+
+pub fn to_lsp_range(range: Range) -> lsp_types::Range {
+    lsp_types::Range::new(to_lsp_position(range.start), to_lsp_position(range.end))
+}
+
+pub fn from_lsp_position(position: lsp_types::Position) -> Position {
+    Position { line: position.line, character: position.character }
+}
+
+/// Converts a document path back into a URI.
+///
+/// Paths that are not absolute file paths (e.g. an `untitled:` document
+/// analyzed under its URI) are parsed back as URIs.
+pub fn to_uri(path: &Path) -> Option<Url> {
+    Url::from_file_path(path).or_else(|_| Url::parse(&path.display().to_string())).ok()
+}
+
+/// Converts a navigation result into an LSP location.
+pub fn to_lsp_location(location: &SymbolLocation) -> Option<lsp_types::Location> {
+    Some(lsp_types::Location::new(to_uri(&location.path)?, to_lsp_range(location.range)))
+}
+
+pub fn to_lsp_text_edit(edit: &TextEdit) -> lsp_types::TextEdit {
+    lsp_types::TextEdit::new(to_lsp_range(edit.range), edit.new_text.clone())
+}
+
+pub fn to_lsp_workspace_edit(edit: &WorkspaceEdit) -> lsp_types::WorkspaceEdit {
+    let changes: HashMap<_, _> = edit
+        .changes
+        .iter()
+        .filter_map(|(path, edits)| {
+            Some((to_uri(path)?, edits.iter().map(to_lsp_text_edit).collect()))
+        })
+        .collect();
+    lsp_types::WorkspaceEdit::new(changes)
+}
+
+pub fn to_lsp_severity(severity: DiagnosticSeverity) -> lsp_types::DiagnosticSeverity {
+    match severity {
+        DiagnosticSeverity::Error => lsp_types::DiagnosticSeverity::ERROR,
+        DiagnosticSeverity::Warning => lsp_types::DiagnosticSeverity::WARNING,
+        DiagnosticSeverity::Info => lsp_types::DiagnosticSeverity::INFORMATION,
+    }
+}
+
+pub fn to_lsp_tag(tag: DiagnosticTag) -> lsp_types::DiagnosticTag {
+    match tag {
+        DiagnosticTag::Unnecessary => lsp_types::DiagnosticTag::UNNECESSARY,
+        DiagnosticTag::Deprecated => lsp_types::DiagnosticTag::DEPRECATED,
+    }
+}
+
+/// Converts a lint diagnostic into an LSP diagnostic.
+///
+/// Diagnostics without a range are reported at the start of the document.
+/// Related information without a URI is in `uri`, the linted document;
+/// related information whose URI cannot be parsed is dropped.
+pub fn to_lsp_diagnostic(uri: &Url, diagnostic: &Diagnostic) -> lsp_types::Diagnostic {
+    let range = diagnostic.range.map(to_lsp_range).unwrap_or_default();
+
+    let related_information: Vec<_> = diagnostic
+        .related_information
+        .iter()
+        .filter_map(|info| {
+            let uri = match info.location.uri.as_str() {
+                "" => uri.clone(),
+                other => Url::parse(other).ok()?,
+            };
+            Some(DiagnosticRelatedInformation {
+                location: lsp_types::Location::new(uri, to_lsp_range(info.location.range)),
+                message: info.message.clone(),
+            })
+        })
+        .collect();
+
+    lsp_types::Diagnostic {
+        range,
+        severity: Some(to_lsp_severity(diagnostic.severity)),
+        code: Some(NumberOrString::String(diagnostic.code.code().to_string())),
+        code_description: None,
+        source: Some(DIAGNOSTIC_SOURCE.to_string()),
+        message: diagnostic.message.clone(),
+        related_information: (!related_information.is_empty()).then_some(related_information),
+        tags: (!diagnostic.tags.is_empty())
+            .then(|| diagnostic.tags.iter().copied().map(to_lsp_tag).collect()),
+        data: None,
+    }
+}
+
+/// Converts a parse or resolution failure into an LSP error diagnostic.
+///
+/// The first labelled span of the report is used as the range; reports
+/// without labels are placed at the start of the document.
+pub fn report_to_lsp_diagnostic(source: &str, report: &miette::Report) -> lsp_types::Diagnostic {
+    let range = report
+        .labels()
+        .and_then(|mut labels| labels.next())
+        .map(|label| Range::from_byte_offsets(source, label.offset(), label.offset() + label.len()))
+        .map(to_lsp_range)
+        .unwrap_or_default();
+
+    let code = report.code().map(|code| NumberOrString::String(code.to_string()));
+
+    lsp_types::Diagnostic {
+        range,
+        severity: Some(lsp_types::DiagnosticSeverity::ERROR),
+        code,
+        code_description: None,
+        source: Some(DIAGNOSTIC_SOURCE.to_string()),
+        message: report.to_string(),
+        related_information: None,
+        tags: None,
+        data: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::{DiagnosticCode, LintResult, RelatedInformation};
+
+    #[test]
+    fn test_diagnostic_conversion() {
+        let mut result = LintResult::new();
+        result.add_diagnostic_with_range(
+            DiagnosticCode::UnusedAnchor,
+            "Anchor 'foo' is defined but never used".to_string(),
+            Some(Range::new(Position::new(1, 4), Position::new(1, 8))),
+            vec![RelatedInformation::here(
+                Range::new(Position::new(3, 4), Position::new(3, 8)),
+                "'&foo' is also defined here",
+            )],
+            vec![DiagnosticTag::Unnecessary],
+        );
+
+        let uri = Url::parse("file:///tmp/test.mon").unwrap();
+        let lsp = to_lsp_diagnostic(&uri, &result.diagnostics[0]);
+
+        assert_eq!(lsp.range.start, lsp_types::Position::new(1, 4));
+        assert_eq!(lsp.range.end, lsp_types::Position::new(1, 8));
+        assert_eq!(lsp.severity, Some(lsp_types::DiagnosticSeverity::WARNING));
+        assert_eq!(lsp.code, Some(NumberOrString::String("LINT2001".to_string())));
+        assert_eq!(lsp.tags, Some(vec![lsp_types::DiagnosticTag::UNNECESSARY]));
+        assert_eq!(lsp.source.as_deref(), Some("mon"));
+
+        let related = &lsp.related_information.unwrap()[0];
+        assert_eq!(related.location.uri, uri);
+        assert_eq!(related.location.range.start, lsp_types::Position::new(3, 4));
+    }
+
+    #[test]
+    fn test_parse_error_conversion() {
+        let source = "{\n    a: 1\n    b: 2\n}";
+        let report: miette::Report =
+            mon_core::parser::Parser::new(source).unwrap().parse_document().unwrap_err().into();
+
+        let lsp = report_to_lsp_diagnostic(source, &report);
+
+        assert_eq!(lsp.severity, Some(lsp_types::DiagnosticSeverity::ERROR));
+        assert_eq!(lsp.range.start.line, 2);
+    }
+}
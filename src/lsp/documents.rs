@@ -0,0 +1,70 @@
+//! In-memory store of documents opened by the client.
+
+use crate::linter::api::AnalysisResult;
+use lsp_types::Url;
+use std::collections::HashMap;
+
+/// A text document as last reported by the client.
+#[derive(Debug, Clone)]
+pub struct Document {
+    /// Full document text
+    pub text: String,
+
+    /// Client-side version number, incremented on each change
+    pub version: i32,
+
+    /// Analysis of the last text that parsed, or `None` if it never has
+    ///
+    /// Kept across edits that break the syntax, so completion and navigation
+    /// keep working while the user is typing.
+    pub analysis: Option<AnalysisResult>,
+}
+
+/// Open documents, keyed by URI.
+///
+/// The server uses full-text synchronization, so every change replaces the
+/// stored text entirely.
+#[derive(Debug, Default)]
+pub struct DocumentStore {
+    documents: HashMap<Url, Document>,
+}
+
+impl DocumentStore {
+    /// Creates an empty document store.
+    pub fn new() -> Self {
+        Self { documents: HashMap::new() }
+    }
+
+    /// Records a newly opened document.
+    pub fn open(&mut self, uri: Url, text: String, version: i32) {
+        self.documents.insert(uri, Document { text, version, analysis: None });
+    }
+
+    /// Replaces the text of an open document.
+    ///
+    /// Changes to documents that were never opened are stored as if opened,
+    /// so a client that skipped `didOpen` still gets diagnostics.
+    pub fn update(&mut self, uri: Url, text: String, version: i32) {
+        let analysis = self.documents.remove(&uri).and_then(|document| document.analysis);
+        self.documents.insert(uri, Document { text, version, analysis });
+    }
+
+    /// Stores the analysis of a document's current text.
+    ///
+    /// `None` (the text does not parse) keeps the previous analysis.
+    pub fn set_analysis(&mut self, uri: &Url, analysis: Option<AnalysisResult>) {
+        if let (Some(document), Some(analysis)) = (self.documents.get_mut(uri), analysis) {
+            document.analysis = Some(analysis);
+        }
+    }
+
+    /// Forgets a closed document.
+    pub fn close(&mut self, uri: &Url) -> Option<Document> {
+        self.documents.remove(uri)
+    }
+
+    /// Gets an open document by URI.
+    pub fn get(&self, uri: &Url) -> Option<&Document> {
+        self.documents.get(uri)
+    }
+}
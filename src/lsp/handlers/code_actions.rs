@@ -0,0 +1,79 @@
+//! `textDocument/codeAction`: quick fixes attached to lint diagnostics.
+
+use crate::linter::api::AnalysisResult;
+use crate::lsp::convert::{from_lsp_position, to_lsp_diagnostic, to_lsp_text_edit};
+use lsp_types::{CodeAction, CodeActionKind, CodeActionOrCommand, Url, WorkspaceEdit};
+use std::collections::HashMap;
+
+/// Builds a quick fix for every fix of the diagnostics overlapping `range`.
+///
+/// The first fix of each diagnostic is marked as preferred. `analysis` must
+/// be the analysis of the document's current text, or the edits would land
+/// in the wrong places.
+pub fn code_actions(
+    uri: &Url,
+    analysis: &AnalysisResult,
+    range: lsp_types::Range,
+) -> Vec<CodeActionOrCommand> {
+    let (start, end) = (from_lsp_position(range.start), from_lsp_position(range.end));
+
+    analysis
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| {
+            diagnostic.range.is_some_and(|r| {
+                (r.start.line, r.start.character) <= (end.line, end.character)
+                    && (start.line, start.character) <= (r.end.line, r.end.character)
+            })
+        })
+        .flat_map(|diagnostic| {
+            diagnostic.fixes.iter().enumerate().map(move |(i, fix)| {
+                let edits = fix.edits.iter().map(to_lsp_text_edit).collect();
+                CodeActionOrCommand::CodeAction(CodeAction {
+                    title: fix.title.clone(),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![to_lsp_diagnostic(uri, diagnostic)]),
+                    edit: Some(WorkspaceEdit::new(HashMap::from([(uri.clone(), edits)]))),
+                    is_preferred: Some(i == 0),
+                    ..Default::default()
+                })
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintConfig;
+    use crate::linter::api::AnalysisService;
+    use lsp_types::Position;
+
+    #[test]
+    fn test_quick_fixes_for_range() {
+        let source = "{\n    &unused: { a: 1 },\n    k: 1,\n    k: 2,\n}";
+        let analysis = AnalysisService::new(LintConfig::default())
+            .analyze_document(source, "/tmp/actions.mon")
+            .unwrap();
+        let uri = Url::parse("file:///tmp/actions.mon").unwrap();
+
+        let at = |line, character| {
+            let cursor = Position::new(line, character);
+            code_actions(&uri, &analysis, lsp_types::Range::new(cursor, cursor))
+        };
+
+        let actions = at(1, 7);
+        assert_eq!(actions.len(), 1);
+        let CodeActionOrCommand::CodeAction(action) = &actions[0] else {
+            panic!("expected a code action");
+        };
+        assert_eq!(action.title, "Remove unused anchor '&unused'");
+        assert_eq!(action.kind, Some(CodeActionKind::QUICKFIX));
+        let edits = &action.edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+        assert_eq!(edits[0].range.start, Position::new(1, 4));
+        assert_eq!(edits[0].range.end, Position::new(1, 5));
+
+        assert!(at(2, 4).is_empty());
+        assert_eq!(at(3, 4).len(), 1);
+    }
+}
@@ -0,0 +1,99 @@
+//! `textDocument/completion`: anchors, types, enum variants, struct fields
+//! and importable names.
+
+use crate::linter::api::{AnalysisResult, AnalysisService};
+use crate::linter::completion::{self, CompletionKind};
+use crate::linter::navigation::Navigator;
+use crate::lsp::convert::{from_lsp_position, to_lsp_range};
+use crate::lsp::handlers::diagnostics::document_path;
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionTextEdit, Documentation, MarkupContent,
+    MarkupKind, Position, TextEdit, Url,
+};
+
+/// Characters that make the client ask for completions without a word typed.
+pub const TRIGGER_CHARACTERS: [&str; 5] = ["*", ".", ":", "$", "{"];
+
+/// Computes completions at `position` in `text`.
+///
+/// `analysis` is the last successful analysis of the document. A document
+/// that has never parsed still gets completions for built-in types and for
+/// names in `import { ... }`.
+pub fn completion(
+    uri: &Url,
+    text: &str,
+    analysis: Option<&AnalysisResult>,
+    position: Position,
+) -> Vec<CompletionItem> {
+    let fallback;
+    let analysis = match analysis {
+        Some(analysis) => analysis,
+        None => match AnalysisService::index_document("{}", &document_path(uri)) {
+            Ok(empty) => {
+                fallback = empty;
+                &fallback
+            }
+            Err(_) => return Vec::new(),
+        },
+    };
+
+    completion::complete(&mut Navigator::new(analysis), text, from_lsp_position(position))
+        .into_iter()
+        .map(|item| CompletionItem {
+            kind: Some(to_lsp_kind(item.kind)),
+            detail: item.detail,
+            documentation: item.documentation.map(|value| {
+                Documentation::MarkupContent(MarkupContent { kind: MarkupKind::Markdown, value })
+            }),
+            text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                range: to_lsp_range(item.replace_range),
+                new_text: item.insert_text,
+            })),
+            label: item.label,
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn to_lsp_kind(kind: CompletionKind) -> CompletionItemKind {
+    match kind {
+        CompletionKind::Anchor => CompletionItemKind::VARIABLE,
+        CompletionKind::Type => CompletionItemKind::STRUCT,
+        CompletionKind::EnumVariant => CompletionItemKind::ENUM_MEMBER,
+        CompletionKind::Field => CompletionItemKind::FIELD,
+        CompletionKind::Namespace => CompletionItemKind::MODULE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintConfig;
+
+    #[test]
+    fn test_completion_uses_last_analysis() {
+        let uri = Url::parse("file:///tmp/completion.mon").unwrap();
+        let analysis = AnalysisService::new(LintConfig::default())
+            .analyze_document("{ &base: { a: 1 }, copy: {} }", "/tmp/completion.mon")
+            .unwrap();
+
+        // The edited text does not parse yet
+        let text = "{ &base: { a: 1 }, copy: *b";
+        let items = completion(&uri, text, Some(&analysis), Position::new(0, 27));
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].label, "base");
+        assert_eq!(items[0].kind, Some(CompletionItemKind::VARIABLE));
+        let Some(CompletionTextEdit::Edit(edit)) = &items[0].text_edit else {
+            panic!("expected a text edit");
+        };
+        assert_eq!(edit.range.start, Position::new(0, 26));
+        assert_eq!(edit.new_text, "base");
+    }
+
+    #[test]
+    fn test_completion_without_analysis() {
+        let uri = Url::parse("file:///tmp/completion.mon").unwrap();
+        let items = completion(&uri, "{ a :: Bo }", None, Position::new(0, 9));
+        assert_eq!(items.iter().map(|i| i.label.as_str()).collect::<Vec<_>>(), vec!["Boolean"]);
+    }
+}
@@ -0,0 +1,50 @@
+//! `textDocument/definition`: jump from an alias, spread, type annotation or
+//! import specifier to the anchor or type it names.
+
+use crate::linter::api::AnalysisResult;
+use crate::linter::navigation::Navigator;
+use crate::lsp::convert::{from_lsp_position, to_lsp_location};
+use lsp_types::{GotoDefinitionResponse, Position};
+
+/// Finds the definition of the symbol at `position`, following imports into
+/// other files when the symbol is not defined in this document.
+pub fn goto_definition(
+    analysis: &AnalysisResult,
+    position: Position,
+) -> Option<GotoDefinitionResponse> {
+    let definition = Navigator::new(analysis).definition_at(from_lsp_position(position))?;
+    to_lsp_location(&definition.location).map(GotoDefinitionResponse::Scalar)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintConfig;
+    use crate::linter::api::AnalysisService;
+
+    #[test]
+    fn test_goto_local_anchor() {
+        let source = "{\n    &base: { a: 1 },\n    copy: *base,\n}";
+        let analysis = AnalysisService::new(LintConfig::default())
+            .analyze_document(source, "/tmp/definition.mon")
+            .unwrap();
+
+        let Some(GotoDefinitionResponse::Scalar(location)) =
+            goto_definition(&analysis, Position::new(2, 12))
+        else {
+            panic!("expected a single location");
+        };
+        assert_eq!(location.uri.path(), "/tmp/definition.mon");
+        assert_eq!(location.range, lsp_types::Range::new(Position::new(1, 5), Position::new(1, 9)));
+    }
+
+    #[test]
+    fn test_no_symbol_at_position() {
+        let source = "{ a: 1 }";
+        let analysis = AnalysisService::new(LintConfig::default())
+            .analyze_document(source, "/tmp/definition.mon")
+            .unwrap();
+
+        assert!(goto_definition(&analysis, Position::new(0, 2)).is_none());
+    }
+}
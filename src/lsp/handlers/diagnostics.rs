@@ -0,0 +1,84 @@
+//! Diagnostics published on `didOpen` and `didChange`.
+
+use crate::linter::api::{AnalysisResult, AnalysisService};
+use crate::lsp::convert::{report_to_lsp_diagnostic, to_lsp_diagnostic};
+use lsp_types::Url;
+
+/// Analyzes a document and returns the analysis along with the diagnostics
+/// to publish for it.
+///
+/// Lint diagnostics come from `AnalysisService::analyze_document`. If the
+/// document does not parse, no analysis is returned and a single error
+/// diagnostic pointing at the parse failure is published instead.
+pub fn compute(
+    service: &AnalysisService,
+    uri: &Url,
+    text: &str,
+) -> (Option<AnalysisResult>, Vec<lsp_types::Diagnostic>) {
+    let file_path = document_path(uri);
+
+    match service.analyze_document(text, &file_path) {
+        Ok(result) => {
+            let diagnostics =
+                result.diagnostics.iter().map(|d| to_lsp_diagnostic(uri, d)).collect();
+            (Some(result), diagnostics)
+        }
+        Err(report) => (None, vec![report_to_lsp_diagnostic(text, &report)]),
+    }
+}
+
+/// Gets a file-system path for a document URI, falling back to the raw URI
+/// for non-file schemes (e.g. `untitled:`).
+pub fn document_path(uri: &Url) -> String {
+    uri.to_file_path().map(|path| path.display().to_string()).unwrap_or_else(|_| uri.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintConfig;
+
+    fn uri() -> Url {
+        Url::parse("file:///tmp/test.mon").unwrap()
+    }
+
+    #[test]
+    fn test_lint_diagnostics() {
+        let source = r#"{
+    &unused: { value: 1 },
+    key: "v1",
+    key: "v2"
+}"#;
+        let service = AnalysisService::new(LintConfig::default());
+        let (analysis, diagnostics) = compute(&service, &uri(), source);
+
+        assert!(analysis.is_some());
+        assert!(
+            diagnostics
+                .iter()
+                .any(|d| d.code == Some(lsp_types::NumberOrString::String("LINT2002".to_string())))
+        );
+        assert!(
+            diagnostics
+                .iter()
+                .any(|d| d.code == Some(lsp_types::NumberOrString::String("LINT2001".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_parse_error_diagnostic() {
+        let source = "{ a: 1 b: 2 }";
+        let service = AnalysisService::new(LintConfig::default());
+        let (analysis, diagnostics) = compute(&service, &uri(), source);
+
+        assert!(analysis.is_none());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Some(lsp_types::DiagnosticSeverity::ERROR));
+    }
+
+    #[test]
+    fn test_clean_document() {
+        let service = AnalysisService::new(LintConfig::default());
+        assert!(compute(&service, &uri(), "{ name: \"test\" }").1.is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+//! `textDocument/formatting` and `textDocument/rangeFormatting`.
+//!
+//! Both run the same `Formatter` as `mon fmt`, configured by the same
+//! `.monconfig.mon` discovery, and answer with line-level edits rather than
+//! a replacement of the whole document.
+
+use crate::formatter::{FormatConfig, Formatter};
+use crate::linter::edit;
+use crate::lsp::convert::{from_lsp_position, to_lsp_text_edit};
+use lsp_types::{TextEdit, Url};
+use miette::Result;
+use std::path::Path;
+
+/// Creates a formatter configured for a document.
+///
+/// The configuration file is looked up from the document's directory, or
+/// from `fallback_dir` for documents that are not files on disk.
+pub fn formatter_for(uri: &Url, fallback_dir: &Path) -> Result<Formatter> {
+    let path = uri.to_file_path().ok();
+    let dir = path.as_deref().and_then(Path::parent).unwrap_or(fallback_dir);
+    let (config, _) = FormatConfig::discover(dir)?;
+    Ok(Formatter::new(config))
+}
+
+/// Edits that format the whole document, or `None` if it does not parse.
+pub fn format_document(formatter: &Formatter, text: &str) -> Option<Vec<TextEdit>> {
+    let formatted = formatter.format(text).ok()?;
+    Some(edit::diff(text, &formatted).iter().map(to_lsp_text_edit).collect())
+}
+
+/// Edits that format the innermost object or array enclosing `range`, or
+/// `None` if the document does not parse.
+pub fn format_range(
+    formatter: &Formatter,
+    text: &str,
+    range: lsp_types::Range,
+) -> Option<Vec<TextEdit>> {
+    let offset = |position: lsp_types::Position| from_lsp_position(position).to_byte_offset(text);
+    let (start, end) = (offset(range.start), offset(range.end));
+
+    let Some((span, formatted)) = formatter.format_range(text, start, end).ok()? else {
+        return Some(vec![]);
+    };
+
+    let mut result = text.to_string();
+    result.replace_range(span, &formatted);
+    Some(edit::diff(text, &result).iter().map(to_lsp_text_edit).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(text: &str, edits: &[TextEdit]) -> String {
+        let edits: Vec<_> = edits
+            .iter()
+            .map(|e| {
+                edit::TextEdit::new(
+                    crate::linter::Range::new(
+                        from_lsp_position(e.range.start),
+                        from_lsp_position(e.range.end),
+                    ),
+                    e.new_text.clone(),
+                )
+            })
+            .collect();
+        edit::apply(text, &edits)
+    }
+
+    #[test]
+    fn test_format_document_with_minimal_edits() {
+        let formatter = Formatter::new(FormatConfig::default());
+        let text = "{\n    a: 1,\n      b: [2],\n    c: 3,\n}\n";
+
+        let edits = format_document(&formatter, text).unwrap();
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].range.start.line, 2);
+        assert_eq!(apply(text, &edits), formatter.format(text).unwrap());
+
+        assert!(format_document(&formatter, "{ a: ").is_none());
+    }
+
+    #[test]
+    fn test_format_range_reformats_enclosing_value() {
+        let formatter = Formatter::new(FormatConfig::default());
+        let text =
+            "{\n      a: 1,\n    app: {\n            port: 80,\n   host: \"x\",\n    },\n}\n";
+        let cursor = lsp_types::Position::new(3, 14);
+
+        let edits = format_range(&formatter, text, lsp_types::Range::new(cursor, cursor)).unwrap();
+
+        // Only `app` changes; `a` keeps its indentation
+        assert!(edits.iter().all(|edit| edit.range.start.line >= 2));
+        assert_eq!(apply(text, &edits), "{\n      a: 1,\n    app: { port: 80, host: \"x\" },\n}\n");
+    }
+}
@@ -0,0 +1,43 @@
+//! `textDocument/hover`: resolved values, type definitions and doc comments.
+
+use crate::linter::api::AnalysisResult;
+use crate::linter::hover::hover_at;
+use crate::linter::navigation::Navigator;
+use crate::lsp::convert::{from_lsp_position, to_lsp_range};
+use lsp_types::{Hover, HoverContents, MarkupContent, MarkupKind, Position};
+
+/// Builds the hover for the symbol at `position`, as Markdown.
+pub fn hover(analysis: &AnalysisResult, position: Position) -> Option<Hover> {
+    let info = hover_at(&mut Navigator::new(analysis), from_lsp_position(position))?;
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: info.contents,
+        }),
+        range: Some(to_lsp_range(info.range)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintConfig;
+    use crate::linter::api::AnalysisService;
+
+    #[test]
+    fn test_hover_is_markdown() {
+        let source = "{\n    &port: 8080,\n    server: { port: *port },\n}";
+        let analysis = AnalysisService::new(LintConfig::default())
+            .analyze_document(source, "/tmp/hover.mon")
+            .unwrap();
+
+        let hover = hover(&analysis, Position::new(2, 23)).unwrap();
+        let HoverContents::Markup(markup) = hover.contents else {
+            panic!("expected markup contents");
+        };
+        assert_eq!(markup.kind, MarkupKind::Markdown);
+        assert_eq!(markup.value, "```mon\n&port 8080\n```");
+        assert_eq!(hover.range.unwrap().start, Position::new(2, 21));
+    }
+}
@@ -0,0 +1,29 @@
+//! `textDocument/inlayHint`: struct field types and spread sources.
+
+use crate::linter::Range;
+use crate::linter::api::AnalysisResult;
+use crate::linter::inlay_hints::{self, HintKind};
+use crate::linter::navigation::Navigator;
+use crate::lsp::convert::{from_lsp_position, to_lsp_position};
+use lsp_types::{InlayHint, InlayHintKind, InlayHintLabel};
+
+pub fn inlay_hints(analysis: &AnalysisResult, range: lsp_types::Range) -> Vec<InlayHint> {
+    let range = Range::new(from_lsp_position(range.start), from_lsp_position(range.end));
+
+    inlay_hints::inlay_hints(&mut Navigator::new(analysis), Some(range))
+        .into_iter()
+        .map(|hint| InlayHint {
+            position: to_lsp_position(hint.position),
+            label: InlayHintLabel::String(hint.label),
+            kind: match hint.kind {
+                HintKind::Type => Some(InlayHintKind::TYPE),
+                HintKind::Spread => None,
+            },
+            text_edits: None,
+            tooltip: None,
+            padding_left: Some(true),
+            padding_right: None,
+            data: None,
+        })
+        .collect()
+}
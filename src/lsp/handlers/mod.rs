@@ -0,0 +1,17 @@
+//! Request and notification handlers for the language server.
+//!
+//! Each handler is a plain function over the document text and analysis
+//! results, so it can be tested without a client connection.
+
+pub mod code_actions;
+pub mod completion;
+pub mod definition;
+pub mod diagnostics;
+pub mod formatting;
+pub mod hover;
+pub mod inlay_hints;
+pub mod references;
+pub mod rename;
+pub mod semantic_tokens;
+pub mod structure;
+pub mod symbols;
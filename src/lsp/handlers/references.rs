@@ -0,0 +1,42 @@
+//! `textDocument/references`: every usage of the anchor or type at the cursor.
+
+use crate::linter::api::AnalysisResult;
+use crate::linter::navigation::Navigator;
+use crate::lsp::convert::{from_lsp_position, to_lsp_location};
+use lsp_types::{Location, Position};
+
+/// Finds the usages of the symbol at `position` in this document and in the
+/// file that defines it.
+pub fn find_references(
+    analysis: &AnalysisResult,
+    position: Position,
+    include_declaration: bool,
+) -> Vec<Location> {
+    Navigator::new(analysis)
+        .references_at(from_lsp_position(position), include_declaration)
+        .iter()
+        .filter_map(to_lsp_location)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintConfig;
+    use crate::linter::api::AnalysisService;
+
+    #[test]
+    fn test_references_from_definition() {
+        let source = "{\n    &base: { a: 1 },\n    copy: *base,\n    more: { ...*base },\n}";
+        let analysis = AnalysisService::new(LintConfig::default())
+            .analyze_document(source, "/tmp/references.mon")
+            .unwrap();
+
+        let with_declaration = find_references(&analysis, Position::new(1, 6), true);
+        assert_eq!(with_declaration.len(), 3);
+
+        let usages = find_references(&analysis, Position::new(1, 6), false);
+        let lines: Vec<_> = usages.iter().map(|l| l.range.start.line).collect();
+        assert_eq!(lines, vec![2, 3]);
+    }
+}
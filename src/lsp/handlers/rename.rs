@@ -0,0 +1,60 @@
+//! `textDocument/rename`: renames an anchor or type in every file of the
+//! workspace that uses it.
+
+use crate::linter::api::AnalysisResult;
+use crate::linter::navigation::Navigator;
+use crate::linter::rename::rename_at;
+use crate::linter::workspace::mon_files;
+use crate::lsp::convert::{from_lsp_position, to_lsp_workspace_edit};
+use lsp_types::{Position, WorkspaceEdit};
+use miette::Result;
+use std::path::Path;
+
+/// Renames the symbol at `position` to `new_name`.
+///
+/// Every `.mon` file under `workspace_root` is searched for usages; without
+/// a workspace root, the document's own directory is searched.
+pub fn rename(
+    analysis: &AnalysisResult,
+    position: Position,
+    new_name: &str,
+    workspace_root: Option<&Path>,
+) -> Result<WorkspaceEdit> {
+    let mut navigator = Navigator::new(analysis);
+    let root = match workspace_root {
+        Some(root) => root.to_path_buf(),
+        None => navigator.root_path().parent().unwrap_or(Path::new(".")).to_path_buf(),
+    };
+    let workspace = mon_files(&root);
+
+    let edit = rename_at(&mut navigator, from_lsp_position(position), new_name, &workspace)?;
+    Ok(to_lsp_workspace_edit(&edit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintConfig;
+    use crate::linter::api::AnalysisService;
+    use lsp_types::Url;
+
+    #[test]
+    fn test_rename_edits_document() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = std::fs::canonicalize(dir.path()).unwrap();
+        let path = root.join("rename.mon");
+        let source = "{\n    &port: 8080,\n    server: { port: *port },\n}";
+        std::fs::write(&path, source).unwrap();
+        let analysis = AnalysisService::new(LintConfig::default())
+            .analyze_document(source, &path.display().to_string())
+            .unwrap();
+
+        let edit = rename(&analysis, Position::new(2, 23), "http_port", Some(&root)).unwrap();
+        let edits = &edit.changes.unwrap()[&Url::from_file_path(&path).unwrap()];
+        assert_eq!(edits.len(), 2);
+        assert!(edits.iter().all(|edit| edit.new_text == "http_port"));
+
+        let error = rename(&analysis, Position::new(0, 0), "x", Some(&root)).unwrap_err();
+        assert!(error.to_string().contains("no anchor or type"));
+    }
+}
@@ -0,0 +1,111 @@
+//! `textDocument/semanticTokens/full` and `/range`: highlighting from the
+//! parsed document.
+
+use crate::linter::api::AnalysisResult;
+use crate::linter::semantic_tokens::{self, TokenKind, TokenModifier};
+use crate::linter::{Position, Range};
+use crate::lsp::convert::from_lsp_position;
+use lsp_types::{SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokensLegend};
+
+/// Modifier for anchors that are never used; not one of the standard ones.
+const UNUSED: &str = "unused";
+
+/// The token types and modifiers, in the order their indices refer to.
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TokenKind::ALL.iter().map(|kind| token_type(*kind)).collect(),
+        token_modifiers: TokenModifier::ALL.iter().map(|m| token_modifier(*m)).collect(),
+    }
+}
+
+/// Encodes the tokens of a document, or of the part of it inside `range`,
+/// relative to each other as the protocol requires.
+pub fn semantic_tokens(
+    analysis: &AnalysisResult,
+    range: Option<lsp_types::Range>,
+) -> Vec<SemanticToken> {
+    let range = range.map(|r| Range::new(from_lsp_position(r.start), from_lsp_position(r.end)));
+
+    let mut previous = Position::new(0, 0);
+    semantic_tokens::semantic_tokens(analysis, range)
+        .into_iter()
+        .map(|token| {
+            let start = token.range.start;
+            let delta_line = start.line - previous.line;
+            let delta_start = if delta_line == 0 {
+                start.character - previous.character
+            } else {
+                start.character
+            };
+            previous = start;
+
+            SemanticToken {
+                delta_line,
+                delta_start,
+                length: token.range.end.character - start.character,
+                token_type: index_of(&TokenKind::ALL, &token.kind),
+                token_modifiers_bitset: token
+                    .modifiers
+                    .iter()
+                    .fold(0, |bits, m| bits | 1 << index_of(&TokenModifier::ALL, m)),
+            }
+        })
+        .collect()
+}
+
+fn index_of<T: PartialEq>(all: &[T], item: &T) -> u32 {
+    all.iter().position(|x| x == item).unwrap_or_default() as u32
+}
+
+fn token_type(kind: TokenKind) -> SemanticTokenType {
+    match kind {
+        TokenKind::Namespace => SemanticTokenType::NAMESPACE,
+        TokenKind::Type => SemanticTokenType::TYPE,
+        TokenKind::Struct => SemanticTokenType::STRUCT,
+        TokenKind::Enum => SemanticTokenType::ENUM,
+        TokenKind::EnumMember => SemanticTokenType::ENUM_MEMBER,
+        TokenKind::Property => SemanticTokenType::PROPERTY,
+        TokenKind::Variable => SemanticTokenType::VARIABLE,
+        TokenKind::Keyword => SemanticTokenType::KEYWORD,
+        TokenKind::Comment => SemanticTokenType::COMMENT,
+    }
+}
+
+fn token_modifier(modifier: TokenModifier) -> SemanticTokenModifier {
+    match modifier {
+        TokenModifier::Definition => SemanticTokenModifier::DEFINITION,
+        TokenModifier::DefaultLibrary => SemanticTokenModifier::DEFAULT_LIBRARY,
+        TokenModifier::Unused => SemanticTokenModifier::new(UNUSED),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintConfig;
+    use crate::linter::api::AnalysisService;
+
+    #[test]
+    fn test_tokens_are_delta_encoded() {
+        let source = "{\n    &base: 1,\n    copy: *base,\n}";
+        let analysis = AnalysisService::new(LintConfig::default())
+            .analyze_document(source, "/tmp/tokens.mon")
+            .unwrap();
+        let legend = legend();
+        let type_of = |token: &SemanticToken| &legend.token_types[token.token_type as usize];
+
+        let tokens = semantic_tokens(&analysis, None);
+        assert_eq!(tokens.len(), 3);
+
+        // `base` definition on line 1
+        assert_eq!((tokens[0].delta_line, tokens[0].delta_start, tokens[0].length), (1, 5, 4));
+        assert_eq!(type_of(&tokens[0]), &SemanticTokenType::VARIABLE);
+        assert_eq!(tokens[0].token_modifiers_bitset, 1);
+
+        // `copy` key, then `base` alias on the same line
+        assert_eq!((tokens[1].delta_line, tokens[1].delta_start, tokens[1].length), (1, 4, 4));
+        assert_eq!(type_of(&tokens[1]), &SemanticTokenType::PROPERTY);
+        assert_eq!((tokens[2].delta_line, tokens[2].delta_start), (0, 7));
+        assert_eq!(tokens[2].token_modifiers_bitset, 0);
+    }
+}
@@ -0,0 +1,68 @@
+//! `textDocument/foldingRange` and `textDocument/selectionRange`.
+
+use crate::linter::api::AnalysisResult;
+use crate::linter::structure::{self, FoldKind};
+use crate::lsp::convert::{from_lsp_position, to_lsp_range};
+use lsp_types::{FoldingRange, FoldingRangeKind, SelectionRange};
+
+pub fn folding_ranges(analysis: &AnalysisResult) -> Vec<FoldingRange> {
+    structure::folding_ranges(analysis)
+        .into_iter()
+        .map(|fold| FoldingRange {
+            start_line: fold.start_line,
+            end_line: fold.end_line,
+            kind: match fold.kind {
+                FoldKind::Region => None,
+                FoldKind::Comment => Some(FoldingRangeKind::Comment),
+                FoldKind::Imports => Some(FoldingRangeKind::Imports),
+            },
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Builds one chain of selection ranges per position, each link's parent
+/// being the next larger range.
+pub fn selection_ranges(
+    analysis: &AnalysisResult,
+    positions: &[lsp_types::Position],
+) -> Vec<SelectionRange> {
+    positions
+        .iter()
+        .filter_map(|position| {
+            let ranges = structure::selection_ranges(analysis, from_lsp_position(*position));
+            ranges.into_iter().rev().fold(None, |parent, range| {
+                Some(SelectionRange { range: to_lsp_range(range), parent: parent.map(Box::new) })
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintConfig;
+    use crate::linter::api::AnalysisService;
+    use lsp_types::Position;
+
+    #[test]
+    fn test_selection_range_chain() {
+        let source = "{\n    a: { b: 1 },\n}";
+        let analysis = AnalysisService::new(LintConfig::default())
+            .analyze_document(source, "/tmp/selection.mon")
+            .unwrap();
+
+        let ranges = selection_ranges(&analysis, &[Position::new(1, 13)]);
+        assert_eq!(ranges.len(), 1);
+
+        let mut depth = 0;
+        let mut link = Some(&ranges[0]);
+        while let Some(selection) = link {
+            depth += 1;
+            link = selection.parent.as_deref();
+        }
+        // `1`, `b: 1`, `{ b: 1 }`, `a: { b: 1 }`, the document
+        assert_eq!(depth, 5);
+        assert_eq!(ranges[0].range.start, Position::new(1, 12));
+    }
+}
@@ -0,0 +1,116 @@
+//! `textDocument/documentSymbol` (outline) and `workspace/symbol` (search
+//! across every `.mon` file of the workspace).
+
+use crate::linter::SymbolKind;
+use crate::linter::api::AnalysisResult;
+use crate::linter::outline::{OutlineKind, OutlineNode, document_outline};
+use crate::linter::workspace::{mon_files, workspace_symbols};
+use crate::lsp::convert::{to_lsp_location, to_lsp_range};
+use lsp_types::{DocumentSymbol, OneOf, WorkspaceSymbol};
+use std::path::Path;
+
+/// Builds the outline of a document.
+pub fn document_symbols(analysis: &AnalysisResult) -> Vec<DocumentSymbol> {
+    document_outline(analysis).into_iter().map(to_document_symbol).collect()
+}
+
+/// Searches the anchors, types, fields and variants of every `.mon` file
+/// under `root`.
+pub fn search_workspace(root: &Path, query: &str) -> Vec<WorkspaceSymbol> {
+    workspace_symbols(&mon_files(root), query)
+        .into_iter()
+        .filter_map(|symbol| {
+            // `User.id` is shown as `id` inside `User`
+            let (container, name) = match symbol.kind {
+                SymbolKind::Field | SymbolKind::EnumVariant => symbol
+                    .name
+                    .rsplit_once('.')
+                    .map(|(container, name)| (Some(container.to_string()), name.to_string()))
+                    .unwrap_or((None, symbol.name.clone())),
+                _ => (None, symbol.name.clone()),
+            };
+
+            Some(WorkspaceSymbol {
+                name,
+                kind: to_lsp_symbol_kind(symbol.kind),
+                tags: None,
+                container_name: container,
+                location: OneOf::Left(to_lsp_location(&symbol.location)?),
+                data: None,
+            })
+        })
+        .collect()
+}
+
+#[allow(deprecated)]
+fn to_document_symbol(node: OutlineNode) -> DocumentSymbol {
+    DocumentSymbol {
+        name: node.name,
+        detail: node.detail,
+        kind: to_lsp_outline_kind(node.kind),
+        tags: None,
+        deprecated: None,
+        range: to_lsp_range(node.range),
+        selection_range: to_lsp_range(node.selection_range),
+        children: (!node.children.is_empty())
+            .then(|| node.children.into_iter().map(to_document_symbol).collect()),
+    }
+}
+
+fn to_lsp_outline_kind(kind: OutlineKind) -> lsp_types::SymbolKind {
+    match kind {
+        OutlineKind::Import => lsp_types::SymbolKind::MODULE,
+        OutlineKind::Object => lsp_types::SymbolKind::OBJECT,
+        OutlineKind::Array => lsp_types::SymbolKind::ARRAY,
+        OutlineKind::Key => lsp_types::SymbolKind::KEY,
+        OutlineKind::Anchor => lsp_types::SymbolKind::VARIABLE,
+        OutlineKind::Struct => lsp_types::SymbolKind::STRUCT,
+        OutlineKind::Field => lsp_types::SymbolKind::FIELD,
+        OutlineKind::Enum => lsp_types::SymbolKind::ENUM,
+        OutlineKind::EnumVariant => lsp_types::SymbolKind::ENUM_MEMBER,
+    }
+}
+
+fn to_lsp_symbol_kind(kind: SymbolKind) -> lsp_types::SymbolKind {
+    match kind {
+        SymbolKind::Anchor => lsp_types::SymbolKind::VARIABLE,
+        SymbolKind::Type => lsp_types::SymbolKind::STRUCT,
+        SymbolKind::Import => lsp_types::SymbolKind::MODULE,
+        SymbolKind::Field => lsp_types::SymbolKind::FIELD,
+        SymbolKind::EnumVariant => lsp_types::SymbolKind::ENUM_MEMBER,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintConfig;
+    use crate::linter::api::AnalysisService;
+    use std::fs;
+
+    #[test]
+    fn test_document_symbols_are_nested() {
+        let source = "{\n    app: {\n        port: 8080,\n    },\n}";
+        let analysis = AnalysisService::new(LintConfig::default())
+            .analyze_document(source, "/tmp/symbols.mon")
+            .unwrap();
+
+        let symbols = document_symbols(&analysis);
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].kind, lsp_types::SymbolKind::OBJECT);
+        let children = symbols[0].children.as_ref().unwrap();
+        assert_eq!(children[0].name, "port");
+        assert_eq!(children[0].detail.as_deref(), Some("8080"));
+    }
+
+    #[test]
+    fn test_search_workspace_splits_container() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(dir.path().join("a.mon"), "{ User: #struct { id(Number) } }").unwrap();
+
+        let symbols = search_workspace(dir.path(), "user.id");
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].name, "id");
+        assert_eq!(symbols[0].container_name.as_deref(), Some("User"));
+    }
+}
@@ -0,0 +1,29 @@
+//! Language Server Protocol support for MON.
+//!
+//! This module implements a synchronous JSON-RPC language server over stdio,
+//! built on top of the linter's `AnalysisService`. It is started with `mon lsp`
+//! and is intended to be launched by editors (Neovim, Helix, VS Code, ...).
+//!
+//! The server keeps the latest text of every open document and re-analyzes it
+//! on each change, publishing diagnostics back to the client.
+
+pub mod convert;
+pub mod documents;
+pub mod handlers;
+pub mod server;
+pub mod transport;
+
+pub use server::Server;
+
+use miette::Result;
+
+/// Runs the language server on stdin/stdout until the client sends `exit`.
+pub fn run_stdio() -> Result<()> {
+    let (connection, io_threads) = transport::stdio()
+        .map_err(|e| miette::miette!("Failed to set up LSP stdio transport: {}", e))?;
+
+    Server::new(connection).run()?;
+
+    io_threads.join().map_err(|e| miette::miette!("LSP I/O thread failed: {}", e))?;
+    Ok(())
+}
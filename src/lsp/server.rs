@@ -0,0 +1,717 @@
+//! The language server main loop.
+//!
+//! Messages are handled one at a time on the calling thread: requests are
+//! dispatched by method name to the functions in `handlers`, and document
+//! notifications update the `DocumentStore` and republish diagnostics.
+
+use crate::linter::LintConfig;
+use crate::linter::api::AnalysisService;
+use crate::lsp::documents::DocumentStore;
+use crate::lsp::handlers;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response, ResponseError};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Exit, LogMessage,
+    Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{
+    CodeActionRequest, Completion, DocumentSymbolRequest, FoldingRangeRequest, Formatting,
+    GotoDefinition, HoverRequest, InlayHintRequest, RangeFormatting, References, Rename,
+    Request as _, SelectionRangeRequest, SemanticTokensFullRequest, SemanticTokensRangeRequest,
+    WorkspaceSymbolRequest,
+};
+use lsp_types::{
+    CodeActionParams, CodeActionProviderCapability, CompletionOptions, CompletionParams,
+    CompletionResponse, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DocumentFormattingParams, DocumentRangeFormattingParams,
+    DocumentSymbolParams, DocumentSymbolResponse, FoldingRangeParams,
+    FoldingRangeProviderCapability, GotoDefinitionParams, HoverParams, HoverProviderCapability,
+    InitializeParams, InlayHintParams, LogMessageParams, MessageType, OneOf,
+    PublishDiagnosticsParams, ReferenceParams, RenameParams, SelectionRangeParams,
+    SelectionRangeProviderCapability, SemanticTokens, SemanticTokensFullOptions,
+    SemanticTokensOptions, SemanticTokensParams, SemanticTokensRangeParams,
+    SemanticTokensRangeResult, SemanticTokensResult, SemanticTokensServerCapabilities,
+    ServerCapabilities, ServerInfo, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+    WorkspaceSymbolParams, WorkspaceSymbolResponse,
+};
+use miette::Result;
+use std::path::PathBuf;
+
+/// A MON language server bound to a client connection.
+pub struct Server {
+    connection: Connection,
+    documents: DocumentStore,
+    service: AnalysisService,
+    /// Directory searched for importing files on rename and for workspace symbols
+    workspace_root: Option<PathBuf>,
+}
+
+impl Server {
+    /// Creates a server that talks to the client over `connection`.
+    pub fn new(connection: Connection) -> Self {
+        Self {
+            connection,
+            documents: DocumentStore::new(),
+            service: AnalysisService::new(LintConfig::default()),
+            workspace_root: None,
+        }
+    }
+
+    /// Capabilities advertised to the client in the `initialize` response.
+    pub fn capabilities() -> ServerCapabilities {
+        ServerCapabilities {
+            text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+            definition_provider: Some(OneOf::Left(true)),
+            references_provider: Some(OneOf::Left(true)),
+            hover_provider: Some(HoverProviderCapability::Simple(true)),
+            rename_provider: Some(OneOf::Left(true)),
+            document_symbol_provider: Some(OneOf::Left(true)),
+            workspace_symbol_provider: Some(OneOf::Left(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+            document_formatting_provider: Some(OneOf::Left(true)),
+            document_range_formatting_provider: Some(OneOf::Left(true)),
+            folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+            selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
+            inlay_hint_provider: Some(OneOf::Left(true)),
+            semantic_tokens_provider: Some(
+                SemanticTokensServerCapabilities::SemanticTokensOptions(SemanticTokensOptions {
+                    legend: handlers::semantic_tokens::legend(),
+                    full: Some(SemanticTokensFullOptions::Bool(true)),
+                    range: Some(true),
+                    ..Default::default()
+                }),
+            ),
+            completion_provider: Some(CompletionOptions {
+                trigger_characters: Some(
+                    handlers::completion::TRIGGER_CHARACTERS
+                        .iter()
+                        .map(|c| c.to_string())
+                        .collect(),
+                ),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Performs the initialize handshake and serves requests until shutdown.
+    pub fn run(mut self) -> Result<()> {
+        let initialize_result = serde_json::json!({
+            "capabilities": Self::capabilities(),
+            "serverInfo": ServerInfo {
+                name: "mon".to_string(),
+                version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            },
+        });
+
+        let (id, params) = self
+            .connection
+            .initialize_start()
+            .map_err(|e| miette::miette!("LSP initialize failed: {}", e))?;
+        let params: InitializeParams = serde_json::from_value(params)
+            .map_err(|e| miette::miette!("LSP initialize failed: {}", e))?;
+        self.workspace_root = workspace_root(&params);
+        self.connection
+            .initialize_finish(id, initialize_result)
+            .map_err(|e| miette::miette!("LSP initialize failed: {}", e))?;
+        self.load_lint_config()?;
+
+        self.main_loop()
+    }
+
+    /// Lints with the `.moncfg.mon` found from the workspace root, as
+    /// `mon lint` would from there. A config that cannot be read is
+    /// reported to the client and the defaults are used instead.
+    fn load_lint_config(&mut self) -> Result<()> {
+        let config = match self.workspace_dir().and_then(|dir| LintConfig::discover(&dir)) {
+            Ok((config, _)) => config,
+            Err(error) => {
+                self.log(MessageType::ERROR, error.to_string())?;
+                LintConfig::default()
+            }
+        };
+        for warning in &config.warnings {
+            self.log(MessageType::WARNING, warning.clone())?;
+        }
+        self.service = AnalysisService::new(config);
+        Ok(())
+    }
+
+    fn main_loop(&mut self) -> Result<()> {
+        let receiver = self.connection.receiver.clone();
+
+        for message in &receiver {
+            match message {
+                Message::Request(request) => {
+                    let is_shutdown = self
+                        .connection
+                        .handle_shutdown(&request)
+                        .map_err(|e| miette::miette!("LSP shutdown failed: {}", e))?;
+                    if is_shutdown {
+                        return Ok(());
+                    }
+                    self.handle_request(request)?;
+                }
+                Message::Notification(notification) => {
+                    if notification.method == Exit::METHOD {
+                        return Ok(());
+                    }
+                    self.handle_notification(notification)?;
+                }
+                Message::Response(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_request(&mut self, request: Request) -> Result<()> {
+        let id = request.id.clone();
+        let response = self.respond(request).unwrap_or_else(|error| Response {
+            id,
+            result: None,
+            error: Some(error),
+        });
+        self.send(response.into())
+    }
+
+    /// Answers a request. Params that do not deserialize and failures
+    /// outside the handlers are answered with an error response.
+    fn respond(&mut self, request: Request) -> Result<Response, ResponseError> {
+        let id = request.id.clone();
+        let response = match request.method.as_str() {
+            Completion::METHOD => {
+                let params: CompletionParams = extract_request(request)?;
+                let position = params.text_document_position;
+                let uri = &position.text_document.uri;
+                let result = self.documents.get(uri).map(|document| {
+                    CompletionResponse::Array(handlers::completion::completion(
+                        uri,
+                        &document.text,
+                        document.analysis.as_ref(),
+                        position.position,
+                    ))
+                });
+                Response::new_ok(id, result)
+            }
+            CodeActionRequest::METHOD => {
+                let params: CodeActionParams = extract_request(request)?;
+                let uri = &params.text_document.uri;
+                let result = self.current_analysis(uri).map(|analysis| {
+                    handlers::code_actions::code_actions(uri, analysis, params.range)
+                });
+                Response::new_ok(id, result)
+            }
+            Formatting::METHOD => {
+                let params: DocumentFormattingParams = extract_request(request)?;
+                let uri = &params.text_document.uri;
+                match self.formatter_for(uri) {
+                    Ok(formatter) => {
+                        let result = self.documents.get(uri).and_then(|document| {
+                            handlers::formatting::format_document(&formatter, &document.text)
+                        });
+                        Response::new_ok(id, result)
+                    }
+                    Err(error) => {
+                        Response::new_err(id, ErrorCode::RequestFailed as i32, error.to_string())
+                    }
+                }
+            }
+            RangeFormatting::METHOD => {
+                let params: DocumentRangeFormattingParams = extract_request(request)?;
+                let uri = &params.text_document.uri;
+                match self.formatter_for(uri) {
+                    Ok(formatter) => {
+                        let result = self.documents.get(uri).and_then(|document| {
+                            handlers::formatting::format_range(
+                                &formatter,
+                                &document.text,
+                                params.range,
+                            )
+                        });
+                        Response::new_ok(id, result)
+                    }
+                    Err(error) => {
+                        Response::new_err(id, ErrorCode::RequestFailed as i32, error.to_string())
+                    }
+                }
+            }
+            FoldingRangeRequest::METHOD => {
+                let params: FoldingRangeParams = extract_request(request)?;
+                let result = self
+                    .current_analysis(&params.text_document.uri)
+                    .map(handlers::structure::folding_ranges);
+                Response::new_ok(id, result)
+            }
+            SelectionRangeRequest::METHOD => {
+                let params: SelectionRangeParams = extract_request(request)?;
+                let result = self.current_analysis(&params.text_document.uri).map(|analysis| {
+                    handlers::structure::selection_ranges(analysis, &params.positions)
+                });
+                Response::new_ok(id, result)
+            }
+            InlayHintRequest::METHOD => {
+                let params: InlayHintParams = extract_request(request)?;
+                let result = self
+                    .current_analysis(&params.text_document.uri)
+                    .map(|analysis| handlers::inlay_hints::inlay_hints(analysis, params.range));
+                Response::new_ok(id, result)
+            }
+            SemanticTokensFullRequest::METHOD => {
+                let params: SemanticTokensParams = extract_request(request)?;
+                let result = self.current_analysis(&params.text_document.uri).map(|analysis| {
+                    SemanticTokensResult::Tokens(SemanticTokens {
+                        result_id: None,
+                        data: handlers::semantic_tokens::semantic_tokens(analysis, None),
+                    })
+                });
+                Response::new_ok(id, result)
+            }
+            SemanticTokensRangeRequest::METHOD => {
+                let params: SemanticTokensRangeParams = extract_request(request)?;
+                let result = self.current_analysis(&params.text_document.uri).map(|analysis| {
+                    SemanticTokensRangeResult::Tokens(SemanticTokens {
+                        result_id: None,
+                        data: handlers::semantic_tokens::semantic_tokens(
+                            analysis,
+                            Some(params.range),
+                        ),
+                    })
+                });
+                Response::new_ok(id, result)
+            }
+            GotoDefinition::METHOD => {
+                let params: GotoDefinitionParams = extract_request(request)?;
+                let position = params.text_document_position_params;
+                let result = self.analysis(&position.text_document.uri).and_then(|analysis| {
+                    handlers::definition::goto_definition(analysis, position.position)
+                });
+                Response::new_ok(id, result)
+            }
+            HoverRequest::METHOD => {
+                let params: HoverParams = extract_request(request)?;
+                let position = params.text_document_position_params;
+                let result = self
+                    .analysis(&position.text_document.uri)
+                    .and_then(|analysis| handlers::hover::hover(analysis, position.position));
+                Response::new_ok(id, result)
+            }
+            References::METHOD => {
+                let params: ReferenceParams = extract_request(request)?;
+                let position = params.text_document_position;
+                let result = self.analysis(&position.text_document.uri).map(|analysis| {
+                    handlers::references::find_references(
+                        analysis,
+                        position.position,
+                        params.context.include_declaration,
+                    )
+                });
+                Response::new_ok(id, result)
+            }
+            Rename::METHOD => {
+                let params: RenameParams = extract_request(request)?;
+                let position = params.text_document_position;
+                let result = self.analysis(&position.text_document.uri).map(|analysis| {
+                    handlers::rename::rename(
+                        analysis,
+                        position.position,
+                        &params.new_name,
+                        self.workspace_root.as_deref(),
+                    )
+                });
+                match result {
+                    Some(Err(error)) => {
+                        Response::new_err(id, ErrorCode::RequestFailed as i32, error.to_string())
+                    }
+                    result => Response::new_ok(id, result.and_then(Result::ok)),
+                }
+            }
+            DocumentSymbolRequest::METHOD => {
+                let params: DocumentSymbolParams = extract_request(request)?;
+                let result = self.analysis(&params.text_document.uri).map(|analysis| {
+                    DocumentSymbolResponse::Nested(handlers::symbols::document_symbols(analysis))
+                });
+                Response::new_ok(id, result)
+            }
+            WorkspaceSymbolRequest::METHOD => {
+                let params: WorkspaceSymbolParams = extract_request(request)?;
+                let workspace_dir = self.workspace_dir().map_err(request_failed)?;
+                let symbols = handlers::symbols::search_workspace(&workspace_dir, &params.query);
+                Response::new_ok(id, WorkspaceSymbolResponse::Nested(symbols))
+            }
+            _ => Response::new_err(
+                id,
+                ErrorCode::MethodNotFound as i32,
+                format!("Unhandled method: {}", request.method),
+            ),
+        };
+        Ok(response)
+    }
+
+    /// Gets the last successful analysis of an open document.
+    fn analysis(&self, uri: &Url) -> Option<&crate::linter::api::AnalysisResult> {
+        self.documents.get(uri)?.analysis.as_ref()
+    }
+
+    /// Gets the analysis of an open document if it was computed from the
+    /// current text. Edits and token positions are only valid for that text.
+    fn current_analysis(&self, uri: &Url) -> Option<&crate::linter::api::AnalysisResult> {
+        let document = self.documents.get(uri)?;
+        document.analysis.as_ref().filter(|analysis| analysis.source == document.text)
+    }
+
+    /// Creates a formatter with the configuration `mon fmt` would use for
+    /// the document.
+    fn formatter_for(&self, uri: &Url) -> Result<crate::formatter::Formatter> {
+        handlers::formatting::formatter_for(uri, &self.workspace_dir()?)
+    }
+
+    /// Gets the workspace folder, or the working directory without one.
+    fn workspace_dir(&self) -> Result<PathBuf> {
+        match &self.workspace_root {
+            Some(root) => Ok(root.clone()),
+            None => std::env::current_dir()
+                .map_err(|e| miette::miette!("Failed to read current directory: {}", e)),
+        }
+    }
+
+    /// Handles a notification. One the server cannot read is logged to the
+    /// client and otherwise ignored.
+    fn handle_notification(&mut self, notification: Notification) -> Result<()> {
+        match self.notified(notification) {
+            Ok(()) => Ok(()),
+            Err(error) => self.log(MessageType::WARNING, error.to_string()),
+        }
+    }
+
+    fn notified(&mut self, notification: Notification) -> Result<()> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = extract_notification(notification)?;
+                let document = params.text_document;
+                self.documents.open(document.uri.clone(), document.text, document.version);
+                self.publish_diagnostics(document.uri)
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams = extract_notification(notification)?;
+                // Full sync: the last change carries the complete new text
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    let uri = params.text_document.uri;
+                    self.documents.update(uri.clone(), change.text, params.text_document.version);
+                    self.publish_diagnostics(uri)?;
+                }
+                Ok(())
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = extract_notification(notification)?;
+                let uri = params.text_document.uri;
+                self.documents.close(&uri);
+                // Clear diagnostics for the closed document
+                self.send_notification::<PublishDiagnostics>(PublishDiagnosticsParams {
+                    uri,
+                    diagnostics: vec![],
+                    version: None,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn publish_diagnostics(&mut self, uri: Url) -> Result<()> {
+        let Some(document) = self.documents.get(&uri) else {
+            return Ok(());
+        };
+
+        let (analysis, diagnostics) =
+            handlers::diagnostics::compute(&self.service, &uri, &document.text);
+        let version = Some(document.version);
+        self.documents.set_analysis(&uri, analysis);
+
+        self.send_notification::<PublishDiagnostics>(PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version,
+        })
+    }
+
+    fn send_notification<N: lsp_types::notification::Notification>(
+        &self,
+        params: N::Params,
+    ) -> Result<()> {
+        self.send(Notification::new(N::METHOD.to_string(), params).into())
+    }
+
+    /// Sends a message to the client's log.
+    fn log(&self, typ: MessageType, message: String) -> Result<()> {
+        self.send_notification::<LogMessage>(LogMessageParams { typ, message })
+    }
+
+    fn send(&self, message: Message) -> Result<()> {
+        self.connection
+            .sender
+            .send(message)
+            .map_err(|e| miette::miette!("Failed to send LSP message: {}", e))
+    }
+}
+
+/// Gets the workspace directory from the client's `initialize` request:
+/// the first workspace folder, or the deprecated `rootUri`.
+#[allow(deprecated)]
+fn workspace_root(params: &InitializeParams) -> Option<PathBuf> {
+    params
+        .workspace_folders
+        .as_ref()
+        .and_then(|folders| folders.first())
+        .map(|folder| &folder.uri)
+        .or(params.root_uri.as_ref())
+        .and_then(|uri| uri.to_file_path().ok())
+}
+
+fn extract_request<P: serde::de::DeserializeOwned>(request: Request) -> Result<P, ResponseError> {
+    let method = request.method.clone();
+    request.extract(&method).map(|(_, params)| params).map_err(|e| ResponseError {
+        code: ErrorCode::InvalidParams as i32,
+        message: format!("Invalid params for {}: {:?}", method, e),
+        data: None,
+    })
+}
+
+fn request_failed(error: miette::Report) -> ResponseError {
+    ResponseError { code: ErrorCode::RequestFailed as i32, message: error.to_string(), data: None }
+}
+
+fn extract_notification<P: serde::de::DeserializeOwned>(notification: Notification) -> Result<P> {
+    let method = notification.method.clone();
+    notification
+        .extract(&method)
+        .map_err(|e| miette::miette!("Invalid params for {}: {:?}", method, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_server::RequestId;
+    use lsp_types::request::{Initialize, Shutdown};
+    use lsp_types::{InitializeParams, InitializedParams, TextDocumentItem};
+    use std::thread;
+
+    /// Drives a server on an in-memory connection through a short session.
+    struct TestClient {
+        connection: Connection,
+        server: Option<thread::JoinHandle<Result<()>>>,
+        next_id: i32,
+        /// Workspace folder, without a `.moncfg.mon` unless a test writes one
+        workspace: tempfile::TempDir,
+    }
+
+    impl TestClient {
+        fn start() -> Self {
+            Self::start_in(tempfile::TempDir::new().unwrap())
+        }
+
+        fn start_in(workspace: tempfile::TempDir) -> Self {
+            let (server_connection, connection) = Connection::memory();
+            let server = thread::spawn(move || Server::new(server_connection).run());
+            let root_uri = Url::from_directory_path(workspace.path()).unwrap();
+            let mut client = Self { connection, server: Some(server), next_id: 0, workspace };
+
+            #[allow(deprecated)]
+            let params = InitializeParams { root_uri: Some(root_uri), ..Default::default() };
+            let response = client.request(Initialize::METHOD, params);
+            assert!(response.error.is_none());
+            client.notify(lsp_types::notification::Initialized::METHOD, InitializedParams {});
+            client
+        }
+
+        fn request(&mut self, method: &str, params: impl serde::Serialize) -> Response {
+            self.next_id += 1;
+            let id = RequestId::from(self.next_id);
+            let request = Request::new(id.clone(), method.to_string(), params);
+            self.connection.sender.send(request.into()).unwrap();
+
+            loop {
+                match self.connection.receiver.recv().unwrap() {
+                    Message::Response(response) if response.id == id => return response,
+                    _ => continue,
+                }
+            }
+        }
+
+        fn notify(&self, method: &str, params: impl serde::Serialize) {
+            let notification = Notification::new(method.to_string(), params);
+            self.connection.sender.send(notification.into()).unwrap();
+        }
+
+        fn next_notification(&self) -> Notification {
+            loop {
+                if let Message::Notification(notification) =
+                    self.connection.receiver.recv().unwrap()
+                {
+                    return notification;
+                }
+            }
+        }
+
+        fn shutdown(mut self) {
+            self.request(Shutdown::METHOD, ());
+            self.notify(Exit::METHOD, ());
+            self.server.take().unwrap().join().unwrap().unwrap();
+        }
+    }
+
+    fn open(client: &TestClient, uri: &Url, text: &str) {
+        client.notify(
+            DidOpenTextDocument::METHOD,
+            DidOpenTextDocumentParams {
+                text_document: TextDocumentItem::new(
+                    uri.clone(),
+                    "mon".to_string(),
+                    1,
+                    text.to_string(),
+                ),
+            },
+        );
+    }
+
+    fn published(client: &TestClient) -> PublishDiagnosticsParams {
+        let notification = client.next_notification();
+        assert_eq!(notification.method, PublishDiagnostics::METHOD);
+        serde_json::from_value(notification.params).unwrap()
+    }
+
+    #[test]
+    fn test_publishes_diagnostics_on_open_and_change() {
+        let client = TestClient::start();
+        let uri = Url::parse("file:///tmp/test.mon").unwrap();
+
+        open(&client, &uri, "{ key: 1, key: 2 }");
+        let params = published(&client);
+        assert_eq!(params.uri, uri);
+        assert_eq!(params.version, Some(1));
+        assert_eq!(params.diagnostics.len(), 1);
+
+        client.notify(
+            DidChangeTextDocument::METHOD,
+            DidChangeTextDocumentParams {
+                text_document: lsp_types::VersionedTextDocumentIdentifier::new(uri.clone(), 2),
+                content_changes: vec![lsp_types::TextDocumentContentChangeEvent {
+                    range: None,
+                    range_length: None,
+                    text: "{ key: 1 }".to_string(),
+                }],
+            },
+        );
+        let params = published(&client);
+        assert_eq!(params.version, Some(2));
+        assert!(params.diagnostics.is_empty());
+
+        client.notify(
+            DidCloseTextDocument::METHOD,
+            DidCloseTextDocumentParams {
+                text_document: lsp_types::TextDocumentIdentifier::new(uri.clone()),
+            },
+        );
+        assert!(published(&client).diagnostics.is_empty());
+
+        client.shutdown();
+    }
+
+    #[test]
+    fn test_definition_and_references_requests() {
+        let mut client = TestClient::start();
+        let uri = Url::parse("file:///tmp/navigation.mon").unwrap();
+
+        open(&client, &uri, "{\n    &base: { a: 1 },\n    copy: *base,\n}");
+        published(&client);
+
+        let position = lsp_types::TextDocumentPositionParams::new(
+            lsp_types::TextDocumentIdentifier::new(uri.clone()),
+            lsp_types::Position::new(2, 12),
+        );
+        let response = client.request(
+            GotoDefinition::METHOD,
+            GotoDefinitionParams {
+                text_document_position_params: position.clone(),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            },
+        );
+        let definition: lsp_types::Location =
+            serde_json::from_value(response.result.unwrap()).unwrap();
+        assert_eq!(definition.uri, uri);
+        assert_eq!(definition.range.start, lsp_types::Position::new(1, 5));
+
+        let response = client.request(
+            References::METHOD,
+            ReferenceParams {
+                text_document_position: position,
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+                context: lsp_types::ReferenceContext { include_declaration: true },
+            },
+        );
+        let references: Vec<lsp_types::Location> =
+            serde_json::from_value(response.result.unwrap()).unwrap();
+        assert_eq!(references.len(), 2);
+
+        client.shutdown();
+    }
+
+    fn log_message(client: &TestClient) -> LogMessageParams {
+        let notification = client.next_notification();
+        assert_eq!(notification.method, LogMessage::METHOD);
+        serde_json::from_value(notification.params).unwrap()
+    }
+
+    #[test]
+    fn test_lints_with_workspace_config() {
+        let workspace = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            workspace.path().join(".moncfg.mon"),
+            "{ linter: { max_array_elements: 1 } }",
+        )
+        .unwrap();
+        let client = TestClient::start_in(workspace);
+
+        let message = log_message(&client);
+        assert_eq!(message.typ, MessageType::WARNING);
+        assert!(message.message.contains("'max_array_elements' is deprecated"));
+
+        let uri = Url::from_file_path(client.workspace.path().join("list.mon")).unwrap();
+        open(&client, &uri, "{ items: [1, 2] }");
+        let params = published(&client);
+        assert_eq!(params.diagnostics.len(), 1);
+        assert_eq!(
+            params.diagnostics[0].code,
+            Some(lsp_types::NumberOrString::String("LINT1003".to_string()))
+        );
+
+        client.shutdown();
+    }
+
+    #[test]
+    fn test_invalid_params_do_not_stop_the_server() {
+        let mut client = TestClient::start();
+
+        let response = client.request(HoverRequest::METHOD, serde_json::json!({ "line": 1 }));
+        assert_eq!(response.error.unwrap().code, ErrorCode::InvalidParams as i32);
+
+        client.notify(DidOpenTextDocument::METHOD, serde_json::json!({}));
+        assert_eq!(log_message(&client).typ, MessageType::WARNING);
+
+        let uri = Url::from_file_path(client.workspace.path().join("test.mon")).unwrap();
+        open(&client, &uri, "{ key: 1, key: 2 }");
+        assert_eq!(published(&client).diagnostics.len(), 1);
+
+        client.shutdown();
+    }
+
+    #[test]
+    fn test_unknown_request_is_rejected() {
+        let mut client = TestClient::start();
+
+        let response = client.request("mon/unknown", ());
+        assert_eq!(response.error.unwrap().code, ErrorCode::MethodNotFound as i32);
+
+        client.shutdown();
+    }
+}
@@ -0,0 +1,90 @@
+//! Stdio transport for the language server.
+//!
+//! mon-core's parser prints a debugging line to stdout whenever it meets an
+//! unexpected token. With lsp-server's own stdio transport, whose writer
+//! thread holds the stdout lock for the whole session, that print blocks the
+//! server forever on the first syntax error; without the lock it would
+//! corrupt the JSON-RPC stream instead.
+//!
+//! This transport writes protocol messages to a duplicate of the original
+//! stdout and, on Unix, points file descriptor 1 at stderr, so anything else
+//! printed to stdout ends up in the client's log.
+
+use crossbeam_channel::bounded;
+use lsp_server::{Connection, Message};
+use std::io::{self, BufWriter, Write};
+use std::thread;
+
+/// The reader and writer threads behind a stdio `Connection`.
+pub struct IoThreads {
+    reader: thread::JoinHandle<io::Result<()>>,
+    writer: thread::JoinHandle<io::Result<()>>,
+}
+
+impl IoThreads {
+    /// Waits for both threads, once the client has sent `exit` and the
+    /// connection has been dropped.
+    pub fn join(self) -> io::Result<()> {
+        for handle in [self.reader, self.writer] {
+            match handle.join() {
+                Ok(result) => result?,
+                Err(panic) => std::panic::resume_unwind(panic),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Creates a connection that reads from stdin and writes to stdout.
+pub fn stdio() -> io::Result<(Connection, IoThreads)> {
+    let mut output = protocol_output()?;
+
+    let (writer_sender, writer_receiver) = bounded::<Message>(0);
+    let writer = thread::Builder::new().name("LspServerWriter".to_string()).spawn(move || {
+        writer_receiver.into_iter().try_for_each(|message| message.write(&mut output))
+    })?;
+
+    let (reader_sender, reader_receiver) = bounded::<Message>(0);
+    let reader = thread::Builder::new().name("LspServerReader".to_string()).spawn(move || {
+        let mut stdin = io::stdin().lock();
+        while let Some(message) = Message::read(&mut stdin)? {
+            let is_exit = matches!(&message, Message::Notification(n) if n.method == "exit");
+            reader_sender.send(message).map_err(io::Error::other)?;
+            if is_exit {
+                break;
+            }
+        }
+        Ok(())
+    })?;
+
+    let connection = Connection { sender: writer_sender, receiver: reader_receiver };
+    Ok((connection, IoThreads { reader, writer }))
+}
+
+/// Takes over the original stdout for protocol messages and sends every
+/// other write to fd 1 to stderr.
+#[cfg(unix)]
+fn protocol_output() -> io::Result<BufWriter<std::fs::File>> {
+    use std::os::fd::FromRawFd;
+
+    io::stdout().flush()?;
+
+    // SAFETY: `dup` returns a new descriptor that nothing else owns, which is
+    // handed to the `File`; `dup2` only re-targets the process's fd 1.
+    unsafe {
+        let protocol = libc::dup(libc::STDOUT_FILENO);
+        if protocol < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(BufWriter::new(std::fs::File::from_raw_fd(protocol)))
+    }
+}
+
+/// Writes protocol messages to stdout, locking it per message.
+#[cfg(not(unix))]
+fn protocol_output() -> io::Result<BufWriter<io::Stdout>> {
+    Ok(BufWriter::new(io::stdout()))
+}
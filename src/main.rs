@@ -6,6 +6,7 @@ mod commands;
 mod errors;
 mod formatter;
 mod linter;
+mod lsp;
 
 #[derive(Parser)]
 #[command(name = "mon")]
@@ -91,10 +92,13 @@ enum Commands {
         /// Auto-fix issues where possible
         #[arg(long, short)]
         fix: bool,
+        /// Print the fixes as a unified diff instead of writing them
+        #[arg(long, conflicts_with = "fix")]
+        fix_dry_run: bool,
         /// Configuration file path
         #[arg(long)]
         config: Option<String>,
-        /// Output format (text/json/sarif)
+        /// Output format (text/json/sarif/github/gitlab/junit/checkstyle)
         #[arg(long, default_value = "text")]
         format: String,
         /// Comma-separated rule IDs to enable
@@ -103,6 +107,21 @@ enum Commands {
         /// Comma-separated rule IDs to disable
         #[arg(long)]
         no_rules: Option<String>,
+        /// Report these rules as errors (comma-separated, repeatable)
+        #[arg(long, short = 'D', value_delimiter = ',')]
+        deny: Vec<String>,
+        /// Report these rules as warnings (comma-separated, repeatable)
+        #[arg(long, short = 'W', value_delimiter = ',')]
+        warn: Vec<String>,
+        /// Turn these rules off (comma-separated, repeatable)
+        #[arg(long, short = 'A', value_delimiter = ',')]
+        allow: Vec<String>,
+        /// Hide the diagnostics recorded in this baseline file
+        #[arg(long)]
+        baseline: Option<String>,
+        /// Record the current diagnostics in this baseline file
+        #[arg(long, conflicts_with_all = ["baseline", "fix_dry_run"])]
+        write_baseline: Option<String>,
     },
     /// Resolve imports and create a single bundled file
     Bundle {
@@ -121,6 +140,57 @@ enum Commands {
         #[arg(long)]
         tree_shake: bool,
     },
+    /// Explain a lint code or error code, with an example
+    Explain {
+        /// The code (LINT2001, mon::syntax_error) or rule name (unused_anchor)
+        #[arg(required_unless_present = "list")]
+        code: Option<String>,
+        /// List every code
+        #[arg(long)]
+        list: bool,
+        /// Output format (text/json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+    /// Start the MON language server (JSON-RPC over stdio)
+    Lsp,
+    /// Find the definition and all usages of an anchor or type
+    Refs {
+        /// The MON file containing the symbol
+        file: String,
+        /// Position of the symbol as <line>:<col> (one-based)
+        position: String,
+        /// Output results as JSON
+        #[arg(long)]
+        as_json: bool,
+    },
+    /// Rename an anchor or type and every usage across the workspace
+    Rename {
+        /// The MON file that defines or imports the symbol
+        file: String,
+        /// Current name (prefix with & to pick the anchor when a type shares the name)
+        old: String,
+        /// New name
+        new: String,
+        /// Workspace root searched for importing files (default: current directory)
+        #[arg(long)]
+        root: Option<String>,
+        /// Print the edits without writing any file
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Print the outline of a MON file, or search the symbols of a directory
+    Symbols {
+        /// A MON file (outline) or a directory (all symbols of its .mon files)
+        #[arg(default_value = ".")]
+        path: String,
+        /// Only list symbols whose name fuzzy-matches this query
+        #[arg(long, short)]
+        query: Option<String>,
+        /// Output results as JSON
+        #[arg(long)]
+        as_json: bool,
+    },
 }
 
 fn main() -> Result<()> {
@@ -164,13 +234,49 @@ fn main() -> Result<()> {
             commands::completions::run(shell)?;
             Ok(())
         }
-        Commands::Lint { files, fix, config, format, rules, no_rules } => {
-            let rules_vec = rules.map(|r| r.split(',').map(String::from).collect());
-            let no_rules_vec = no_rules.map(|r| r.split(',').map(String::from).collect());
-            commands::lint::run(files, fix, config, format, rules_vec, no_rules_vec)
+        Commands::Lint {
+            files,
+            fix,
+            fix_dry_run,
+            config,
+            format,
+            rules,
+            no_rules,
+            deny,
+            warn,
+            allow,
+            baseline,
+            write_baseline,
+        } => {
+            let rule_options = commands::lint::RuleOptions {
+                rules: rules.map(|r| r.split(',').map(String::from).collect()),
+                no_rules: no_rules.map(|r| r.split(',').map(String::from).collect()),
+                deny,
+                warn,
+                allow,
+            };
+            let baseline_options = commands::lint::BaselineOptions { baseline, write_baseline };
+            commands::lint::run(
+                files,
+                fix,
+                fix_dry_run,
+                config,
+                format,
+                rule_options,
+                baseline_options,
+            )
         }
         Commands::Bundle { entry_file, output, to, minify, tree_shake } => {
             commands::bundle::run(entry_file, output, to, minify, tree_shake)
         }
+        Commands::Explain { code, list, format } => commands::explain::run(code, list, format),
+        Commands::Lsp => commands::lsp::run(),
+        Commands::Refs { file, position, as_json } => {
+            commands::refs::run(&file, &position, as_json)
+        }
+        Commands::Rename { file, old, new, root, dry_run } => {
+            commands::rename::run(&file, &old, &new, root, dry_run)
+        }
+        Commands::Symbols { path, query, as_json } => commands::symbols::run(&path, query, as_json),
     }
 }
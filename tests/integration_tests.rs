@@ -1,4 +1,9 @@
+// The tests written before `cargo_bin_cmd!` still use the deprecated
+// `Command::cargo_bin`
+#![allow(deprecated, clippy::needless_borrows_for_generic_args)]
+
 use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
 use predicates::prelude::*;
 
 #[test]
@@ -230,3 +235,456 @@ fn test_compile_json_schema_primitives() {
         .stdout(predicate::str::contains("\"type\": \"number\""))
         .stdout(predicate::str::contains("\"type\": \"boolean\""));
 }
+
+fn lsp_frame(body: &str) -> String {
+    format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+}
+
+#[test]
+fn test_lsp_initialize_and_shutdown() {
+    let input = [
+        r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"capabilities":{}}}"#,
+        r#"{"jsonrpc":"2.0","method":"initialized","params":{}}"#,
+        r#"{"jsonrpc":"2.0","id":2,"method":"shutdown"}"#,
+        r#"{"jsonrpc":"2.0","method":"exit"}"#,
+    ]
+    .iter()
+    .map(|body| lsp_frame(body))
+    .collect::<String>();
+
+    cargo_bin_cmd!("mon")
+        .arg("lsp")
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"capabilities\""))
+        .stdout(predicate::str::contains("\"textDocumentSync\":1"));
+}
+
+#[test]
+fn test_refs_follows_named_import() {
+    cargo_bin_cmd!("mon")
+        .args(["refs", "tests/tests/named_import_main.mon", "4:14"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("named_import_schemas.mon:2:5: definition"))
+        .stdout(predicate::str::contains("named_import_main.mon:1:10: reference"))
+        .stdout(predicate::str::contains("named_import_main.mon:4:14: reference"));
+}
+
+#[test]
+fn test_refs_follows_namespace_import() {
+    cargo_bin_cmd!("mon")
+        .args(["refs", "tests/tests/cross_file_main.mon", "4:22", "--as-json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"symbol\": \"User\""))
+        .stdout(predicate::str::contains("cross_file_schemas.mon"));
+}
+
+#[test]
+fn test_refs_rejects_bad_position() {
+    cargo_bin_cmd!("mon")
+        .args(["refs", "tests/tests/named_import_main.mon", "0:1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid position"));
+}
+
+#[test]
+fn test_rename_dry_run_lists_edits() {
+    cargo_bin_cmd!("mon")
+        .args(["rename", "tests/tests/named_import_schemas.mon", "User", "Account"])
+        .args(["--root", "tests/tests", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("named_import_schemas.mon:2:5: User -> Account"))
+        .stdout(predicate::str::contains("named_import_main.mon:1:10: User -> Account"));
+}
+
+#[test]
+fn test_rename_writes_importing_files() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("base.mon"), "{\n    &base: { a: 1 },\n}").unwrap();
+    std::fs::write(
+        dir.path().join("app.mon"),
+        "import { &base } from \"./base.mon\"\n{\n    app: { ...*base },\n}",
+    )
+    .unwrap();
+
+    cargo_bin_cmd!("mon")
+        .current_dir(dir.path())
+        .args(["rename", "base.mon", "base", "defaults"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("3 edit(s) in 2 file(s)"));
+
+    let app = std::fs::read_to_string(dir.path().join("app.mon")).unwrap();
+    assert_eq!(app, "import { &defaults } from \"./base.mon\"\n{\n    app: { ...*defaults },\n}");
+}
+
+#[test]
+fn test_rename_refuses_collision() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("a.mon"), "{\n    &one: 1,\n    &two: 2,\n}").unwrap();
+
+    cargo_bin_cmd!("mon")
+        .current_dir(dir.path())
+        .args(["rename", "a.mon", "one", "two"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("anchor 'two' already exists"));
+}
+
+#[test]
+fn test_symbols_prints_outline() {
+    cargo_bin_cmd!("mon")
+        .args(["symbols", "tests/tests/named_import_schemas.mon"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("User (struct) #struct"))
+        .stdout(predicate::str::contains("name (field) String"));
+}
+
+#[test]
+fn test_symbols_query_searches_directory() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("a.mon"),
+        "{\n    Status: #enum { Active, Inactive },\n    &settings: { a: 1 },\n}",
+    )
+    .unwrap();
+
+    cargo_bin_cmd!("mon")
+        .current_dir(dir.path())
+        .args(["symbols", ".", "--query", "stat"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("a.mon:2:5: Status (type)"))
+        .stdout(predicate::str::contains("a.mon:2:21: Status.Active (variant)"))
+        .stdout(predicate::str::contains("settings").not());
+}
+
+#[test]
+fn test_lint_fix_writes_fixed_file() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("a.mon");
+    std::fs::write(&file, "{\n    &unused: 1,\n    k: 1,\n    k: 2,\n}").unwrap();
+
+    cargo_bin_cmd!("mon")
+        .current_dir(dir.path())
+        .args(["lint", "--fix", "a.mon"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fixed 1 issue(s) in a.mon"));
+
+    // Other files may import '&unused', so it stays
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "{\n    &unused: 1,\n    k: 2,\n}");
+}
+
+#[test]
+fn test_lint_fix_keeps_comments_of_next_member() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("a.mon");
+    let source =
+        "{\n    timeout: 30,\n    // Timeout in seconds, agreed with ops\n    timeout: 60,\n}";
+    std::fs::write(&file, source).unwrap();
+
+    cargo_bin_cmd!("mon")
+        .current_dir(dir.path())
+        .args(["lint", "--fix", "a.mon"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fixed 1 issue(s) in a.mon"));
+
+    assert_eq!(
+        std::fs::read_to_string(&file).unwrap(),
+        "{\n    // Timeout in seconds, agreed with ops\n    timeout: 60,\n}"
+    );
+}
+
+#[test]
+fn test_lint_fix_dry_run_prints_diff() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("a.mon");
+    let source = "{\n    k: 1,\n    k: 2,\n}";
+    std::fs::write(&file, source).unwrap();
+
+    cargo_bin_cmd!("mon")
+        .current_dir(dir.path())
+        .args(["lint", "--fix-dry-run", "a.mon"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("--- a/a.mon\n+++ b/a.mon\n"))
+        .stdout(predicate::str::contains("-    k: 1,\n"))
+        .stderr(predicate::str::contains("Would fix 1 issue(s) in a.mon"));
+
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), source);
+}
+
+#[test]
+fn test_lint_rules_filters_checks() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("a.mon"), "{\n    &unused: 1,\n    k: 1,\n    k: 2,\n}")
+        .unwrap();
+
+    cargo_bin_cmd!("mon")
+        .current_dir(dir.path())
+        .args(["lint", "--rules", "duplicate_key", "a.mon"])
+        .assert()
+        .stdout(predicate::str::contains("LINT2002"))
+        .stdout(predicate::str::contains("LINT2001").not());
+
+    cargo_bin_cmd!("mon")
+        .current_dir(dir.path())
+        .args(["lint", "--no-rules", "LINT2*", "a.mon"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("LINT2").not());
+}
+
+#[test]
+fn test_lint_rejects_unknown_rule() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("a.mon"), "{ a: 1 }").unwrap();
+
+    cargo_bin_cmd!("mon")
+        .current_dir(dir.path())
+        .args(["lint", "--rules", "LINT2001,no_such_rule", "a.mon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown lint rule 'no_such_rule'"));
+}
+
+#[test]
+fn test_check_lint_uses_moncfg() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("a.mon"), "{\n    k: 1,\n    k: 2,\n}").unwrap();
+    std::fs::write(
+        dir.path().join(".moncfg.mon"),
+        "{\n    linter: { disabled_rules: [\"duplicate_key\"] },\n}",
+    )
+    .unwrap();
+
+    cargo_bin_cmd!("mon")
+        .current_dir(dir.path())
+        .args(["check", "--lint", "a.mon"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No issues found"));
+}
+
+#[test]
+fn test_lint_config_suggests_known_key() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("a.mon"), "{ a: 1 }").unwrap();
+    std::fs::write(dir.path().join(".moncfg.mon"), "{\n    linter: { max_spreads_per_obj: 2 },\n}")
+        .unwrap();
+
+    cargo_bin_cmd!("mon")
+        .current_dir(dir.path())
+        .args(["lint", "a.mon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown lint config key 'max_spreads_per_obj'"))
+        .stderr(predicate::str::contains("Did you mean 'max_spreads_per_object'?"));
+}
+
+#[test]
+fn test_lint_config_accepts_deprecated_keys() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("a.mon"), "{ list: [1, 2] }").unwrap();
+    std::fs::write(
+        dir.path().join(".moncfg.mon"),
+        "{\n    linter: { max_array_elements: 1, max_spreads: 3 },\n}",
+    )
+    .unwrap();
+
+    // Keys written by older `mon init` still apply, with a warning
+    cargo_bin_cmd!("mon")
+        .current_dir(dir.path())
+        .args(["lint", "a.mon"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("LINT1003"))
+        .stderr(predicate::str::contains(
+            "Lint config key 'max_array_elements' is deprecated, use 'max_array_items' instead",
+        ));
+}
+
+#[test]
+fn test_lint_severity_flags() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("a.mon"), "{\n    k: 1,\n    k: 0,\n    port: 8080,\n}")
+        .unwrap();
+
+    // A duplicate key is an error unless allowed
+    cargo_bin_cmd!("mon")
+        .current_dir(dir.path())
+        .args(["lint", "-A", "duplicate_key", "a.mon"])
+        .assert()
+        .success();
+
+    cargo_bin_cmd!("mon")
+        .current_dir(dir.path())
+        .args(["lint", "--warn", "LINT2002", "--deny", "magic_number", "a.mon"])
+        .assert()
+        .code(4)
+        .stdout(predicate::str::contains("LINT2004"));
+}
+
+#[test]
+fn test_lint_text_output_renders_code_frames() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("a.mon"), "{\n    k: 1,\n    k: 2,\n}").unwrap();
+
+    cargo_bin_cmd!("mon")
+        .current_dir(dir.path())
+        .args(["lint", "a.mon"])
+        .assert()
+        .code(4)
+        .stdout(predicate::str::contains("[a.mon:3:5]"))
+        .stdout(predicate::str::contains(" 3 │     k: 2,"))
+        .stdout(predicate::str::contains("'k' is first defined here"))
+        .stdout(predicate::str::contains("help: Fix available: Remove the earlier 'k'"));
+}
+
+#[test]
+fn test_lint_sarif_output_matches_schema() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("a.mon"),
+        "{\n    &unused: 1,\n    k: 1,\n    k: 1,\n    port: 8080,\n    empty: {},\n}",
+    )
+    .unwrap();
+
+    let output = cargo_bin_cmd!("mon")
+        .current_dir(dir.path())
+        .args(["lint", "--format", "sarif", "--deny", "magic_number", "a.mon"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(4));
+    let log: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    // The OASIS schema, vendored unchanged from
+    // https://docs.oasis-open.org/sarif/sarif/v2.1.0/errata01/os/schemas/sarif-schema-2.1.0.json
+    // Without it, the excerpt of its definitions that mon writes is used.
+    let tests = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+    let schema = std::fs::read_to_string(tests.join("sarif-schema-2.1.0.json"))
+        .or_else(|_| std::fs::read_to_string(tests.join("sarif-schema-2.1.0-excerpt.json")))
+        .unwrap();
+    let schema: serde_json::Value = serde_json::from_str(&schema).unwrap();
+    let validator = jsonschema::options().should_validate_formats(true).build(&schema).unwrap();
+    let errors: Vec<_> = validator.iter_errors(&log).map(|e| e.to_string()).collect();
+    assert!(errors.is_empty(), "{:#?}", errors);
+
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    let rule_ids: Vec<_> = results.iter().map(|r| r["ruleId"].as_str().unwrap()).collect();
+    assert_eq!(rule_ids, vec!["LINT2001", "LINT2004", "LINT2002", "LINT3003"]);
+    // An overridden severity shows in the result's level
+    assert_eq!(results[1]["level"], "error");
+}
+
+#[test]
+fn test_lint_ci_formats() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("a.mon"), "{\n    k: 1,\n    k: 2,\n}").unwrap();
+    std::fs::write(dir.path().join("b.mon"), "{ a: 1 }").unwrap();
+    let lint = |format: &str| {
+        cargo_bin_cmd!("mon")
+            .current_dir(dir.path())
+            .args(["lint", "--format", format, "a.mon", "b.mon"])
+            .assert()
+            .code(4)
+    };
+
+    lint("github").stdout(
+        "::error file=a.mon,line=3,col=5,endLine=3,endColumn=5,\
+         title=LINT2002 Duplicate object key::Duplicate key 'k' in object\n",
+    );
+    lint("checkstyle")
+        .stdout(predicate::str::contains(
+            "<error line=\"3\" column=\"5\" severity=\"error\" \
+             message=\"Duplicate key 'k' in object\" source=\"mon.LINT2002\"/>",
+        ))
+        .stdout(predicate::str::contains("<file name=\"b.mon\">"));
+    lint("junit")
+        .stdout(predicate::str::contains("tests=\"2\" failures=\"1\""))
+        .stdout(predicate::str::contains("<testcase name=\"b.mon\" classname=\"mon lint\"/>"));
+
+    let output = lint("gitlab").get_output().stdout.clone();
+    let issues: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(issues[0]["check_name"], "LINT2002");
+    assert_eq!(issues[0]["severity"], "major");
+    assert_eq!(issues[0]["location"]["positions"]["begin"]["line"], 3);
+
+    cargo_bin_cmd!("mon")
+        .current_dir(dir.path())
+        .args(["lint", "--format", "xml", "a.mon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown output format 'xml'"));
+}
+
+#[test]
+fn test_explain_codes() {
+    cargo_bin_cmd!("mon")
+        .args(["explain", "lint2001"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("LINT2001: Unused anchor definition"))
+        .stdout(predicate::str::contains("Config key:       warn_unused_anchors"))
+        .stdout(predicate::str::contains("Good:\n    {\n        &defaults: { port: 80 },"));
+
+    cargo_bin_cmd!("mon")
+        .args(["explain", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("LINT4003"))
+        .stdout(predicate::str::contains("mon::validation_error  error"));
+
+    let output = cargo_bin_cmd!("mon")
+        .args(["explain", "--list", "--format", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let codes: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(codes[0]["code"], "LINT1001");
+    assert_eq!(codes[0]["config_key"], "max_nesting_depth");
+    assert_eq!(codes.as_array().unwrap().last().unwrap()["code"], "mon::validation_error");
+
+    cargo_bin_cmd!("mon")
+        .args(["explain", "LINT9999"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown code 'LINT9999'"));
+}
+
+#[test]
+fn test_lint_baseline() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("a.mon");
+    std::fs::write(&file, "{\n    &a: 1,\n    k: 1,\n    k: 2,\n}").unwrap();
+    let lint = |args: &[&str]| {
+        cargo_bin_cmd!("mon").current_dir(dir.path()).arg("lint").args(args).arg("a.mon").assert()
+    };
+
+    lint(&["--write-baseline", ".monlint-baseline.json"])
+        .success()
+        .stdout(predicate::str::contains("Wrote 2 diagnostic(s)"));
+    lint(&["--baseline", ".monlint-baseline.json", "--format", "github"])
+        .success()
+        .stdout("")
+        .stderr("");
+
+    // The duplicate key is fixed and a new anchor is added above the old one
+    std::fs::write(&file, "{\n    &b: 2,\n    &a: 1,\n    k: 2,\n}").unwrap();
+    lint(&["--baseline", ".monlint-baseline.json", "--format", "github"])
+        .success()
+        .stdout(predicate::str::contains("Anchor 'b' is defined but never used"))
+        .stdout(predicate::str::contains("Anchor 'a'").not())
+        .stderr(predicate::str::contains("1 baseline entry(ies) no longer match"))
+        .stderr(predicate::str::contains("a.mon: LINT2002 Duplicate key 'k' in object"));
+}